@@ -1,4 +1,6 @@
 use super::error::RuntimeError;
+use super::float::Float;
+use alloc::vec::Vec;
 use core::fmt::Debug;
 
 /// Specify the tolerance which must be satisfied after calculation
@@ -6,18 +8,18 @@ use core::fmt::Debug;
 /// It is recommended to Relative tolerance higher than 1e-14, since roundoff
 /// error prevents the calculation error from converging into 0.
 #[derive(Debug, Clone, PartialEq)]
-pub enum Tolerance {
+pub enum Tolerance<T = f64> {
     /// `delta < absolute`
-    Absolute(f64),
+    Absolute(T),
     /// `delta < relative * result`
-    Relative(f64),
+    Relative(T),
     /// `delta < max(absolute, relative * result)`
-    AbsOrRel(f64, f64),
+    AbsOrRel(T, T),
     /// `delta < min(absolute, relative * result)`
-    AbsAndRel(f64, f64),
+    AbsAndRel(T, T),
 }
 
-impl Tolerance {
+impl<T: Float> Tolerance<T> {
     #[inline]
     pub(crate) fn contains_nan(&self) -> bool {
         match *self {
@@ -31,20 +33,27 @@ impl Tolerance {
 
     /// calculate the absolute tolerance from estimation
     #[inline]
-    pub fn to_abs(&self, value: f64) -> f64 {
+    pub fn to_abs(&self, value: T) -> T {
         match *self {
             Tolerance::Absolute(x) => x,
             Tolerance::Relative(y) => value * y,
-            Tolerance::AbsOrRel(x, y) => f64::max(x, value * y),
-            Tolerance::AbsAndRel(x, y) => f64::min(x, value * y),
+            Tolerance::AbsOrRel(x, y) => x.max(value * y),
+            Tolerance::AbsAndRel(x, y) => x.min(value * y),
         }
     }
 }
 
-impl Default for Tolerance {
+impl<T: Float> Default for Tolerance<T> {
+    /// `AbsOrRel(1.49e-8, 1.49e-8)` for `f64` and more precise types, or
+    /// `sqrt(EPSILON)` for less precise types such as `f32`
     #[inline]
     fn default() -> Self {
-        Tolerance::AbsOrRel(1.49e-8, 1.49e-8)
+        let tol = if T::EPSILON > T::from_f64(core::f64::EPSILON) {
+            T::EPSILON.sqrt()
+        } else {
+            T::from_f64(1.49e-8)
+        };
+        Tolerance::AbsOrRel(tol, tol)
     }
 }
 
@@ -210,25 +219,138 @@ impl<T, E: Debug> ValueWithError<T, E> {
 
 /// Estimation result for integral.
 #[derive(Clone, Debug, PartialEq)]
-pub struct Solution {
+pub struct Solution<T = f64> {
     /// Estimation for the integral
-    pub estimate: f64,
+    pub estimate: T,
+    /// Estimated maximum absolute error for the estimation
+    pub delta: T,
+    /// What times the integrand was evaluated
+    pub nevals: usize,
+}
+
+impl<T: Float> Default for Solution<T> {
+    #[inline]
+    fn default() -> Self {
+        Self {
+            estimate: T::ZERO,
+            delta: T::MAX,
+            nevals: 0,
+        }
+    }
+}
+
+/// Result of numerical integration
+pub type IntegrationResult<T = f64> = ValueWithError<Solution<T>, RuntimeError>;
+
+/// Error returned by the fallible integrand
+///
+/// The integration stops at the first error, and the point where the error
+/// occured is recorded along with the partial result.
+#[derive(Clone, Debug, PartialEq)]
+pub struct IntegrandError<E, T = f64> {
+    /// Error returned by the integrand
+    pub error: E,
+    /// Explanatory variable where the integrand failed
+    pub x: T,
+    /// Partial result at the time when the integration stopped
+    pub partial: Solution<T>,
+}
+
+impl<E: core::fmt::Display, T: Debug> core::fmt::Display for IntegrandError<E, T> {
+    fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
+        write!(f, "integrand failed at x = {:?}: {}", self.x, self.error)
+    }
+}
+
+#[cfg(feature = "std")]
+#[cfg_attr(docsrs, doc(cfg(feature = "std")))]
+impl<E: std::error::Error + 'static, T: Debug> std::error::Error for IntegrandError<E, T> {
+    fn source(&self) -> Option<&(dyn std::error::Error + 'static)> {
+        Some(&self.error)
+    }
+}
+
+/// Result of numerical integration for fallible integrand
+///
+/// `Err` is returned if the integrand failed. Otherwise the result is the same
+/// as the infallible integrand.
+pub type TryIntegrationResult<E, T = f64> = Result<IntegrationResult<T>, IntegrandError<E, T>>;
+
+/// Estimation result for the integral of vector-valued function.
+#[derive(Clone, Debug, Default, PartialEq)]
+pub struct VecSolution {
+    /// Estimation for the integral of each component
+    pub estimate: Vec<f64>,
+    /// Estimated maximum absolute error for each component
+    pub delta: Vec<f64>,
+    /// What times the integrand was evaluated
+    pub nevals: usize,
+}
+
+impl VecSolution {
+    /// Number of components
+    #[inline]
+    pub fn len(&self) -> usize {
+        self.estimate.len()
+    }
+
+    /// Return true if the solution has no components
+    #[inline]
+    pub fn is_empty(&self) -> bool {
+        self.estimate.is_empty()
+    }
+
+    /// Extract the solution for the `i`-th component
+    ///
+    /// Return `None` if `i` is out of bounds.
+    #[inline]
+    pub fn get(&self, i: usize) -> Option<Solution> {
+        Some(Solution {
+            estimate: *self.estimate.get(i)?,
+            delta: *self.delta.get(i)?,
+            nevals: self.nevals,
+        })
+    }
+
+    /// Convert into the solutions for each component
+    pub fn into_solutions(self) -> Vec<Solution> {
+        let nevals = self.nevals;
+        self.estimate
+            .into_iter()
+            .zip(self.delta)
+            .map(|(estimate, delta)| Solution {
+                estimate,
+                delta,
+                nevals,
+            })
+            .collect()
+    }
+}
+
+/// Result of numerical integration for vector-valued function
+pub type VecIntegrationResult = ValueWithError<VecSolution, RuntimeError>;
+
+/// Estimation result for the integral of complex-valued function.
+#[derive(Clone, Debug, PartialEq)]
+pub struct ComplexSolution {
+    /// Estimation for the integral, as `(re, im)`
+    pub estimate: (f64, f64),
     /// Estimated maximum absolute error for the estimation
     pub delta: f64,
     /// What times the integrand was evaluated
     pub nevals: usize,
 }
 
-impl Default for Solution {
+impl Default for ComplexSolution {
     #[inline]
     fn default() -> Self {
         Self {
-            estimate: 0.0,
+            estimate: (0.0, 0.0),
             delta: core::f64::MAX,
             nevals: 0,
         }
     }
 }
 
-/// Result of numerical integration
-pub type IntegrationResult = ValueWithError<Solution, RuntimeError>;
+/// Result of numerical integration for complex-valued function
+pub type ComplexIntegrationResult = ValueWithError<ComplexSolution, RuntimeError>;
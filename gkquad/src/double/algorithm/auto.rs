@@ -2,13 +2,28 @@ use super::super::common::{Integrand2, IntegrationConfig2};
 use super::super::range::{DynamicX, DynamicY, Rectangle};
 use super::{Algorithm2, QAGP2, QAGS2};
 use crate::common::IntegrationResult;
+use crate::single::GaussKronrod;
 
 #[derive(Clone)]
-pub struct AUTO2;
+pub struct AUTO2 {
+    rule: Option<GaussKronrod>,
+}
 
 impl AUTO2 {
     pub fn new() -> Self {
-        Self
+        Self { rule: None }
+    }
+
+    /// Create a new `AUTO2` object which uses the given Gauss-Kronrod rule
+    #[inline]
+    pub fn with_rule(rule: GaussKronrod) -> Self {
+        Self { rule: Some(rule) }
+    }
+
+    /// Gauss-Kronrod rule specified by the user
+    #[inline]
+    pub fn rule(&self) -> Option<GaussKronrod> {
+        self.rule
     }
 }
 
@@ -22,9 +37,11 @@ macro_rules! impl_algorithm2 {
                 config: &IntegrationConfig2,
             ) -> IntegrationResult {
                 if config.points.is_empty() {
-                    QAGS2::new().integrate(f, range, config)
+                    let mut qags = self.rule.map_or_else(QAGS2::new, QAGS2::with_rule);
+                    qags.integrate(f, range, config)
                 } else {
-                    QAGP2::new().integrate(f, range, config)
+                    let mut qagp = self.rule.map_or_else(QAGP2::new, QAGP2::with_rule);
+                    qagp.integrate(f, range, config)
                 }
             }
         }
@@ -9,7 +9,9 @@ pub trait Algorithm2<F: Integrand2 + ?Sized, R: Range2> {
         -> IntegrationResult;
 }
 
-macro_rules! extra_traits {
+/// implement `Default`, `Debug` and the comparison traits, which depend on
+/// the `rule` field
+macro_rules! rule_traits {
     ($name:ident) => {
         impl Default for $name {
             #[inline]
@@ -20,14 +22,16 @@ macro_rules! extra_traits {
 
         impl core::fmt::Debug for $name {
             fn fmt(&self, f: &mut core::fmt::Formatter) -> core::fmt::Result {
-                f.write_str(stringify!($name))
+                f.debug_struct(stringify!($name))
+                    .field("rule", &self.rule)
+                    .finish()
             }
         }
 
         impl PartialEq<$name> for $name {
             #[inline]
-            fn eq(&self, _: &Self) -> bool {
-                true
+            fn eq(&self, other: &Self) -> bool {
+                self.rule == other.rule
             }
         }
 
@@ -35,20 +39,22 @@ macro_rules! extra_traits {
 
         impl PartialOrd for $name {
             #[inline]
-            fn partial_cmp(&self, _: &Self) -> Option<core::cmp::Ordering> {
-                Some(core::cmp::Ordering::Equal)
+            fn partial_cmp(&self, other: &Self) -> Option<core::cmp::Ordering> {
+                Some(self.cmp(other))
             }
         }
 
         impl Ord for $name {
             #[inline]
-            fn cmp(&self, _: &Self) -> core::cmp::Ordering {
-                core::cmp::Ordering::Equal
+            fn cmp(&self, other: &Self) -> core::cmp::Ordering {
+                self.rule.cmp(&other.rule)
             }
         }
 
         impl core::hash::Hash for $name {
-            fn hash<H: core::hash::Hasher>(&self, _: &mut H) {}
+            fn hash<H: core::hash::Hasher>(&self, state: &mut H) {
+                self.rule.hash(state)
+            }
         }
     };
 }
@@ -64,3 +70,9 @@ pub use qagp::*;
 
 mod auto;
 pub use auto::*;
+
+#[cfg(feature = "rayon")]
+mod parallel;
+#[cfg(feature = "rayon")]
+#[cfg_attr(docsrs, doc(cfg(feature = "rayon")))]
+pub use parallel::*;
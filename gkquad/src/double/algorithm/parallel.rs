@@ -0,0 +1,288 @@
+use alloc::vec::Vec;
+
+use rayon::prelude::*;
+
+use super::super::common::{Integrand2, IntegrationConfig2, Point2};
+use super::super::range::{DynamicX, DynamicY, Rectangle};
+use super::Algorithm2;
+use crate::common::IntegrationResult;
+use crate::error::RuntimeError;
+use crate::single::algorithm::{Algorithm, QAGP, QAGS};
+use crate::single::{GaussKronrod, Integrand, IntegrationConfig, Points, Range, WorkSpace};
+
+/// `QAGS2` algorithm which evaluates the inner integrals in parallel
+///
+/// The inner integrals for all the abscissae of an outer Gauss-Kronrod rule
+/// are evaluated concurrently on the rayon thread pool. Each worker thread
+/// uses its own clone of the integrand and its own `WorkSpace`, and the
+/// remaining number of evaluations is split evenly among them.
+///
+/// The result is the same as `QAGS2` unless the maximum number of evaluations
+/// is exceeded.
+#[derive(Clone)]
+pub struct ParallelQAGS2 {
+    rule: Option<GaussKronrod>,
+}
+
+impl ParallelQAGS2 {
+    pub fn new() -> Self {
+        Self { rule: None }
+    }
+
+    /// Create a new `ParallelQAGS2` object which uses the given Gauss-Kronrod rule
+    /// for both the inner and the outer integrals
+    #[inline]
+    pub fn with_rule(rule: GaussKronrod) -> Self {
+        Self { rule: Some(rule) }
+    }
+
+    /// Gauss-Kronrod rule specified by the user
+    #[inline]
+    pub fn rule(&self) -> Option<GaussKronrod> {
+        self.rule
+    }
+}
+
+/// `QAGP2` algorithm which evaluates the inner integrals in parallel
+///
+/// See [`ParallelQAGS2`](struct.ParallelQAGS2.html) for details.
+#[derive(Clone)]
+pub struct ParallelQAGP2 {
+    rule: Option<GaussKronrod>,
+}
+
+impl ParallelQAGP2 {
+    pub fn new() -> Self {
+        Self { rule: None }
+    }
+
+    /// Create a new `ParallelQAGP2` object which uses the given Gauss-Kronrod rule
+    /// for both the inner and the outer integrals
+    #[inline]
+    pub fn with_rule(rule: GaussKronrod) -> Self {
+        Self { rule: Some(rule) }
+    }
+
+    /// Gauss-Kronrod rule specified by the user
+    #[inline]
+    pub fn rule(&self) -> Option<GaussKronrod> {
+        self.rule
+    }
+}
+
+macro_rules! impl_algorithm2 {
+    ($name:ident, $points:expr) => {
+        impl<F: Integrand2 + Clone + Sync> Algorithm2<F, Rectangle> for $name {
+            fn integrate(
+                &mut self,
+                f: &mut F,
+                range: &Rectangle,
+                config: &IntegrationConfig2,
+            ) -> IntegrationResult {
+                let yrange = |_: f64| range.yrange.clone();
+                integrate_impl(&*f, &range.xrange, &yrange, config, $points, self.rule)
+            }
+        }
+
+        impl<'a, F: Integrand2 + Clone + Sync> Algorithm2<F, DynamicX<'a>> for $name {
+            fn integrate(
+                &mut self,
+                f: &mut F,
+                range: &DynamicX<'a>,
+                config: &IntegrationConfig2,
+            ) -> IntegrationResult {
+                // swap x and y
+                let g = Swapped(f.clone());
+                let config = IntegrationConfig2 {
+                    tolerance: config.tolerance.clone(),
+                    max_evals: config.max_evals,
+                    points: config.points.iter().map(|&(x, y)| (y, x)).collect(),
+                    range_transform: config.range_transform.clone(),
+                };
+                integrate_impl(
+                    &g,
+                    &range.yrange,
+                    &*range.xrange,
+                    &config,
+                    $points,
+                    self.rule,
+                )
+            }
+        }
+
+        impl<'a, F: Integrand2 + Clone + Sync> Algorithm2<F, DynamicY<'a>> for $name {
+            fn integrate(
+                &mut self,
+                f: &mut F,
+                range: &DynamicY<'a>,
+                config: &IntegrationConfig2,
+            ) -> IntegrationResult {
+                integrate_impl(
+                    &*f,
+                    &range.xrange,
+                    &*range.yrange,
+                    config,
+                    $points,
+                    self.rule,
+                )
+            }
+        }
+
+        rule_traits!($name);
+    };
+}
+
+impl_algorithm2!(ParallelQAGS2, false);
+impl_algorithm2!(ParallelQAGP2, true);
+
+/// integrand whose `x` and `y` are swapped
+#[derive(Clone)]
+struct Swapped<F>(F);
+
+impl<F: Integrand2> Integrand2 for Swapped<F> {
+    #[inline]
+    fn apply(&mut self, x: Point2) -> f64 {
+        self.0.apply((x.1, x.0))
+    }
+}
+
+/// inner integral as a function of `x`
+struct OuterIntegrand<'a, F> {
+    f: &'a F,
+    yrange: &'a (dyn Fn(f64) -> Range + Sync),
+    config: IntegrationConfig,
+    points: bool,
+    rule: Option<GaussKronrod>,
+    max_evals: usize,
+    nevals: usize,
+    error: Option<RuntimeError>,
+}
+
+impl<'a, F: Integrand2 + Clone + Sync> Integrand for OuterIntegrand<'a, F> {
+    #[inline]
+    fn apply(&mut self, x: f64) -> f64 {
+        let mut s = [x];
+        self.apply_to_slice(&mut s);
+        s[0]
+    }
+
+    fn apply_to_slice(&mut self, s: &mut [f64]) {
+        // split the remaining budget evenly so that the abscissae evaluated
+        // concurrently cannot exceed `max_evals` together
+        self.config.max_evals = if self.error.is_some() || s.is_empty() {
+            0
+        } else {
+            self.max_evals.saturating_sub(self.nevals) / s.len()
+        };
+
+        let (f, yrange, config) = (self.f, self.yrange, &self.config);
+        let (points, rule) = (self.points, self.rule);
+        let results: Vec<IntegrationResult> = s
+            .par_iter()
+            .map_init(
+                || (f.clone(), WorkSpace::new()),
+                |(g, ws), &x| {
+                    let mut integrand = |y: f64| g.apply((x, y));
+                    let yrange = yrange(x);
+                    if points {
+                        QAGP::with_workspace_and_rule(ws, rule).integrate(
+                            &mut integrand,
+                            &yrange,
+                            config,
+                        )
+                    } else {
+                        QAGS::with_workspace_and_rule(ws, rule).integrate(
+                            &mut integrand,
+                            &yrange,
+                            config,
+                        )
+                    }
+                },
+            )
+            .collect();
+
+        // merge the results in the order of the abscissae, so that the first
+        // error does not depend on the scheduling
+        for (y, result) in s.iter_mut().zip(results) {
+            self.nevals += result.value.nevals;
+            *y = match result.error {
+                Some(error) => {
+                    if self.error.is_none() {
+                        self.error = Some(error);
+                    }
+                    core::f64::NAN
+                }
+                None => result.value.estimate,
+            };
+        }
+    }
+}
+
+fn integrate_impl<F: Integrand2 + Clone + Sync>(
+    f: &F,
+    xrange: &Range,
+    yrange: &(dyn Fn(f64) -> Range + Sync),
+    config: &IntegrationConfig2,
+    points: bool,
+    rule: Option<GaussKronrod>,
+) -> IntegrationResult {
+    let mut inner_config = IntegrationConfig {
+        tolerance: config.tolerance.clone(),
+        max_evals: 0,
+        points: Points::new(),
+        periodic: false,
+        range_transform: config.range_transform.clone(),
+    };
+
+    let mut outer_config = IntegrationConfig {
+        tolerance: config.tolerance.clone(),
+        max_evals: config.max_evals / 17,
+        points: Points::new(),
+        periodic: false,
+        range_transform: config.range_transform.clone(),
+    };
+
+    if points {
+        let xtransform = !xrange.begin.is_finite() || !xrange.end.is_finite();
+        config.points.iter().for_each(|&(x, y)| {
+            if xtransform {
+                outer_config.points.push(config.range_transform.inverse(x));
+            } else {
+                outer_config.points.push(x)
+            }
+            inner_config.points.push(y);
+        });
+    }
+
+    let mut integrand = OuterIntegrand {
+        f,
+        yrange,
+        config: inner_config,
+        points,
+        rule,
+        max_evals: config.max_evals,
+        nevals: 0,
+        error: None,
+    };
+
+    let mut outer_ws = WorkSpace::new();
+    let mut result = if points {
+        QAGP::with_workspace_and_rule(&mut outer_ws, rule).integrate(
+            &mut integrand,
+            xrange,
+            &outer_config,
+        )
+    } else {
+        QAGS::with_workspace_and_rule(&mut outer_ws, rule).integrate(
+            &mut integrand,
+            xrange,
+            &outer_config,
+        )
+    };
+    result.value.nevals = integrand.nevals;
+    if integrand.error.is_some() {
+        result.error = integrand.error;
+    }
+
+    result
+}
@@ -4,14 +4,29 @@ use super::Algorithm2;
 use crate::common::IntegrationResult;
 use crate::single::algorithm::{Algorithm, QAG};
 use crate::single::IntegrationConfig;
-use crate::single::WorkSpace;
+use crate::single::{GaussKronrod, WorkSpace};
 
 #[derive(Clone)]
-pub struct QAG2;
+pub struct QAG2 {
+    rule: Option<GaussKronrod>,
+}
 
 impl QAG2 {
     pub fn new() -> Self {
-        Self
+        Self { rule: None }
+    }
+
+    /// Create a new `QAG2` object which uses the given Gauss-Kronrod rule
+    /// for both the inner and the outer integrals
+    #[inline]
+    pub fn with_rule(rule: GaussKronrod) -> Self {
+        Self { rule: Some(rule) }
+    }
+
+    /// Gauss-Kronrod rule specified by the user
+    #[inline]
+    pub fn rule(&self) -> Option<GaussKronrod> {
+        self.rule
     }
 }
 
@@ -53,12 +68,13 @@ impl<'a, F: Integrand2 + ?Sized> Algorithm2<F, DynamicY<'a>> for QAG2 {
         let mut config1 = IntegrationConfig {
             tolerance: config.tolerance.clone(),
             max_evals: config.max_evals / 17,
+            range_transform: config.range_transform.clone(),
             ..Default::default()
         };
         let config2 = config1.clone();
 
         let mut inner_ws = WorkSpace::new();
-        let mut inner = QAG::with_workspace(&mut inner_ws);
+        let mut inner = QAG::with_workspace_and_rule(&mut inner_ws, self.rule);
         let mut error = None;
         let mut nevals = 0usize;
 
@@ -86,7 +102,9 @@ impl<'a, F: Integrand2 + ?Sized> Algorithm2<F, DynamicY<'a>> for QAG2 {
             }
         };
 
-        let mut result = QAG::new().integrate(&mut integrand, &range.xrange, &config2);
+        let mut outer_ws = WorkSpace::new();
+        let mut outer = QAG::with_workspace_and_rule(&mut outer_ws, self.rule);
+        let mut result = outer.integrate(&mut integrand, &range.xrange, &config2);
         result.value.nevals = nevals;
         if error.is_some() {
             result.error = error;
@@ -96,4 +114,4 @@ impl<'a, F: Integrand2 + ?Sized> Algorithm2<F, DynamicY<'a>> for QAG2 {
     }
 }
 
-extra_traits!(QAG2);
+rule_traits!(QAG2);
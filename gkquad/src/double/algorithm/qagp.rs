@@ -5,17 +5,32 @@ use super::super::range::{DynamicX, DynamicY, Rectangle};
 use super::Algorithm2;
 use crate::common::IntegrationResult;
 use crate::single::algorithm::{Algorithm, QAGP};
-use crate::single::{IntegrationConfig, Points, Range, WorkSpace};
+use crate::single::{GaussKronrod, IntegrationConfig, Points, Range, WorkSpace};
 
 #[cfg(not(feature = "std"))]
 use crate::float::Float;
 
 #[derive(Clone)]
-pub struct QAGP2;
+pub struct QAGP2 {
+    rule: Option<GaussKronrod>,
+}
 
 impl QAGP2 {
     pub fn new() -> Self {
-        Self
+        Self { rule: None }
+    }
+
+    /// Create a new `QAGP2` object which uses the given Gauss-Kronrod rule
+    /// for both the inner and the outer integrals
+    #[inline]
+    pub fn with_rule(rule: GaussKronrod) -> Self {
+        Self { rule: Some(rule) }
+    }
+
+    /// Gauss-Kronrod rule specified by the user
+    #[inline]
+    pub fn rule(&self) -> Option<GaussKronrod> {
+        self.rule
     }
 }
 
@@ -27,7 +42,7 @@ impl<F: Integrand2 + ?Sized> Algorithm2<F, Rectangle> for QAGP2 {
         config: &IntegrationConfig2,
     ) -> IntegrationResult {
         let yrange = |_: f64| Cow::Borrowed(&range.yrange);
-        integrate_impl(f, &range.xrange, yrange, config)
+        integrate_impl(f, &range.xrange, yrange, config, self.rule)
     }
 }
 
@@ -48,6 +63,7 @@ impl<'a, F: Integrand2 + ?Sized> Algorithm2<F, DynamicX<'a>> for QAGP2 {
             tolerance: config.tolerance.clone(),
             max_evals: config.max_evals,
             points: config.points.iter().map(|&(x, y)| (y, x)).collect(),
+            range_transform: config.range_transform.clone(),
         };
         self.integrate(&mut g, &range, &config)
     }
@@ -61,17 +77,18 @@ impl<'a, F: Integrand2 + ?Sized> Algorithm2<F, DynamicY<'a>> for QAGP2 {
         config: &IntegrationConfig2,
     ) -> IntegrationResult {
         let yrange = |x: f64| Cow::Owned((range.yrange)(x));
-        integrate_impl(f, &range.xrange, yrange, config)
+        integrate_impl(f, &range.xrange, yrange, config, self.rule)
     }
 }
 
-extra_traits!(QAGP2);
+rule_traits!(QAGP2);
 
 fn integrate_impl<'a, F, G>(
     f: &mut F,
     xrange: &Range,
     yrange: G,
     config: &IntegrationConfig2,
+    rule: Option<GaussKronrod>,
 ) -> IntegrationResult
 where
     F: Integrand2 + ?Sized,
@@ -82,23 +99,27 @@ where
         tolerance: config.tolerance.clone(),
         max_evals: 0,
         points: Points::with_capacity(config.points.len()),
+        periodic: false,
+        range_transform: config.range_transform.clone(),
     };
 
     let mut outer_config = IntegrationConfig {
         tolerance: config.tolerance.clone(),
         max_evals: config.max_evals / 17,
         points: Points::with_capacity(config.points.len()),
+        periodic: false,
+        range_transform: config.range_transform.clone(),
     };
 
     let mut inner_ws = WorkSpace::new();
-    let mut inner = QAGP::with_workspace(&mut inner_ws);
+    let mut inner = QAGP::with_workspace_and_rule(&mut inner_ws, rule);
     let mut error = None;
     let mut nevals = 0usize;
 
     let xtransform = !xrange.begin.is_finite() || !xrange.end.is_finite();
     config.points.iter().for_each(|&(x, y)| {
         if xtransform {
-            outer_config.points.push(transform_point(x));
+            outer_config.points.push(config.range_transform.inverse(x));
         } else {
             outer_config.points.push(x)
         }
@@ -129,7 +150,9 @@ where
         }
     };
 
-    let mut result = QAGP::new().integrate(&mut integrand, xrange, &outer_config);
+    let mut outer_ws = WorkSpace::new();
+    let mut outer = QAGP::with_workspace_and_rule(&mut outer_ws, rule);
+    let mut result = outer.integrate(&mut integrand, xrange, &outer_config);
     result.value.nevals = nevals;
     if error.is_some() {
         result.error = error;
@@ -137,14 +160,3 @@ where
 
     result
 }
-
-#[inline]
-fn transform_point(x: f64) -> f64 {
-    if x == core::f64::NEG_INFINITY {
-        -1.0
-    } else if x == core::f64::INFINITY {
-        1.0
-    } else {
-        x / (1.0 + x.abs())
-    }
-}
@@ -1,5 +1,6 @@
 use smallvec::SmallVec;
 
+use crate::single::RangeTransform;
 use crate::Tolerance;
 
 /// Point in 2-dimension
@@ -18,6 +19,8 @@ pub struct IntegrationConfig2 {
     pub max_evals: usize,
     /// specify singular points
     pub points: Points2,
+    /// transformation applied to the infinite ranges of both `x` and `y`
+    pub range_transform: RangeTransform,
 }
 
 impl Default for IntegrationConfig2 {
@@ -27,6 +30,7 @@ impl Default for IntegrationConfig2 {
             tolerance: Tolerance::default(),
             max_evals: 100000,
             points: Points2::new(),
+            range_transform: RangeTransform::default(),
         }
     }
 }
@@ -1,6 +1,7 @@
 use super::algorithm::*;
 use super::common::{Integrand2, IntegrationConfig2, Points2};
 use super::range::IntoRange2;
+use crate::single::RangeTransform;
 
 use crate::common::{IntegrationResult, Tolerance};
 
@@ -71,6 +72,23 @@ impl<F: Integrand2, A> Integrator2<F, A> {
         self
     }
 
+    /// Set the transformation applied to the infinite ranges
+    ///
+    /// # Panics
+    ///
+    /// Panics if the center is not finite, or the scale is not positive and
+    /// finite.
+    #[inline]
+    pub fn range_transform(mut self, transform: RangeTransform) -> Self {
+        assert!(
+            transform.is_valid(),
+            "invalid range transformation: {:?}",
+            transform
+        );
+        self.config.range_transform = transform;
+        self
+    }
+
     #[inline]
     pub fn get_algorithm(&self) -> &A {
         &self.algorithm
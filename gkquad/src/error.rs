@@ -174,5 +174,23 @@ impl_error!(
             "Integrand has returned a NAN value, so the algorithm cannot \
             continue the calculation.",
         },
+        InvalidRange {
+            "integration range is not supported by the algorithm",
+            "The integration range is not supported by the algorithm.\n\
+            For example, some algorithms cannot be applied to the infinite \
+            range.",
+        },
+        CycleFailure {
+            "integration failed in one or more cycles",
+            "Integration failed in one or more cycles of the oscillation.\n\
+            The location and the kind of the difficulty can be determined by \
+            integrating over each cycle with QAWO algorithm.",
+        },
+        PoleOnBoundary {
+            "pole of the weight function lies on the end point of the range",
+            "The pole of the weight function lies on the end point of the \
+            integration range, so the principal value cannot be defined.\n\
+            Move the end point away from the pole.",
+        },
     }
 );
@@ -1,159 +1,353 @@
 #![allow(clippy::many_single_char_names)]
 
-pub trait Float: Copy {
+use core::fmt::Debug;
+use core::ops::{Add, AddAssign, Div, DivAssign, Mul, MulAssign, Neg, Sub, SubAssign};
+
+/// Floating-point type which can be used in the integration
+///
+/// `f64` and `f32` implement this trait. The higher-precision type (e.g.
+/// double-double) can be used by implementing this trait.
+pub trait Float:
+    'static
+    + Copy
+    + Debug
+    + PartialOrd
+    + Add<Output = Self>
+    + Sub<Output = Self>
+    + Mul<Output = Self>
+    + Div<Output = Self>
+    + Neg<Output = Self>
+    + AddAssign
+    + SubAssign
+    + MulAssign
+    + DivAssign
+{
+    const ZERO: Self;
+    const ONE: Self;
+    /// machine epsilon
+    const EPSILON: Self;
+    /// smallest positive normal value
+    const MIN_POSITIVE: Self;
+    /// largest finite value
+    const MAX: Self;
+    const INFINITY: Self;
+    const NEG_INFINITY: Self;
+
+    /// convert from `f64` (rounded if necessary)
+    fn from_f64(x: f64) -> Self;
+
+    /// convert into `f64` (rounded if necessary)
+    fn to_f64(self) -> f64;
+
+    /// parse the decimal literal such as `"0.183434642495649804939476142360184"`
+    ///
+    /// The Gauss-Kronrod coefficients are stored as 33-digit literals, and
+    /// converted into `Self` by this method, so that the types more precise
+    /// than `f64` can take advantage of the extra digits.
+    fn parse_literal(s: &str) -> Self;
+
     fn abs(self) -> Self;
+
     fn sqrt(self) -> Self;
+
+    #[inline]
+    #[allow(clippy::eq_op)]
+    fn is_nan(self) -> bool {
+        self != self
+    }
+
+    #[inline]
+    fn is_finite(self) -> bool {
+        self.abs() <= Self::MAX
+    }
+
+    #[inline]
+    fn max(self, other: Self) -> Self {
+        if self < other || self.is_nan() {
+            other
+        } else {
+            self
+        }
+    }
+
+    #[inline]
+    fn min(self, other: Self) -> Self {
+        if other < self || self.is_nan() {
+            other
+        } else {
+            self
+        }
+    }
 }
 
 impl Float for f64 {
+    const ZERO: f64 = 0.0;
+    const ONE: f64 = 1.0;
+    const EPSILON: f64 = core::f64::EPSILON;
+    const MIN_POSITIVE: f64 = core::f64::MIN_POSITIVE;
+    const MAX: f64 = core::f64::MAX;
+    const INFINITY: f64 = core::f64::INFINITY;
+    const NEG_INFINITY: f64 = core::f64::NEG_INFINITY;
+
     #[inline]
-    fn abs(self: f64) -> f64 {
+    fn from_f64(x: f64) -> f64 {
+        x
+    }
+
+    #[inline]
+    fn to_f64(self) -> f64 {
+        self
+    }
+
+    #[inline]
+    fn parse_literal(s: &str) -> f64 {
+        s.parse().expect("invalid floating-point literal")
+    }
+
+    #[inline]
+    fn abs(self) -> f64 {
         f64::from_bits(self.to_bits() & (core::u64::MAX / 2))
     }
 
-    fn sqrt(self: f64) -> f64 {
-        #[cfg(target_feature = "sse2")]
+    #[inline]
+    fn sqrt(self) -> f64 {
+        #[cfg(feature = "std")]
         {
-            #[cfg(target_arch = "x86")]
-            use core::arch::x86::*;
-            #[cfg(target_arch = "x86_64")]
-            use core::arch::x86_64::*;
-            unsafe {
-                let m = _mm_set_sd(self);
-                let m_sqrt = _mm_sqrt_pd(m);
-                _mm_cvtsd_f64(m_sqrt)
-            }
+            f64::sqrt(self)
+        }
+        #[cfg(not(feature = "std"))]
+        {
+            sqrt(self)
+        }
+    }
+
+    #[inline]
+    fn is_nan(self) -> bool {
+        f64::is_nan(self)
+    }
+
+    #[inline]
+    fn is_finite(self) -> bool {
+        f64::is_finite(self)
+    }
+
+    #[inline]
+    fn max(self, other: f64) -> f64 {
+        f64::max(self, other)
+    }
+
+    #[inline]
+    fn min(self, other: f64) -> f64 {
+        f64::min(self, other)
+    }
+}
+
+impl Float for f32 {
+    const ZERO: f32 = 0.0;
+    const ONE: f32 = 1.0;
+    const EPSILON: f32 = core::f32::EPSILON;
+    const MIN_POSITIVE: f32 = core::f32::MIN_POSITIVE;
+    const MAX: f32 = core::f32::MAX;
+    const INFINITY: f32 = core::f32::INFINITY;
+    const NEG_INFINITY: f32 = core::f32::NEG_INFINITY;
+
+    #[inline]
+    fn from_f64(x: f64) -> f32 {
+        x as f32
+    }
+
+    #[inline]
+    fn to_f64(self) -> f64 {
+        f64::from(self)
+    }
+
+    #[inline]
+    fn parse_literal(s: &str) -> f32 {
+        s.parse().expect("invalid floating-point literal")
+    }
+
+    #[inline]
+    fn abs(self) -> f32 {
+        f32::from_bits(self.to_bits() & (core::u32::MAX / 2))
+    }
+
+    #[inline]
+    fn sqrt(self) -> f32 {
+        #[cfg(feature = "std")]
+        {
+            f32::sqrt(self)
         }
-        #[cfg(not(target_feature = "sse2"))]
+        #[cfg(not(feature = "std"))]
         {
-            use core::num::Wrapping;
-
-            const TINY: f64 = 1.0e-300;
-
-            let mut z: f64;
-            let sign: Wrapping<u32> = Wrapping(0x80000000);
-            let mut ix0: i32;
-            let mut s0: i32;
-            let mut q: i32;
-            let mut m: i32;
-            let mut t: i32;
-            let mut i: i32;
-            let mut r: Wrapping<u32>;
-            let mut t1: Wrapping<u32>;
-            let mut s1: Wrapping<u32>;
-            let mut ix1: Wrapping<u32>;
-            let mut q1: Wrapping<u32>;
-
-            ix0 = (self.to_bits() >> 32) as i32;
-            ix1 = Wrapping(self.to_bits() as u32);
-
-            /* take care of Inf and NaN */
-            if (ix0 & 0x7ff00000) == 0x7ff00000 {
-                return self * self + self; /* sqrt(NaN)=NaN, sqrt(+inf)=+inf, sqrt(-inf)=sNaN */
+            // sqrt of the f32 value is exactly rounded via f64
+            sqrt(f64::from(self)) as f32
+        }
+    }
+
+    #[inline]
+    fn is_nan(self) -> bool {
+        f32::is_nan(self)
+    }
+
+    #[inline]
+    fn is_finite(self) -> bool {
+        f32::is_finite(self)
+    }
+
+    #[inline]
+    fn max(self, other: f32) -> f32 {
+        f32::max(self, other)
+    }
+
+    #[inline]
+    fn min(self, other: f32) -> f32 {
+        f32::min(self, other)
+    }
+}
+
+#[cfg(not(feature = "std"))]
+fn sqrt(x: f64) -> f64 {
+    #[cfg(target_feature = "sse2")]
+    {
+        #[cfg(target_arch = "x86")]
+        use core::arch::x86::*;
+        #[cfg(target_arch = "x86_64")]
+        use core::arch::x86_64::*;
+        unsafe {
+            let m = _mm_set_sd(x);
+            let m_sqrt = _mm_sqrt_pd(m);
+            _mm_cvtsd_f64(m_sqrt)
+        }
+    }
+    #[cfg(not(target_feature = "sse2"))]
+    {
+        use core::num::Wrapping;
+
+        const TINY: f64 = 1.0e-300;
+
+        let mut z: f64;
+        let sign: Wrapping<u32> = Wrapping(0x80000000);
+        let mut ix0: i32;
+        let mut s0: i32;
+        let mut q: i32;
+        let mut m: i32;
+        let mut t: i32;
+        let mut i: i32;
+        let mut r: Wrapping<u32>;
+        let mut t1: Wrapping<u32>;
+        let mut s1: Wrapping<u32>;
+        let mut ix1: Wrapping<u32>;
+        let mut q1: Wrapping<u32>;
+
+        ix0 = (x.to_bits() >> 32) as i32;
+        ix1 = Wrapping(x.to_bits() as u32);
+
+        /* take care of Inf and NaN */
+        if (ix0 & 0x7ff00000) == 0x7ff00000 {
+            return x * x + x; /* sqrt(NaN)=NaN, sqrt(+inf)=+inf, sqrt(-inf)=sNaN */
+        }
+        /* take care of zero */
+        if ix0 <= 0 {
+            if ((ix0 & !(sign.0 as i32)) | ix1.0 as i32) == 0 {
+                return x; /* sqrt(+-0) = +-0 */
             }
-            /* take care of zero */
-            if ix0 <= 0 {
-                if ((ix0 & !(sign.0 as i32)) | ix1.0 as i32) == 0 {
-                    return self; /* sqrt(+-0) = +-0 */
-                }
-                if ix0 < 0 {
-                    return (self - self) / (self - self); /* sqrt(-ve) = sNaN */
-                }
+            if ix0 < 0 {
+                return (x - x) / (x - x); /* sqrt(-ve) = sNaN */
             }
-            /* normalize x */
-            m = ix0 >> 20;
-            if m == 0 {
-                /* subnormal x */
-                while ix0 == 0 {
-                    m -= 21;
-                    ix0 |= (ix1 >> 11).0 as i32;
-                    ix1 <<= 21;
-                }
-                i = 0;
-                while (ix0 & 0x00100000) == 0 {
-                    i += 1;
-                    ix0 <<= 1;
-                }
-                m -= i - 1;
-                ix0 |= (ix1 >> (32 - i) as usize).0 as i32;
-                ix1 = ix1 << i as usize;
+        }
+        /* normalize x */
+        m = ix0 >> 20;
+        if m == 0 {
+            /* subnormal x */
+            while ix0 == 0 {
+                m -= 21;
+                ix0 |= (ix1 >> 11).0 as i32;
+                ix1 <<= 21;
             }
-            m -= 1023; /* unbias exponent */
-            ix0 = (ix0 & 0x000fffff) | 0x00100000;
-            if (m & 1) == 1 {
-                /* odd m, double x to make it even */
-                ix0 += ix0 + ((ix1 & sign) >> 31).0 as i32;
-                ix1 += ix1;
+            i = 0;
+            while (ix0 & 0x00100000) == 0 {
+                i += 1;
+                ix0 <<= 1;
             }
-            m >>= 1; /* m = [m/2] */
-
-            /* generate sqrt(x) bit by bit */
+            m -= i - 1;
+            ix0 |= (ix1 >> (32 - i) as usize).0 as i32;
+            ix1 = ix1 << i as usize;
+        }
+        m -= 1023; /* unbias exponent */
+        ix0 = (ix0 & 0x000fffff) | 0x00100000;
+        if (m & 1) == 1 {
+            /* odd m, double x to make it even */
             ix0 += ix0 + ((ix1 & sign) >> 31).0 as i32;
             ix1 += ix1;
-            q = 0; /* [q,q1] = sqrt(x) */
-            q1 = Wrapping(0);
-            s0 = 0;
-            s1 = Wrapping(0);
-            r = Wrapping(0x00200000); /* r = moving bit from right to left */
-
-            while r != Wrapping(0) {
-                t = s0 + r.0 as i32;
-                if t <= ix0 {
-                    s0 = t + r.0 as i32;
-                    ix0 -= t;
-                    q += r.0 as i32;
-                }
-                ix0 += ix0 + ((ix1 & sign) >> 31).0 as i32;
-                ix1 += ix1;
-                r >>= 1;
+        }
+        m >>= 1; /* m = [m/2] */
+
+        /* generate sqrt(x) bit by bit */
+        ix0 += ix0 + ((ix1 & sign) >> 31).0 as i32;
+        ix1 += ix1;
+        q = 0; /* [q,q1] = sqrt(x) */
+        q1 = Wrapping(0);
+        s0 = 0;
+        s1 = Wrapping(0);
+        r = Wrapping(0x00200000); /* r = moving bit from right to left */
+
+        while r != Wrapping(0) {
+            t = s0 + r.0 as i32;
+            if t <= ix0 {
+                s0 = t + r.0 as i32;
+                ix0 -= t;
+                q += r.0 as i32;
             }
+            ix0 += ix0 + ((ix1 & sign) >> 31).0 as i32;
+            ix1 += ix1;
+            r >>= 1;
+        }
 
-            r = sign;
-            while r != Wrapping(0) {
-                t1 = s1 + r;
-                t = s0;
-                if t < ix0 || (t == ix0 && t1 <= ix1) {
-                    s1 = t1 + r;
-                    if (t1 & sign) == sign && (s1 & sign) == Wrapping(0) {
-                        s0 += 1;
-                    }
-                    ix0 -= t;
-                    if ix1 < t1 {
-                        ix0 -= 1;
-                    }
-                    ix1 -= t1;
-                    q1 += r;
+        r = sign;
+        while r != Wrapping(0) {
+            t1 = s1 + r;
+            t = s0;
+            if t < ix0 || (t == ix0 && t1 <= ix1) {
+                s1 = t1 + r;
+                if (t1 & sign) == sign && (s1 & sign) == Wrapping(0) {
+                    s0 += 1;
                 }
-                ix0 += ix0 + ((ix1 & sign) >> 31).0 as i32;
-                ix1 += ix1;
-                r >>= 1;
+                ix0 -= t;
+                if ix1 < t1 {
+                    ix0 -= 1;
+                }
+                ix1 -= t1;
+                q1 += r;
             }
+            ix0 += ix0 + ((ix1 & sign) >> 31).0 as i32;
+            ix1 += ix1;
+            r >>= 1;
+        }
 
-            /* use floating add to find out rounding direction */
-            if (ix0 as u32 | ix1.0) != 0 {
-                z = 1.0 - TINY; /* raise inexact flag */
-                if z >= 1.0 {
-                    z = 1.0 + TINY;
-                    if q1.0 == 0xffffffff {
-                        q1 = Wrapping(0);
+        /* use floating add to find out rounding direction */
+        if (ix0 as u32 | ix1.0) != 0 {
+            z = 1.0 - TINY; /* raise inexact flag */
+            if z >= 1.0 {
+                z = 1.0 + TINY;
+                if q1.0 == 0xffffffff {
+                    q1 = Wrapping(0);
+                    q += 1;
+                } else if z > 1.0 {
+                    if q1.0 == 0xfffffffe {
                         q += 1;
-                    } else if z > 1.0 {
-                        if q1.0 == 0xfffffffe {
-                            q += 1;
-                        }
-                        q1 += Wrapping(2);
-                    } else {
-                        q1 += q1 & Wrapping(1);
                     }
+                    q1 += Wrapping(2);
+                } else {
+                    q1 += q1 & Wrapping(1);
                 }
             }
-            ix0 = (q >> 1) + 0x3fe00000;
-            ix1 = q1 >> 1;
-            if (q & 1) == 1 {
-                ix1 |= sign;
-            }
-            ix0 += m << 20;
-            f64::from_bits((ix0 as u64) << 32 | ix1.0 as u64)
         }
+        ix0 = (q >> 1) + 0x3fe00000;
+        ix1 = q1 >> 1;
+        if (q & 1) == 1 {
+            ix1 |= sign;
+        }
+        ix0 += m << 20;
+        f64::from_bits((ix0 as u64) << 32 | ix1.0 as u64)
     }
 }
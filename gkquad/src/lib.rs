@@ -38,13 +38,12 @@ compile_error!("`simd` feature flag requires x86 or x86_64 architecture.");
 
 mod common;
 mod error;
-mod utils;
-
-#[cfg(not(feature = "std"))]
 mod float;
+mod utils;
 
 pub use common::*;
 pub use error::*;
+pub use float::Float;
 
 pub mod single;
 
@@ -1,6 +1,9 @@
 pub use crate::{common::IntegrationResult, RuntimeError, Tolerance};
 
-pub use crate::single::{algorithm::*, integral, Integrand, Integrator};
+pub use crate::single::{
+    algorithm::*, integral, integral_complex, integral_vec, try_integral, BatchIntegrand,
+    Integrand, IntegrandComplex, IntegrandVec, Integrator, TryIntegrand,
+};
 
 #[cfg(feature = "double")]
 #[cfg_attr(docsrs, doc(cfg(feature = "double")))]
@@ -1,22 +1,57 @@
 use crate::common::IntegrationResult;
+use crate::error::RuntimeError::*;
 use crate::single::algorithm::*;
 use crate::single::common::{Integrand, IntegrationConfig, Range};
+use crate::single::qk::GaussKronrod;
 
 /// Automatically select algorithm based on configuration
+///
+/// If no points are specified, `QNG` is tried first for the finite range, and
+/// `QAGS` is used when it fails to reach the tolerance. If the integrand is
+/// declared as periodic, the periodic variant of `Romberg` is tried instead of
+/// `QNG` with a quarter of the maximum number of evaluations.
+///
+/// The Gauss-Kronrod rule given by [`with_rule`](#method.with_rule) is used by
+/// the adaptive algorithms `QAGS` and `QAGP`.
 #[derive(Clone)]
 pub struct AUTO {
+    rule: Option<GaussKronrod>,
+    qng: QNG,
     qags: QAGS<'static>,
     qagp: QAGP<'static>,
+    romberg: Romberg,
 }
 
 impl AUTO {
     #[inline]
     pub fn new() -> Self {
         Self {
+            rule: None,
+            qng: QNG::new(),
             qags: QAGS::new(),
             qagp: QAGP::new(),
+            romberg: Romberg::periodic(),
         }
     }
+
+    /// Create a new `AUTO` object whose adaptive algorithms use the given
+    /// Gauss-Kronrod rule
+    #[inline]
+    pub fn with_rule(rule: GaussKronrod) -> Self {
+        Self {
+            rule: Some(rule),
+            qng: QNG::new(),
+            qags: QAGS::with_rule(rule),
+            qagp: QAGP::with_rule(rule),
+            romberg: Romberg::periodic(),
+        }
+    }
+
+    /// Gauss-Kronrod rule specified by the user
+    #[inline]
+    pub fn rule(&self) -> Option<GaussKronrod> {
+        self.rule
+    }
 }
 
 impl<F: Integrand + ?Sized> Algorithm<F> for AUTO {
@@ -26,12 +61,36 @@ impl<F: Integrand + ?Sized> Algorithm<F> for AUTO {
         range: &Range,
         config: &IntegrationConfig,
     ) -> IntegrationResult {
-        if config.points.is_empty() {
-            self.qags.integrate(f, range, config)
+        if !config.points.is_empty() {
+            return self.qagp.integrate(f, range, config);
+        }
+
+        if !range.begin.is_finite() || !range.end.is_finite() {
+            return self.qags.integrate(f, range, config);
+        }
+
+        let result = if config.periodic {
+            // limit the evaluations wasted in case the integrand is not
+            // actually smooth and periodic
+            let mut config1 = config.clone();
+            config1.max_evals = config.max_evals / 4;
+            self.romberg.integrate(f, range, &config1)
         } else {
-            self.qagp.integrate(f, range, config)
+            self.qng.integrate(f, range, config)
+        };
+        if result.error != Some(InsufficientIteration) {
+            return result;
         }
+
+        // fall back to the adaptive algorithm with the remaining evaluations
+        let nevals = result.value.nevals;
+        let mut config2 = config.clone();
+        config2.max_evals = config.max_evals - nevals;
+
+        let mut result = self.qags.integrate(f, range, &config2);
+        result.value.nevals += nevals;
+        result
     }
 }
 
-extra_traits!(AUTO);
+rule_traits!(AUTO);
@@ -0,0 +1,390 @@
+use alloc::vec::Vec;
+use core::f64::consts::PI;
+
+use crate::common::{IntegrationResult, Solution};
+use crate::error::RuntimeError::{self, *};
+use crate::single::algorithm::Algorithm;
+use crate::single::common::{Integrand, IntegrationConfig, Range};
+use crate::single::util::{bisect, subrange_too_small};
+use crate::single::workspace::{SubRangeInfo, WorkSpace};
+use crate::utils::CowMut;
+
+/// minimum number of intervals between the Chebyshev nodes
+const MIN_N: usize = 8;
+/// maximum number of intervals between the Chebyshev nodes
+const MAX_N: usize = 64;
+
+/// table of `cos(πm/MAX_N)` for m = 0, ..., 2 MAX_N - 1
+///
+/// The nodes and the coefficients of the Chebyshev interpolants at all the
+/// orders are calculated from this table.
+struct CosineTable([f64; 2 * MAX_N]);
+
+impl CosineTable {
+    fn new() -> Self {
+        let mut table = [0.0; 2 * MAX_N];
+        for (m, v) in table.iter_mut().enumerate() {
+            *v = (PI * m as f64 / MAX_N as f64).cos();
+        }
+        Self(table)
+    }
+
+    /// `cos(πm/n)`, where `n` must be a divisor of `MAX_N`
+    #[inline]
+    fn cos(&self, m: usize, n: usize) -> f64 {
+        self.0[(m % (2 * n)) * (MAX_N / n)]
+    }
+}
+
+/// Chebyshev expansion of the integrand over the subrange
+///
+/// `f(x)` is interpolated by `Σ c[k] T_k(t)` at the Chebyshev nodes, where
+/// `t` is the position of `x` scaled into [-1, 1].
+#[derive(Clone, Debug, PartialEq)]
+pub struct ChebyshevExpansion {
+    range: Range,
+    coefficients: Vec<f64>,
+}
+
+impl ChebyshevExpansion {
+    /// subrange over which the integrand is interpolated
+    #[inline]
+    pub fn range(&self) -> &Range {
+        &self.range
+    }
+
+    /// coefficients of the Chebyshev polynomials `T_0, T_1, ..., T_n`
+    #[inline]
+    pub fn coefficients(&self) -> &[f64] {
+        &self.coefficients
+    }
+
+    /// evaluate the interpolant at `x` by Clenshaw's recurrence formula
+    pub fn eval(&self, x: f64) -> f64 {
+        let (a, b) = (self.range.begin, self.range.end);
+        let t = (2. * x - a - b) / (b - a);
+
+        let (mut b1, mut b2) = (0.0, 0.0);
+        for &c in self.coefficients.iter().skip(1).rev() {
+            let tmp = 2. * t * b1 - b2 + c;
+            b2 = b1;
+            b1 = tmp;
+        }
+
+        t * b1 - b2 + self.coefficients[0]
+    }
+}
+
+/// Adaptive Clenshaw-Curtis quadrature
+///
+/// Each subrange is integrated with the Clenshaw-Curtis rules whose number of
+/// points doubles on each refinement (9, 17, 33 and 65 points), so that all
+/// the previous function values are reused. If the highest order rule does not
+/// meet the tolerance, the subrange with the largest error is bisected.
+///
+/// After the integration, the Chebyshev expansions of the integrand over the
+/// subranges are available from [`expansions`](#method.expansions) method,
+/// which can be used as a cheap interpolant of the integrand.
+///
+/// Since the Clenshaw-Curtis rules use the end points of the subranges, the
+/// integrand must be finite at the end points of the range. This algorithm
+/// cannot be applied to the infinite range.
+///
+/// # Examples
+///
+/// ```
+/// use gkquad::single::algorithm::ClenshawCurtis;
+/// use gkquad::single::Integrator;
+///
+/// let mut integrator = Integrator::with_algorithm(|x: f64| x.exp(), ClenshawCurtis::new());
+/// let result = integrator.run(0.0..1.0).unwrap();
+///
+/// let expansions = integrator.get_algorithm().expansions();
+/// assert!((expansions[0].eval(0.5) - 0.5f64.exp()).abs() < 1e-10);
+/// ```
+#[derive(Clone)]
+pub struct ClenshawCurtis<'a> {
+    expansions: Vec<ChebyshevExpansion>,
+    workspace: CowMut<'a, WorkSpace>,
+}
+
+impl<'a> ClenshawCurtis<'a> {
+    #[inline]
+    pub fn new() -> Self {
+        Self {
+            expansions: Vec::new(),
+            workspace: CowMut::Owned(WorkSpace::new()),
+        }
+    }
+
+    #[inline]
+    #[doc(hidden)]
+    pub fn with_workspace(ws: &'a mut WorkSpace) -> Self {
+        Self {
+            expansions: Vec::new(),
+            workspace: CowMut::Borrowed(ws),
+        }
+    }
+
+    /// Chebyshev expansions over the subranges in the last integration
+    ///
+    /// The expansions are sorted along the integration range.
+    #[inline]
+    pub fn expansions(&self) -> &[ChebyshevExpansion] {
+        &self.expansions
+    }
+}
+
+impl<'a, F: Integrand + ?Sized> Algorithm<F> for ClenshawCurtis<'a> {
+    fn integrate(
+        &mut self,
+        f: &mut F,
+        range: &Range,
+        config: &IntegrationConfig,
+    ) -> IntegrationResult {
+        self.expansions.clear();
+
+        if !range.begin.is_finite() || !range.end.is_finite() {
+            return IntegrationResult::with_error(Solution::default(), InvalidRange);
+        }
+
+        let result = integrate_impl(f, range, config, &mut *self.workspace, &mut self.expansions);
+
+        let ascending = range.begin <= range.end;
+        self.expansions.sort_by(|x, y| {
+            let ord = x.range.begin.partial_cmp(&y.range.begin).unwrap();
+            if ascending {
+                ord
+            } else {
+                ord.reverse()
+            }
+        });
+
+        result
+    }
+}
+
+extra_traits!(ClenshawCurtis<'a>);
+
+/// result of `qcc`
+struct QCCResult {
+    estimate: f64,
+    delta: f64,
+    nevals: usize,
+    expansion: ChebyshevExpansion,
+}
+
+/// Integrate `f` over the range with the nested Clenshaw-Curtis rules until
+/// the error estimate becomes smaller than `tolerance(estimate)`.
+fn qcc<F: Integrand + ?Sized>(
+    f: &mut F,
+    range: &Range,
+    table: &CosineTable,
+    tolerance: &dyn Fn(f64) -> f64,
+) -> QCCResult {
+    let center = 0.5 * (range.begin + range.end);
+    let half_length = 0.5 * (range.end - range.begin);
+
+    let mut n = MIN_N;
+    let mut values: Vec<f64> = (0..=n)
+        .map(|j| center + half_length * table.cos(j, n))
+        .collect();
+    f.apply_to_slice(&mut values);
+    let mut nevals = n + 1;
+
+    let mut coefficients = chebyshev_coefficients(&values, table);
+    let mut estimate = half_length * integrate_series(&coefficients);
+    let mut delta;
+
+    loop {
+        // evaluate the integrand at the midpoints of the previous nodes
+        let mut new_values: Vec<f64> = (0..n)
+            .map(|j| center + half_length * table.cos(2 * j + 1, 2 * n))
+            .collect();
+        f.apply_to_slice(&mut new_values);
+        nevals += n;
+
+        let mut merged = Vec::with_capacity(2 * n + 1);
+        for j in 0..n {
+            merged.push(values[j]);
+            merged.push(new_values[j]);
+        }
+        merged.push(values[n]);
+
+        values = merged;
+        n *= 2;
+
+        let previous = estimate;
+        coefficients = chebyshev_coefficients(&values, table);
+        estimate = half_length * integrate_series(&coefficients);
+        delta = (estimate - previous).abs();
+
+        if delta <= tolerance(estimate) || n >= MAX_N || estimate.is_nan() {
+            break;
+        }
+    }
+
+    QCCResult {
+        estimate,
+        delta,
+        nevals,
+        expansion: ChebyshevExpansion {
+            range: range.clone(),
+            coefficients,
+        },
+    }
+}
+
+/// calculate the coefficients of the Chebyshev interpolant from the values at
+/// the nodes `cos(πj/n)` (j = 0, ..., n)
+fn chebyshev_coefficients(values: &[f64], table: &CosineTable) -> Vec<f64> {
+    let n = values.len() - 1;
+    let mut coefficients: Vec<f64> = (0..=n)
+        .map(|k| {
+            let sum: f64 = values
+                .iter()
+                .enumerate()
+                .map(|(j, &v)| {
+                    // the end points have half weights
+                    let v = if j == 0 || j == n { 0.5 * v } else { v };
+                    v * table.cos(j * k, n)
+                })
+                .sum();
+            sum * 2. / n as f64
+        })
+        .collect();
+
+    coefficients[0] *= 0.5;
+    coefficients[n] *= 0.5;
+    coefficients
+}
+
+/// integral of the Chebyshev series over [-1, 1]
+#[inline]
+fn integrate_series(coefficients: &[f64]) -> f64 {
+    coefficients
+        .iter()
+        .enumerate()
+        .step_by(2)
+        .map(|(k, &c)| 2. * c / (1. - (k * k) as f64))
+        .sum()
+}
+
+fn integrate_impl<F: Integrand + ?Sized>(
+    f: &mut F,
+    range: &Range,
+    config: &IntegrationConfig,
+    ws: &mut WorkSpace,
+    expansions: &mut Vec<ChebyshevExpansion>,
+) -> IntegrationResult {
+    // number of evaluations in the worst case of `qcc`
+    const MAX_EVALS_QCC: usize = MAX_N + 1;
+    // number of evaluations in the best case of `qcc`
+    const MIN_EVALS_QCC: usize = 2 * MIN_N + 1;
+
+    if config.max_evals < MAX_EVALS_QCC {
+        return IntegrationResult::with_error(Solution::default(), InsufficientIteration);
+    }
+
+    let length = (range.end - range.begin).abs();
+    let table = CosineTable::new();
+
+    let result0 = qcc(f, range, &table, &|estimate| {
+        config.tolerance.to_abs(estimate.abs())
+    });
+    let mut nevals = result0.nevals;
+
+    ws.clear();
+    ws.reserve((config.max_evals - nevals) / (2 * MIN_EVALS_QCC) + 1);
+    ws.push(SubRangeInfo::new(
+        range.clone(),
+        result0.estimate,
+        result0.delta,
+        0,
+    ));
+    expansions.push(result0.expansion);
+
+    if result0.estimate.is_nan() {
+        return finish(
+            result0.estimate,
+            result0.delta,
+            nevals,
+            Some(NanValueEncountered),
+        );
+    }
+
+    let mut area = result0.estimate;
+    let mut errsum = result0.delta;
+    let mut tolerance = config.tolerance.to_abs(area.abs());
+    let mut error = None;
+
+    while errsum > tolerance {
+        if config.max_evals - nevals < 2 * MAX_EVALS_QCC {
+            error = Some(InsufficientIteration);
+            break;
+        }
+
+        // bisect the subrange with the largest error estimate
+        let i = ws.i;
+        let info = ws.get().clone();
+        let (r1, r2) = bisect(&info.range);
+
+        // distribute the tolerance in proportion to the length of subranges
+        let local_tolerance = tolerance * 0.5 * (info.range.end - info.range.begin).abs() / length;
+
+        let result1 = qcc(f, &r1, &table, &|_| local_tolerance);
+        let result2 = qcc(f, &r2, &table, &|_| local_tolerance);
+        nevals += result1.nevals + result2.nevals;
+
+        if result1.estimate.is_nan() || result2.estimate.is_nan() {
+            error = Some(NanValueEncountered);
+            break;
+        }
+
+        area += result1.estimate + result2.estimate - info.estimate;
+        errsum += result1.delta + result2.delta - info.delta;
+        tolerance = config.tolerance.to_abs(area.abs());
+
+        if subrange_too_small(r1.begin, r1.end, r2.end) {
+            error = Some(SubrangeTooSmall);
+        }
+
+        let level = info.level + 1;
+        let s1 = SubRangeInfo::new(r1, result1.estimate, result1.delta, level);
+        let s2 = SubRangeInfo::new(r2, result2.estimate, result2.delta, level);
+
+        // keep `expansions` in the same order as the subranges in workspace
+        if result2.delta > result1.delta {
+            expansions[i] = result2.expansion;
+            expansions.push(result1.expansion);
+        } else {
+            expansions[i] = result1.expansion;
+            expansions.push(result2.expansion);
+        }
+        ws.update(s1, s2);
+
+        if error.is_some() {
+            break;
+        }
+    }
+
+    finish(ws.sum_results(), errsum, nevals, error)
+}
+
+#[inline]
+#[must_use]
+fn finish(
+    estimate: f64,
+    delta: f64,
+    nevals: usize,
+    error: Option<RuntimeError>,
+) -> IntegrationResult {
+    IntegrationResult {
+        value: Solution {
+            estimate,
+            delta,
+            nevals,
+        },
+        error,
+    }
+}
@@ -0,0 +1,482 @@
+use alloc::borrow::Cow;
+use alloc::vec::Vec;
+use core::f64::consts::PI;
+
+use crate::common::{IntegrationResult, Solution};
+use crate::error::RuntimeError::{self, *};
+use crate::single::algorithm::Algorithm;
+use crate::single::common::{Integrand, IntegrationConfig, Range};
+use crate::single::util::{transform_range, IntegrandWrapper};
+use crate::single::workspace::{SubRangeInfo, WorkSpace};
+use crate::utils::CowMut;
+
+/// number of intervals between the nodes for each depth
+const N: [usize; 4] = [4, 8, 16, 32];
+/// index step of the nodes for each depth
+const SKIP: [usize; 4] = [8, 4, 2, 1];
+/// maximum number of successive divergent subdivisions
+const NDIV_MAX: usize = 20;
+
+/// Doubly-adaptive integration algorithm based on CQUAD
+///
+/// Each subrange is integrated with the interpolatory rules on the nested
+/// Chebyshev nodes of 5, 9, 17 and 33 points. The subrange with the largest
+/// error is either refined by increasing the degree of the rule, or bisected
+/// if the highest degree is reached or the interpolant changes too much.
+///
+/// If the integrand returns a non-finite value (NaN or infinity) at some
+/// points, these nodes are dropped from the interpolation and the subranges
+/// around them are refined. So this algorithm can handle the integrands which
+/// are not defined at isolated points, including the end points of the range.
+///
+/// # Examples
+///
+/// ```
+/// use gkquad::single::algorithm::CQUAD;
+/// use gkquad::single::Integrator;
+///
+/// // the integrand is NaN at x = 0
+/// let result = Integrator::with_algorithm(|x: f64| x.sin() / x, CQUAD::new())
+///     .run(-1.0..1.0)
+///     .unwrap()
+///     .estimate;
+/// ```
+#[derive(Clone)]
+pub struct CQUAD<'a> {
+    workspace: CowMut<'a, WorkSpace>,
+}
+
+impl<'a> CQUAD<'a> {
+    #[inline]
+    pub fn new() -> Self {
+        Self {
+            workspace: CowMut::Owned(WorkSpace::new()),
+        }
+    }
+
+    #[inline]
+    #[doc(hidden)]
+    pub fn with_workspace(ws: &'a mut WorkSpace) -> Self {
+        Self {
+            workspace: CowMut::Borrowed(ws),
+        }
+    }
+}
+
+impl<'a, F: Integrand + ?Sized> Algorithm<F> for CQUAD<'a> {
+    fn integrate(
+        &mut self,
+        f: &mut F,
+        range: &Range,
+        config: &IntegrationConfig,
+    ) -> IntegrationResult {
+        let transform = if range.begin.is_finite() && range.end.is_finite() {
+            None
+        } else {
+            Some(&config.range_transform)
+        };
+        let mut wrapper = IntegrandWrapper {
+            inner: f,
+            transform,
+        };
+        let range = match transform {
+            Some(transform) => Cow::Owned(transform_range(range, transform)),
+            None => Cow::Borrowed(range),
+        };
+
+        if range.begin > range.end {
+            let range = unsafe { Range::new_unchecked(range.end, range.begin) };
+            let mut result = integrate_impl(&mut wrapper, &range, config, &mut *self.workspace);
+            result.value.estimate = -result.value.estimate;
+            result
+        } else {
+            integrate_impl(&mut wrapper, &range, config, &mut *self.workspace)
+        }
+    }
+}
+
+extra_traits!(CQUAD<'a>);
+
+/// additional information of the subrange
+#[derive(Clone)]
+struct Interval {
+    /// function values at the nodes, or NaN if the value is not finite
+    fx: [f64; 33],
+    /// Chebyshev coefficients of the interpolant at the current depth
+    c: [f64; 33],
+    /// `c[0]` at depth 0, which is used to detect the divergence
+    c0: f64,
+    /// current depth of the rule
+    depth: usize,
+    /// number of the successive subdivisions where the integral has grown
+    ndiv: usize,
+}
+
+/// `-cos(πi/32)` for i = 0, ..., 32
+#[inline]
+fn node(i: usize) -> f64 {
+    -(PI * i as f64 / 32.).cos()
+}
+
+/// Evaluate the integrand at the nodes of the given indices, and return the
+/// number of evaluations.
+fn evaluate<F: Integrand + ?Sized, I: Iterator<Item = usize> + Clone>(
+    f: &mut F,
+    range: &Range,
+    fx: &mut [f64; 33],
+    indices: I,
+) -> usize {
+    let center = 0.5 * (range.begin + range.end);
+    let half_length = 0.5 * (range.end - range.begin);
+
+    let mut xs: Vec<f64> = indices
+        .clone()
+        .map(|i| center + half_length * node(i))
+        .collect();
+    f.apply_to_slice(&mut xs);
+
+    for (i, &y) in indices.zip(xs.iter()) {
+        fx[i] = if y.is_finite() { y } else { core::f64::NAN };
+    }
+
+    xs.len()
+}
+
+/// Evaluate the integrand at the nodes of the given depth which are not
+/// evaluated at the lower depth, and return the number of evaluations.
+#[inline]
+fn refine<F: Integrand + ?Sized>(
+    f: &mut F,
+    range: &Range,
+    fx: &mut [f64; 33],
+    depth: usize,
+) -> usize {
+    let step = SKIP[depth];
+    evaluate(f, range, fx, (step..=32).step_by(2 * step))
+}
+
+/// Calculate the Chebyshev coefficients of the interpolant at the nodes of
+/// the given depth. The nodes with non-finite value are dropped, and the
+/// degree of the interpolant decreases accordingly.
+fn coefficients(fx: &[f64; 33], depth: usize) -> [f64; 33] {
+    let n = N[depth];
+    let step = SKIP[depth];
+    let mut c = [0.0; 33];
+
+    if (0..=n).all(|j| fx[j * step].is_finite()) {
+        // discrete cosine transform
+        for (k, ck) in c.iter_mut().enumerate().take(n + 1) {
+            let mut sum = 0.0;
+            for j in 0..=n {
+                // the end points have half weights
+                let v = if j == 0 || j == n { 0.5 } else { 1.0 } * fx[j * step];
+                sum += v * (PI * ((k * (n - j)) % (2 * n)) as f64 / n as f64).cos();
+            }
+            *ck = sum * 2. / n as f64;
+        }
+        c[0] *= 0.5;
+        c[n] *= 0.5;
+        return c;
+    }
+
+    // solve the interpolation problem with the remaining nodes
+    let kept: Vec<usize> = (0..=n).filter(|&j| fx[j * step].is_finite()).collect();
+    let m = kept.len();
+    let mut a = [[0.0; 34]; 33];
+
+    for (r, &j) in kept.iter().enumerate() {
+        let x = node(j * step);
+        let (mut t0, mut t1) = (1.0, x);
+        for v in a[r].iter_mut().take(m) {
+            *v = t0;
+            let t2 = 2. * x * t1 - t0;
+            t0 = t1;
+            t1 = t2;
+        }
+        a[r][m] = fx[j * step];
+    }
+
+    // Gaussian elimination with partial pivoting
+    for col in 0..m {
+        let pivot = (col..m)
+            .max_by(|&p, &q| a[p][col].abs().partial_cmp(&a[q][col].abs()).unwrap())
+            .unwrap();
+        a.swap(col, pivot);
+
+        let pivot_row = a[col];
+        for row in a.iter_mut().take(m).skip(col + 1) {
+            let factor = row[col] / pivot_row[col];
+            for (v, p) in row[col..=m].iter_mut().zip(&pivot_row[col..=m]) {
+                *v -= factor * p;
+            }
+        }
+    }
+
+    for k in (0..m).rev() {
+        let s: f64 = (k + 1..m).map(|l| a[k][l] * c[l]).sum();
+        c[k] = (a[k][m] - s) / a[k][k];
+    }
+
+    c
+}
+
+/// evaluate the Chebyshev series at `t` by Clenshaw's recurrence formula
+#[inline]
+fn chebyshev_eval(c: &[f64; 33], t: f64) -> f64 {
+    let (mut b1, mut b2) = (0.0, 0.0);
+    for &ck in c.iter().skip(1).rev() {
+        let tmp = 2. * t * b1 - b2 + ck;
+        b2 = b1;
+        b1 = tmp;
+    }
+    t * b1 - b2 + c[0]
+}
+
+/// integral of the Chebyshev series over [-1, 1]
+#[inline]
+fn integrate_series(c: &[f64; 33]) -> f64 {
+    c.iter()
+        .enumerate()
+        .step_by(2)
+        .map(|(k, &ck)| 2. * ck / (1. - (k * k) as f64))
+        .sum()
+}
+
+/// L2 norm of the difference of the coefficients
+#[inline]
+fn norm_diff(c1: &[f64; 33], c2: &[f64; 33]) -> f64 {
+    c1.iter()
+        .zip(c2.iter())
+        .map(|(x, y)| (x - y) * (x - y))
+        .sum::<f64>()
+        .sqrt()
+}
+
+/// Create the child subrange of `parent` and return the number of
+/// evaluations. `right` specifies which half of the parent is created.
+fn split<F: Integrand + ?Sized>(
+    f: &mut F,
+    parent: &Interval,
+    info: &SubRangeInfo,
+    right: bool,
+) -> (SubRangeInfo, Interval, usize) {
+    let center = 0.5 * (info.range.begin + info.range.end);
+    let range = if right {
+        unsafe { Range::new_unchecked(center, info.range.end) }
+    } else {
+        unsafe { Range::new_unchecked(info.range.begin, center) }
+    };
+    let half_length = 0.5 * (range.end - range.begin);
+
+    let mut fx = [core::f64::NAN; 33];
+    if right {
+        fx[0] = parent.fx[16];
+        fx[32] = parent.fx[32];
+    } else {
+        fx[0] = parent.fx[0];
+        fx[32] = parent.fx[16];
+    }
+    // the end points are shared with the parent, and the interior nodes at
+    // depth 0 are evaluated
+    let nevals = evaluate(f, &range, &mut fx, (SKIP[0]..32).step_by(SKIP[0]));
+    let c = coefficients(&fx, 0);
+
+    // the interpolant of the parent restricted to the child
+    let shift = if right { 1. } else { -1. };
+    let mut restricted = [0.0; 33];
+    for (i, v) in restricted.iter_mut().enumerate() {
+        *v = chebyshev_eval(&parent.c, 0.5 * (node(i) + shift));
+    }
+    let restricted = coefficients(&restricted, 3);
+
+    let delta = 2. * half_length * norm_diff(&c, &restricted);
+    let estimate = half_length * integrate_series(&c);
+
+    let diverging = parent.c0.abs() > 0.0 && c[0] / parent.c0 > 2.;
+
+    let child = Interval {
+        fx,
+        c,
+        c0: c[0],
+        depth: 0,
+        ndiv: parent.ndiv + diverging as usize,
+    };
+    let info = SubRangeInfo::new(range, estimate, delta, info.level + 1);
+
+    (info, child, nevals)
+}
+
+fn integrate_impl<F: Integrand + ?Sized>(
+    f: &mut F,
+    range: &Range,
+    config: &IntegrationConfig,
+    ws: &mut WorkSpace,
+) -> IntegrationResult {
+    if config.max_evals < 33 {
+        return IntegrationResult::with_error(Solution::default(), InsufficientIteration);
+    }
+
+    // initial integral with the highest degree
+    let half_length = 0.5 * (range.end - range.begin);
+    let mut fx = [core::f64::NAN; 33];
+    let mut nevals = evaluate(f, range, &mut fx, 0..=32);
+
+    let c = coefficients(&fx, 3);
+    let c_lower = coefficients(&fx, 2);
+    let nc = c.iter().map(|x| x * x).sum::<f64>().sqrt();
+    let ncdiff = norm_diff(&c, &c_lower);
+
+    let estimate = half_length * integrate_series(&c);
+    let mut delta = 2. * half_length * ncdiff;
+    if ncdiff / nc > 0.1 && delta < 2. * half_length * nc {
+        delta = 2. * half_length * nc;
+    }
+
+    let mut intervals = Vec::new();
+    intervals.push(Interval {
+        fx,
+        c,
+        c0: coefficients(&fx, 0)[0],
+        depth: 3,
+        ndiv: 0,
+    });
+
+    ws.clear();
+    ws.reserve((config.max_evals - nevals) / (2 * (N[0] - 1)) + 1);
+    ws.push(SubRangeInfo::new(range.clone(), estimate, delta, 0));
+
+    let mut area = estimate;
+    let mut errsum = delta;
+    // sum of the errors over the subranges which are never refined
+    let mut err_final = 0.0;
+    let mut error = None;
+
+    loop {
+        let tolerance = config.tolerance.to_abs(area.abs());
+
+        if errsum <= tolerance || errsum == 0.0 {
+            break;
+        }
+
+        // the refinement of the remaining subranges cannot improve the result
+        // any more
+        if err_final > tolerance && errsum - err_final < tolerance {
+            error = Some(RoundoffError);
+            break;
+        }
+
+        // the dropped subranges have zero error in the workspace, so the
+        // subrange with the largest error is never dropped unless all of them
+        // are
+        let i = ws.i;
+        let mut info = ws.get().clone();
+        if info.delta == 0.0 {
+            error = Some(RoundoffError);
+            break;
+        }
+
+        let half_length = 0.5 * (info.range.end - info.range.begin);
+        let mut split_needed = true;
+
+        // try to increase the degree
+        if intervals[i].depth < 3 {
+            let d = intervals[i].depth + 1;
+            if nevals + N[d - 1] > config.max_evals {
+                error = Some(InsufficientIteration);
+                break;
+            }
+
+            let iv = &mut intervals[i];
+            nevals += refine(f, &info.range, &mut iv.fx, d);
+
+            let c = coefficients(&iv.fx, d);
+            let nc = c.iter().map(|x| x * x).sum::<f64>().sqrt();
+            let ncdiff = norm_diff(&c, &iv.c);
+
+            iv.c = c;
+            iv.depth = d;
+
+            let estimate = half_length * integrate_series(&c);
+            let delta = 2. * half_length * ncdiff;
+            area += estimate - info.estimate;
+            errsum += delta - info.delta;
+            info.estimate = estimate;
+            info.delta = delta;
+
+            // split the interval prematurely if the interpolant changes too
+            // much
+            split_needed = nc > 0.0 && ncdiff / nc > 0.1;
+        }
+
+        let collapsed = info.range.begin + half_length * (1. + node(0))
+            >= info.range.begin + half_length * (1. + node(1))
+            || info.range.begin + half_length * (1. + node(31))
+                >= info.range.begin + half_length * (1. + node(32));
+
+        if collapsed || info.delta < info.estimate.abs() * core::f64::EPSILON * 10. {
+            // keep the contribution of this subrange, but move its error to
+            // `err_final` so that it is never refined again
+            err_final += info.delta;
+            info.delta = 0.0;
+            ws.replace(info);
+            continue;
+        }
+
+        if !split_needed {
+            ws.replace(info);
+            continue;
+        }
+
+        // each child evaluates the interior nodes at depth 0
+        if nevals + 2 * (N[0] - 1) > config.max_evals {
+            ws.replace(info);
+            error = Some(InsufficientIteration);
+            break;
+        }
+
+        let (s1, iv1, n1) = split(f, &intervals[i], &info, false);
+        let (s2, iv2, n2) = split(f, &intervals[i], &info, true);
+        nevals += n1 + n2;
+
+        if iv1.ndiv > NDIV_MAX && 2 * iv1.ndiv > s1.level
+            || iv2.ndiv > NDIV_MAX && 2 * iv2.ndiv > s2.level
+        {
+            ws.replace(info);
+            error = Some(Divergent);
+            break;
+        }
+
+        area += s1.estimate + s2.estimate - info.estimate;
+        errsum += s1.delta + s2.delta - info.delta;
+
+        // keep `intervals` in the same order as the subranges in workspace
+        if s2.delta > s1.delta {
+            intervals[i] = iv2;
+            intervals.push(iv1);
+        } else {
+            intervals[i] = iv1;
+            intervals.push(iv2);
+        }
+        ws.update(s1, s2);
+    }
+
+    finish(ws.sum_results(), errsum, nevals, error)
+}
+
+#[inline]
+#[must_use]
+fn finish(
+    estimate: f64,
+    delta: f64,
+    nevals: usize,
+    error: Option<RuntimeError>,
+) -> IntegrationResult {
+    IntegrationResult {
+        value: Solution {
+            estimate,
+            delta,
+            nevals,
+        },
+        error,
+    }
+}
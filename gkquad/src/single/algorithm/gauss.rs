@@ -0,0 +1,309 @@
+use alloc::vec::Vec;
+
+use crate::common::{IntegrationResult, Solution};
+use crate::error::RuntimeError::{self, *};
+use crate::single::algorithm::Algorithm;
+use crate::single::common::{Integrand, IntegrationConfig, Range};
+use crate::single::GaussRule;
+
+/// order of the rule at the first level
+const MIN_ORDER: usize = 8;
+/// maximum level of the refinement
+const MAX_LEVEL: usize = 10;
+
+/// Adaptive-order Gauss-Laguerre quadrature
+///
+/// Calculates `∫ e^-|x-a| f(x) dx` over the semi-infinite range whose finite
+/// end point is `a`. The order of the rule doubles from 8 until the
+/// difference between the successive results meets the tolerance.
+///
+/// Note that the integrand `f` must not contain the weight function
+/// `e^-|x-a|`. If the range is not semi-infinite, `InvalidRange` error is
+/// returned.
+///
+/// # Examples
+///
+/// ```
+/// use core::f64::INFINITY;
+/// use gkquad::single::algorithm::GaussLaguerre;
+/// use gkquad::single::Integrator;
+///
+/// // ∫ e^-x cos(x) dx (0 < x < ∞)
+/// let result = Integrator::with_algorithm(|x: f64| x.cos(), GaussLaguerre::new())
+///     .run(0.0..INFINITY)
+///     .unwrap()
+///     .estimate;
+/// ```
+#[derive(Clone)]
+pub struct GaussLaguerre {
+    rules: Vec<GaussRule>,
+}
+
+impl GaussLaguerre {
+    #[inline]
+    pub fn new() -> Self {
+        Self { rules: Vec::new() }
+    }
+}
+
+impl<F: Integrand + ?Sized> Algorithm<F> for GaussLaguerre {
+    fn integrate(
+        &mut self,
+        f: &mut F,
+        range: &Range,
+        config: &IntegrationConfig,
+    ) -> IntegrationResult {
+        let (begin, end) = (range.begin, range.end);
+        let (a, infinity) = if begin.is_finite() && end.is_infinite() {
+            (begin, end)
+        } else if begin.is_infinite() && end.is_finite() {
+            (end, begin)
+        } else {
+            return IntegrationResult::with_error(Solution::default(), InvalidRange);
+        };
+
+        let direction = if infinity > 0.0 { 1.0 } else { -1.0 };
+        let negative = (infinity == begin) == (infinity > 0.0);
+
+        let transform = |t: f64| a + direction * t;
+        let mut result = integrate_impl(
+            f,
+            &mut self.rules,
+            &GaussRule::laguerre,
+            &transform,
+            1.0,
+            config,
+        );
+        if negative {
+            result.value.estimate = -result.value.estimate;
+        }
+        result
+    }
+}
+
+extra_traits!(GaussLaguerre);
+
+/// Adaptive-order Gauss-Hermite quadrature
+///
+/// Calculates `∫ e^-x² f(x) dx` over (-∞, ∞). The order of the rule doubles
+/// from 8 until the difference between the successive results meets the
+/// tolerance.
+///
+/// Note that the integrand `f` must not contain the weight function `e^-x²`.
+/// If the range is not (-∞, ∞), `InvalidRange` error is returned.
+///
+/// # Examples
+///
+/// ```
+/// use core::f64::{INFINITY, NEG_INFINITY};
+/// use gkquad::single::algorithm::GaussHermite;
+/// use gkquad::single::Integrator;
+///
+/// // ∫ e^-x² cos(x) dx (-∞ < x < ∞)
+/// let result = Integrator::with_algorithm(|x: f64| x.cos(), GaussHermite::new())
+///     .run(NEG_INFINITY..INFINITY)
+///     .unwrap()
+///     .estimate;
+/// ```
+#[derive(Clone)]
+pub struct GaussHermite {
+    rules: Vec<GaussRule>,
+}
+
+impl GaussHermite {
+    #[inline]
+    pub fn new() -> Self {
+        Self { rules: Vec::new() }
+    }
+}
+
+impl<F: Integrand + ?Sized> Algorithm<F> for GaussHermite {
+    fn integrate(
+        &mut self,
+        f: &mut F,
+        range: &Range,
+        config: &IntegrationConfig,
+    ) -> IntegrationResult {
+        if range.begin.is_finite() || range.end.is_finite() {
+            return IntegrationResult::with_error(Solution::default(), InvalidRange);
+        }
+
+        let mut result =
+            integrate_impl(f, &mut self.rules, &GaussRule::hermite, &|x| x, 1.0, config);
+        if range.begin > range.end {
+            result.value.estimate = -result.value.estimate;
+        }
+        result
+    }
+}
+
+extra_traits!(GaussHermite);
+
+/// Adaptive-order Gauss-Jacobi quadrature
+///
+/// Calculates `∫ (b-x)^α (x-a)^β f(x) dx` over the finite range [a, b]. The
+/// order of the rule doubles from 8 until the difference between the
+/// successive results meets the tolerance.
+///
+/// Note that the integrand `f` must not contain the weight function. If the
+/// range is reversed, `a` and `b` are swapped and the sign of the result is
+/// inverted. If the range is not finite, `InvalidRange` error is returned.
+///
+/// # Examples
+///
+/// ```
+/// use gkquad::single::algorithm::GaussJacobi;
+/// use gkquad::single::Integrator;
+///
+/// // ∫ cos(x) / √(x (1-x)) dx (0 < x < 1)
+/// let result = Integrator::with_algorithm(|x: f64| x.cos(), GaussJacobi::new(-0.5, -0.5))
+///     .run(0.0..1.0)
+///     .unwrap()
+///     .estimate;
+/// ```
+#[cfg(feature = "std")]
+#[cfg_attr(docsrs, doc(cfg(feature = "std")))]
+#[derive(Clone)]
+pub struct GaussJacobi {
+    alpha: f64,
+    beta: f64,
+    rules: Vec<GaussRule>,
+}
+
+#[cfg(feature = "std")]
+impl GaussJacobi {
+    /// Create a new `GaussJacobi` object with the weight function
+    /// `(b-x)^α (x-a)^β`
+    ///
+    /// # Panics
+    ///
+    /// Panics if either `alpha` or `beta` is not greater than -1.
+    #[inline]
+    pub fn new(alpha: f64, beta: f64) -> Self {
+        assert!(
+            alpha > -1.0 && beta > -1.0,
+            "exponents of the weight function must be greater than -1"
+        );
+        Self {
+            alpha,
+            beta,
+            rules: Vec::new(),
+        }
+    }
+}
+
+#[cfg(feature = "std")]
+impl<F: Integrand + ?Sized> Algorithm<F> for GaussJacobi {
+    fn integrate(
+        &mut self,
+        f: &mut F,
+        range: &Range,
+        config: &IntegrationConfig,
+    ) -> IntegrationResult {
+        if range.begin.is_infinite() || range.end.is_infinite() {
+            return IntegrationResult::with_error(Solution::default(), InvalidRange);
+        }
+
+        let (a, b) = if range.begin <= range.end {
+            (range.begin, range.end)
+        } else {
+            (range.end, range.begin)
+        };
+
+        let (alpha, beta) = (self.alpha, self.beta);
+        let center = 0.5 * (a + b);
+        let half = 0.5 * (b - a);
+        let scale = half.powf(alpha + beta + 1.);
+
+        let make = |n| GaussRule::jacobi(n, alpha, beta);
+        let transform = |t: f64| center + half * t;
+        let mut result = integrate_impl(f, &mut self.rules, &make, &transform, scale, config);
+        if range.begin > range.end {
+            result.value.estimate = -result.value.estimate;
+        }
+        result
+    }
+}
+
+#[cfg(feature = "std")]
+impl core::fmt::Debug for GaussJacobi {
+    fn fmt(&self, f: &mut core::fmt::Formatter) -> core::fmt::Result {
+        f.debug_struct("GaussJacobi")
+            .field("alpha", &self.alpha)
+            .field("beta", &self.beta)
+            .finish()
+    }
+}
+
+#[cfg(feature = "std")]
+impl PartialEq for GaussJacobi {
+    #[inline]
+    fn eq(&self, other: &Self) -> bool {
+        self.alpha == other.alpha && self.beta == other.beta
+    }
+}
+
+/// Apply the rules of order 8, 16, 32, ... until the difference between the
+/// successive results becomes smaller than the tolerance.
+///
+/// The rules are computed by `make` and cached in `rules`. The results are
+/// multiplied by `scale`.
+fn integrate_impl<F: Integrand + ?Sized>(
+    f: &mut F,
+    rules: &mut Vec<GaussRule>,
+    make: &dyn Fn(usize) -> GaussRule,
+    transform: &dyn Fn(f64) -> f64,
+    scale: f64,
+    config: &IntegrationConfig,
+) -> IntegrationResult {
+    let mut estimate = 0.0;
+    let mut delta = core::f64::MAX;
+    let mut nevals = 0;
+
+    for level in 0..=MAX_LEVEL {
+        let order = MIN_ORDER << level;
+        if nevals + order > config.max_evals {
+            return finish(estimate, delta, nevals, Some(InsufficientIteration));
+        }
+
+        if rules.len() <= level {
+            rules.push(make(order));
+        }
+        let rule = &rules[level];
+
+        let previous = estimate;
+        estimate = scale * rule.integrate_with(f, transform);
+        nevals += rule.len();
+
+        if estimate.is_nan() {
+            return finish(estimate, delta, nevals, Some(NanValueEncountered));
+        }
+
+        if level > 0 {
+            delta = (estimate - previous).abs();
+            if delta <= config.tolerance.to_abs(estimate.abs()) {
+                return finish(estimate, delta, nevals, None);
+            }
+        }
+    }
+
+    finish(estimate, delta, nevals, Some(InsufficientIteration))
+}
+
+#[inline]
+#[must_use]
+fn finish(
+    estimate: f64,
+    delta: f64,
+    nevals: usize,
+    error: Option<RuntimeError>,
+) -> IntegrationResult {
+    IntegrationResult {
+        value: Solution {
+            estimate,
+            delta,
+            nevals,
+        },
+        error,
+    }
+}
@@ -0,0 +1,240 @@
+use alloc::vec::Vec;
+
+use crate::common::{IntegrationResult, Solution};
+use crate::error::RuntimeError::{self, *};
+use crate::single::algorithm::Algorithm;
+use crate::single::common::{Integrand, IntegrationConfig, Range};
+
+/// √(2/3)
+const ALPHA: f64 = 0.816496580927726;
+/// 1/√5
+const BETA: f64 = 0.447213595499958;
+
+/// abscissae of the 13-point Kronrod extension of the 7-point Lobatto rule
+const XK: [f64; 7] = [
+    1.0,
+    0.942882415695480,
+    ALPHA,
+    0.641853342345781,
+    BETA,
+    0.236383199662150,
+    0.0,
+];
+
+/// weights of the 13-point Kronrod extension of the 7-point Lobatto rule
+const WK: [f64; 7] = [
+    0.0158271919734802,
+    0.0942738402188500,
+    0.155071987336585,
+    0.188821573960182,
+    0.199773405226859,
+    0.224926465333340,
+    0.242611071901408,
+];
+
+/// number of the function evaluations for the initial estimate
+const INITIAL_POINTS: usize = 13;
+
+/// Adaptive Gauss-Lobatto quadrature
+///
+/// Each subrange is integrated with the 4-point and 7-point Gauss-Lobatto
+/// rules, and the subrange is divided into 6 subranges at the interior nodes
+/// if the difference between them does not meet the tolerance. The function
+/// values at the end points of the subranges are reused by the child
+/// subranges, so that each subdivision costs only 5 function evaluations.
+///
+/// The tolerance is distributed over the subranges in proportion to their
+/// lengths, and the sum of the differences is returned as the error estimate.
+///
+/// This algorithm is robust for the integrands which have discontinuities or
+/// kinks at unknown locations. Since the Lobatto rules use the end points of
+/// the subranges, the integrand must be finite at the end points of the
+/// range. This algorithm cannot be applied to the infinite range.
+///
+/// # Examples
+///
+/// ```
+/// use gkquad::single::algorithm::GaussLobatto;
+/// use gkquad::single::Integrator;
+///
+/// let result = Integrator::with_algorithm(|x: f64| (x - 0.3).abs(), GaussLobatto::new())
+///     .run(0.0..1.0)
+///     .unwrap()
+///     .estimate;
+/// ```
+///
+/// # References
+///
+/// * W. Gander and W. Gautschi, Adaptive quadrature - revisited, BIT 40
+///   (2000), 84-101.
+#[derive(Clone)]
+pub struct GaussLobatto {
+    _private: (),
+}
+
+impl GaussLobatto {
+    #[inline]
+    pub fn new() -> Self {
+        Self { _private: () }
+    }
+}
+
+impl<F: Integrand + ?Sized> Algorithm<F> for GaussLobatto {
+    fn integrate(
+        &mut self,
+        f: &mut F,
+        range: &Range,
+        config: &IntegrationConfig,
+    ) -> IntegrationResult {
+        if !range.begin.is_finite() || !range.end.is_finite() {
+            return IntegrationResult::with_error(Solution::default(), InvalidRange);
+        }
+
+        if range.begin <= range.end {
+            integrate_impl(f, range.begin, range.end, config)
+        } else {
+            let mut result = integrate_impl(f, range.end, range.begin, config);
+            result.value.estimate = -result.value.estimate;
+            result
+        }
+    }
+}
+
+extra_traits!(GaussLobatto);
+
+/// subrange whose interior nodes have not been evaluated yet
+struct Interval {
+    a: f64,
+    b: f64,
+    fa: f64,
+    fb: f64,
+}
+
+fn integrate_impl<F: Integrand + ?Sized>(
+    f: &mut F,
+    a: f64,
+    b: f64,
+    config: &IntegrationConfig,
+) -> IntegrationResult {
+    if config.max_evals < INITIAL_POINTS {
+        return IntegrationResult::with_error(Solution::default(), InsufficientIteration);
+    }
+
+    let center = 0.5 * (a + b);
+    let half = 0.5 * (b - a);
+
+    // initial estimate by 13-point Kronrod rule
+    let mut y = [0.0; INITIAL_POINTS];
+    for (i, &x) in XK.iter().enumerate() {
+        y[i] = center - half * x;
+        y[12 - i] = center + half * x;
+    }
+    f.apply_to_slice(&mut y);
+    let mut nevals = INITIAL_POINTS;
+
+    let initial = half * (0..6).fold(WK[6] * y[6], |acc, i| acc + WK[i] * (y[i] + y[12 - i]));
+
+    if initial.is_nan() {
+        return finish(initial, core::f64::MAX, nevals, Some(NanValueEncountered));
+    }
+
+    let tolerance = config.tolerance.to_abs(initial.abs());
+    // the difference below this value cannot be reduced by the subdivision
+    let roundoff = core::f64::EPSILON * if initial == 0.0 { b - a } else { initial.abs() };
+
+    let mut estimate = 0.0;
+    let mut delta = 0.0;
+    let mut insufficient = false;
+    let mut stack: Vec<Interval> = Vec::new();
+
+    // the interior nodes of the whole range were already evaluated
+    let mut interior = Some([y[2], y[4], y[6], y[8], y[10]]);
+    let mut current = Interval {
+        a,
+        b,
+        fa: y[0],
+        fb: y[12],
+    };
+
+    loop {
+        let Interval { a: l, b: r, fa, fb } = current;
+        let m = 0.5 * (l + r);
+        let h = 0.5 * (r - l);
+        let x = [m - ALPHA * h, m - BETA * h, m, m + BETA * h, m + ALPHA * h];
+
+        let fx = match interior.take() {
+            Some(fx) => fx,
+            None => {
+                let mut fx = x;
+                f.apply_to_slice(&mut fx);
+                nevals += 5;
+                fx
+            }
+        };
+
+        // 4-point and 7-point Gauss-Lobatto rules
+        let i2 = h / 6. * (fa + fb + 5. * (fx[1] + fx[3]));
+        let i1 = h / 1470.
+            * (77. * (fa + fb) + 432. * (fx[0] + fx[4]) + 625. * (fx[1] + fx[3]) + 672. * fx[2]);
+
+        if i1.is_nan() {
+            return finish(i1, core::f64::MAX, nevals, Some(NanValueEncountered));
+        }
+
+        let diff = (i1 - i2).abs();
+        let converged = diff <= tolerance * (r - l) / (b - a) || diff <= roundoff;
+        let collapsed = x[0] <= l || r <= x[4];
+        // the children must be evaluated within the budget
+        let affordable = nevals + 5 * (stack.len() + 6) <= config.max_evals;
+
+        if converged || collapsed || !affordable {
+            insufficient |= !converged && !collapsed;
+            estimate += i1;
+            delta += diff;
+        } else {
+            let nodes = [l, x[0], x[1], x[2], x[3], x[4], r];
+            let values = [fa, fx[0], fx[1], fx[2], fx[3], fx[4], fb];
+            for j in (0..6).rev() {
+                stack.push(Interval {
+                    a: nodes[j],
+                    b: nodes[j + 1],
+                    fa: values[j],
+                    fb: values[j + 1],
+                });
+            }
+        }
+
+        current = match stack.pop() {
+            Some(interval) => interval,
+            None => break,
+        };
+    }
+
+    let error = if insufficient {
+        Some(InsufficientIteration)
+    } else if delta > tolerance.max(config.tolerance.to_abs(estimate.abs())) {
+        Some(RoundoffError)
+    } else {
+        None
+    };
+
+    finish(estimate, delta, nevals, error)
+}
+
+#[inline]
+#[must_use]
+fn finish(
+    estimate: f64,
+    delta: f64,
+    nevals: usize,
+    error: Option<RuntimeError>,
+) -> IntegrationResult {
+    IntegrationResult {
+        value: Solution {
+            estimate,
+            delta,
+            nevals,
+        },
+        error,
+    }
+}
@@ -5,21 +5,105 @@
 //! * [Numerical Integration — GNU GSL documentation](https://www.gnu.org/software/gsl/doc/html/integration.html)
 //! * [Netlib quadpack library](http://www.netlib.org/quadpack/)
 
-use super::common::{Integrand, IntegrationConfig, Range};
-use crate::common::IntegrationResult;
+use super::common::{
+    Integrand, IntegrandComplex, IntegrandVec, IntegrationConfig, Range, TryIntegrand,
+};
+use super::util::TryIntegrandWrapper;
+use crate::common::{
+    ComplexIntegrationResult, IntegrandError, IntegrationResult, TryIntegrationResult,
+    VecIntegrationResult,
+};
+use crate::float::Float;
 
 /// 1-dimentional integration algorithm API
 ///
+/// `T` is the floating-point type of the integrand. Currently `QAGS` and `QAGP`
+/// support the types other than `f64`.
+///
+/// # Notes
+///
+/// This API is still unstable, and may changes dramatically in the future.
+pub trait Algorithm<F: Integrand<T> + ?Sized, T: Float = f64> {
+    fn integrate(
+        &mut self,
+        f: &mut F,
+        range: &Range<T>,
+        config: &IntegrationConfig<T>,
+    ) -> IntegrationResult<T>;
+}
+
+/// 1-dimentional integration algorithm API for the vector-valued integrand
+///
+/// `dim` is the number of components of the integrand.
+///
 /// # Notes
 ///
 /// This API is still unstable, and may changes dramatically in the future.
-pub trait Algorithm<F: Integrand + ?Sized> {
+pub trait AlgorithmVec<F: IntegrandVec + ?Sized> {
     fn integrate(
         &mut self,
         f: &mut F,
+        dim: usize,
         range: &Range,
         config: &IntegrationConfig,
-    ) -> IntegrationResult;
+    ) -> VecIntegrationResult;
+}
+
+/// 1-dimentional integration algorithm API for the complex-valued integrand
+///
+/// # Notes
+///
+/// This API is still unstable, and may changes dramatically in the future.
+pub trait AlgorithmComplex<F: IntegrandComplex + ?Sized> {
+    fn integrate_complex(
+        &mut self,
+        f: &mut F,
+        range: &Range,
+        config: &IntegrationConfig,
+    ) -> ComplexIntegrationResult;
+}
+
+/// 1-dimentional integration algorithm API for the fallible integrand
+///
+/// This trait is implemented for all the algorithms. The integration stops at
+/// the first error returned by the integrand.
+///
+/// # Notes
+///
+/// This API is still unstable, and may changes dramatically in the future.
+pub trait TryAlgorithm<F: TryIntegrand<T> + ?Sized, T: Float = f64> {
+    fn try_integrate<'a>(
+        &mut self,
+        f: &'a mut F,
+        range: &Range<T>,
+        config: &IntegrationConfig<T>,
+    ) -> TryIntegrationResult<F::Error, T>
+    where
+        Self: Algorithm<TryIntegrandWrapper<'a, F, T>, T>;
+}
+
+impl<T: Float, F: TryIntegrand<T> + ?Sized, A: ?Sized> TryAlgorithm<F, T> for A {
+    fn try_integrate<'a>(
+        &mut self,
+        f: &'a mut F,
+        range: &Range<T>,
+        config: &IntegrationConfig<T>,
+    ) -> TryIntegrationResult<F::Error, T>
+    where
+        Self: Algorithm<TryIntegrandWrapper<'a, F, T>, T>,
+    {
+        let mut wrapper = TryIntegrandWrapper::new(f);
+        let result = self.integrate(&mut wrapper, range, config);
+
+        match wrapper.failure {
+            Some((x, error)) => {
+                let mut partial = result.value;
+                partial.nevals = wrapper.nevals;
+                Err(IntegrandError { error, x, partial })
+            }
+            None => Ok(result),
+        }
+    }
 }
 
 macro_rules! extra_traits {
@@ -75,6 +159,107 @@ macro_rules! extra_traits {
     };
 }
 
+/// same as `extra_traits!`, but the traits depend on the `rule` field
+macro_rules! rule_traits {
+    ($name:ident) => {
+        rule_traits!(@INNER $name [] []);
+    };
+    ($name:ident<$lifetime:tt>) => {
+        rule_traits!(@INNER $name [$lifetime] [$lifetime]);
+    };
+    ($name:ident<$lifetime:tt, $t:ident>) => {
+        rule_traits!(@INNER $name [$lifetime, $t: crate::float::Float] [$lifetime, $t]);
+    };
+    (@INNER $name:ident [$($params:tt)*] [$($args:tt)*]) => {
+        impl<$($params)*> Default for $name<$($args)*> {
+            #[inline]
+            fn default() -> Self {
+                Self::new()
+            }
+        }
+
+        impl<$($params)*> core::fmt::Debug for $name<$($args)*> {
+            fn fmt(&self, f: &mut core::fmt::Formatter) -> core::fmt::Result {
+                f.debug_struct(stringify!($name))
+                    .field("rule", &self.rule)
+                    .finish()
+            }
+        }
+
+        impl<$($params)*> PartialEq<$name<$($args)*>> for $name<$($args)*> {
+            #[inline]
+            fn eq(&self, other: &Self) -> bool {
+                self.rule == other.rule
+            }
+        }
+
+        impl<$($params)*> Eq for $name<$($args)*> {}
+
+        impl<$($params)*> PartialOrd for $name<$($args)*> {
+            #[inline]
+            fn partial_cmp(&self, other: &Self) -> Option<core::cmp::Ordering> {
+                Some(self.cmp(other))
+            }
+        }
+
+        impl<$($params)*> Ord for $name<$($args)*> {
+            #[inline]
+            fn cmp(&self, other: &Self) -> core::cmp::Ordering {
+                self.rule.cmp(&other.rule)
+            }
+        }
+
+        impl<$($params)*> core::hash::Hash for $name<$($args)*> {
+            fn hash<H: core::hash::Hasher>(&self, state: &mut H) {
+                self.rule.hash(state)
+            }
+        }
+
+        #[cfg(feature = "std")]
+        impl<$($params)*> std::panic::UnwindSafe for $name<$($args)*> {}
+    };
+}
+
+/// same as `rule_traits!`, but the traits also depend on the `norm` field
+macro_rules! vec_traits {
+    ($name:ident<$lifetime:tt>) => {
+        impl<$lifetime> Default for $name<$lifetime> {
+            #[inline]
+            fn default() -> Self {
+                Self::new()
+            }
+        }
+
+        impl<$lifetime> core::fmt::Debug for $name<$lifetime> {
+            fn fmt(&self, f: &mut core::fmt::Formatter) -> core::fmt::Result {
+                f.debug_struct(stringify!($name))
+                    .field("rule", &self.rule)
+                    .field("norm", &self.norm)
+                    .finish()
+            }
+        }
+
+        impl<$lifetime> PartialEq<$name<$lifetime>> for $name<$lifetime> {
+            #[inline]
+            fn eq(&self, other: &Self) -> bool {
+                self.rule == other.rule && self.norm == other.norm
+            }
+        }
+
+        impl<$lifetime> Eq for $name<$lifetime> {}
+
+        impl<$lifetime> core::hash::Hash for $name<$lifetime> {
+            fn hash<H: core::hash::Hasher>(&self, state: &mut H) {
+                self.rule.hash(state);
+                self.norm.hash(state);
+            }
+        }
+
+        #[cfg(feature = "std")]
+        impl<$lifetime> std::panic::UnwindSafe for $name<$lifetime> {}
+    };
+}
+
 mod qag;
 pub use qag::*;
 
@@ -84,5 +269,71 @@ pub use qags::*;
 mod qagp;
 pub use qagp::*;
 
+mod qags_vec;
+pub use qags_vec::*;
+
+mod qagp_vec;
+pub use qagp_vec::*;
+
+#[cfg(feature = "rayon")]
+mod parallel_qag;
+#[cfg(feature = "rayon")]
+#[cfg_attr(docsrs, doc(cfg(feature = "rayon")))]
+pub use parallel_qag::*;
+
+mod qng;
+pub use qng::*;
+
+mod gauss;
+pub use gauss::*;
+
+mod gauss_lobatto;
+pub use gauss_lobatto::*;
+
+mod romberg;
+pub use romberg::*;
+
+#[cfg(feature = "std")]
+mod qawo;
+#[cfg(feature = "std")]
+#[cfg_attr(docsrs, doc(cfg(feature = "std")))]
+pub use qawo::*;
+
+#[cfg(feature = "std")]
+mod qawf;
+#[cfg(feature = "std")]
+#[cfg_attr(docsrs, doc(cfg(feature = "std")))]
+pub use qawf::*;
+
+#[cfg(feature = "std")]
+mod qaws;
+#[cfg(feature = "std")]
+#[cfg_attr(docsrs, doc(cfg(feature = "std")))]
+pub use qaws::*;
+
+#[cfg(feature = "std")]
+mod qawc;
+#[cfg(feature = "std")]
+#[cfg_attr(docsrs, doc(cfg(feature = "std")))]
+pub use qawc::*;
+
+#[cfg(feature = "std")]
+mod tanh_sinh;
+#[cfg(feature = "std")]
+#[cfg_attr(docsrs, doc(cfg(feature = "std")))]
+pub use tanh_sinh::*;
+
+#[cfg(feature = "std")]
+mod clenshaw_curtis;
+#[cfg(feature = "std")]
+#[cfg_attr(docsrs, doc(cfg(feature = "std")))]
+pub use clenshaw_curtis::*;
+
+#[cfg(feature = "std")]
+mod cquad;
+#[cfg(feature = "std")]
+#[cfg_attr(docsrs, doc(cfg(feature = "std")))]
+pub use cquad::*;
+
 mod auto;
 pub use auto::*;
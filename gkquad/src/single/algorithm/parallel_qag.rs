@@ -0,0 +1,394 @@
+use alloc::borrow::Cow;
+use alloc::vec::Vec;
+use core::cmp::Ordering;
+
+use rayon::prelude::*;
+
+use crate::common::{IntegrationResult, Solution};
+use crate::error::RuntimeError::{self, *};
+use crate::single::algorithm::qagp::make_sorted_points;
+use crate::single::algorithm::Algorithm;
+use crate::single::common::{Integrand, IntegrationConfig, Range, RangeTransform};
+use crate::single::qk::{GaussKronrod, QKResult};
+use crate::single::util::{bisect, subrange_too_small, transform_range, IntegrandWrapper};
+use crate::single::workspace::{SubRangeInfo, WorkSpace};
+use crate::utils::CowMut;
+
+/// Adaptive integration which evaluates the subranges in parallel
+///
+/// On each step, the `batch_size` subranges with the largest errors are
+/// bisected, and the Gauss-Kronrod rules over the new subranges are evaluated
+/// concurrently on the rayon thread pool. Singular points given by
+/// `IntegrationConfig::points` are taken into account in the same way as
+/// [`QAGP`](struct.QAGP.html), but the extrapolation is not performed.
+///
+/// Each worker thread evaluates its own clone of the integrand. The
+/// subranges are updated in a fixed order regardless of the scheduling, so the
+/// reported solution does not depend on the number of threads as long as the
+/// integrand is a pure function.
+///
+/// This algorithm is useful when the integrand is expensive to evaluate.
+///
+/// # Examples
+///
+/// ```
+/// use gkquad::single::algorithm::ParallelQAG;
+/// use gkquad::single::Integrator;
+///
+/// let result = Integrator::with_algorithm(|x: f64| x.sqrt(), ParallelQAG::new())
+///     .run(0.0..1.0)
+///     .unwrap();
+/// assert!((result.estimate - 2.0 / 3.0).abs() < 1e-8);
+/// ```
+#[derive(Clone)]
+pub struct ParallelQAG<'a> {
+    rule: Option<GaussKronrod>,
+    batch_size: usize,
+    workspace: CowMut<'a, WorkSpace>,
+}
+
+impl<'a> ParallelQAG<'a> {
+    #[inline]
+    pub fn new() -> Self {
+        Self {
+            rule: None,
+            batch_size: 8,
+            workspace: CowMut::Owned(WorkSpace::new()),
+        }
+    }
+
+    /// Create a new `ParallelQAG` object which uses the given Gauss-Kronrod
+    /// rule
+    ///
+    /// By default, the 25-point rule is used.
+    #[inline]
+    pub fn with_rule(rule: GaussKronrod) -> Self {
+        Self {
+            rule: Some(rule),
+            batch_size: 8,
+            workspace: CowMut::Owned(WorkSpace::new()),
+        }
+    }
+
+    #[inline]
+    #[doc(hidden)]
+    pub fn with_workspace(ws: &'a mut WorkSpace) -> Self {
+        Self {
+            rule: None,
+            batch_size: 8,
+            workspace: CowMut::Borrowed(ws),
+        }
+    }
+
+    /// Set the maximum number of subranges bisected on each step
+    ///
+    /// The default value is 8. The result depends on this value, but not on
+    /// the number of threads.
+    ///
+    /// # Panics
+    ///
+    /// Panics if `batch_size` is zero.
+    #[inline]
+    pub fn batch_size(mut self, batch_size: usize) -> Self {
+        assert!(batch_size > 0, "batch size must be positive");
+        self.batch_size = batch_size;
+        self
+    }
+
+    /// Gauss-Kronrod rule specified by the user
+    #[inline]
+    pub fn rule(&self) -> Option<GaussKronrod> {
+        self.rule
+    }
+
+    /// Maximum number of subranges bisected on each step
+    #[inline]
+    pub fn get_batch_size(&self) -> usize {
+        self.batch_size
+    }
+}
+
+impl<'a, F: Integrand + Clone + Sync> Algorithm<F> for ParallelQAG<'a> {
+    fn integrate(
+        &mut self,
+        f: &mut F,
+        range: &Range,
+        config: &IntegrationConfig,
+    ) -> IntegrationResult {
+        let transform = if range.begin.is_finite() && range.end.is_finite() {
+            None
+        } else {
+            Some(&config.range_transform)
+        };
+        let range = match transform {
+            Some(transform) => Cow::Owned(transform_range(range, transform)),
+            None => Cow::Borrowed(range),
+        };
+
+        let rule = self.rule.unwrap_or(GaussKronrod::K25);
+        let f = &*f;
+
+        // the results are collected in the same order as the ranges
+        let qk = |ranges: &[Range]| -> Vec<QKResult> {
+            ranges
+                .par_iter()
+                .map_init(
+                    || f.clone(),
+                    |g, r| {
+                        let mut wrapper = IntegrandWrapper {
+                            inner: g,
+                            transform,
+                        };
+                        rule.integrate(&mut wrapper, r)
+                    },
+                )
+                .collect()
+        };
+
+        integrate_impl(
+            &qk,
+            rule.points(),
+            self.batch_size,
+            &range,
+            config,
+            transform,
+            &mut *self.workspace,
+        )
+    }
+}
+
+impl<'a> Default for ParallelQAG<'a> {
+    #[inline]
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl<'a> core::fmt::Debug for ParallelQAG<'a> {
+    fn fmt(&self, f: &mut core::fmt::Formatter) -> core::fmt::Result {
+        f.debug_struct("ParallelQAG")
+            .field("rule", &self.rule)
+            .field("batch_size", &self.batch_size)
+            .finish()
+    }
+}
+
+impl<'a> PartialEq<ParallelQAG<'a>> for ParallelQAG<'a> {
+    #[inline]
+    fn eq(&self, other: &Self) -> bool {
+        self.rule == other.rule && self.batch_size == other.batch_size
+    }
+}
+
+impl<'a> Eq for ParallelQAG<'a> {}
+
+impl<'a> core::hash::Hash for ParallelQAG<'a> {
+    fn hash<H: core::hash::Hasher>(&self, state: &mut H) {
+        self.rule.hash(state);
+        self.batch_size.hash(state);
+    }
+}
+
+impl<'a> std::panic::UnwindSafe for ParallelQAG<'a> {}
+
+fn integrate_impl(
+    qk: &dyn Fn(&[Range]) -> Vec<QKResult>,
+    npoints: usize,
+    batch_size: usize,
+    range: &Range,
+    config: &IntegrationConfig,
+    transform: Option<&RangeTransform>,
+    ws: &mut WorkSpace,
+) -> IntegrationResult {
+    let pts = make_sorted_points(range, &config.points, transform);
+
+    // ignore small ranges
+    let ranges: Vec<Range> = pts
+        .windows(2)
+        .filter(|w| (w[1] - w[0]).abs() >= 100. * core::f64::MIN_POSITIVE)
+        .map(|w| unsafe { Range::new_unchecked(w[0], w[1]) })
+        .collect();
+
+    if config.max_evals < ranges.len() * npoints {
+        return IntegrationResult::with_error(Solution::default(), InsufficientIteration);
+    }
+
+    let results = qk(&ranges);
+    let mut nevals = ranges.len() * npoints;
+
+    ws.clear();
+    ws.reserve(ranges.len() + (config.max_evals - nevals) / (2 * npoints));
+
+    // initial integral
+    let mut result0 = QKResult {
+        estimate: 0.0,
+        delta: 0.0,
+        absvalue: 0.0,
+        asc: 0.0,
+    };
+
+    for (r, result) in ranges.into_iter().zip(results) {
+        if result.estimate.is_nan() {
+            return finish(
+                result0.estimate,
+                result0.delta,
+                nevals,
+                Some(NanValueEncountered),
+            );
+        }
+
+        result0.estimate += result.estimate;
+        result0.delta += result.delta;
+        result0.absvalue += result.absvalue;
+        result0.asc += result.asc;
+        ws.push(SubRangeInfo::new(r, result.estimate, result.delta, 0));
+    }
+
+    let tolerance = config.tolerance.to_abs(result0.estimate.abs());
+    let round_off = 100. * core::f64::EPSILON * result0.absvalue;
+
+    if result0.delta <= round_off && result0.delta > tolerance {
+        return finish(result0.estimate, result0.delta, nevals, Some(RoundoffError));
+    } else if result0.delta <= tolerance && result0.delta != result0.asc || result0.delta == 0.0 {
+        return finish(result0.estimate, result0.delta, nevals, None);
+    }
+
+    sort_by_delta(ws);
+
+    let mut area = result0.estimate;
+    let mut deltasum = result0.delta;
+    let (mut roundoff_type1, mut roundoff_type2) = (0_i32, 0_i32);
+    let mut iteration = 0usize;
+    let mut too_small = false;
+    let mut error = None;
+
+    loop {
+        // maximum number of subranges which are bisected on this step
+        let kmax = batch_size
+            .min(ws.size())
+            .min((config.max_evals - nevals) / (2 * npoints));
+        if kmax == 0 {
+            error = Some(InsufficientIteration);
+            break;
+        }
+
+        // 誤差が大きい順に部分区間を取り出して分割する
+        // 残りの部分区間の誤差の和が許容誤差以下であれば、それ以上は分割しない
+        let tolerance = config.tolerance.to_abs(area.abs());
+        let mut targets = Vec::with_capacity(kmax);
+        let mut rest = deltasum;
+        for &i in &ws.order[..kmax] {
+            if !targets.is_empty() && rest <= tolerance {
+                break;
+            }
+            targets.push(i);
+            rest -= ws.subranges[i].delta;
+        }
+        let k = targets.len();
+
+        let mut ranges = Vec::with_capacity(2 * k);
+        for &i in &targets {
+            let (r1, r2) = bisect(&ws.subranges[i].range);
+            ranges.push(r1);
+            ranges.push(r2);
+        }
+
+        let results = qk(&ranges);
+        nevals += 2 * k * npoints;
+
+        // 計算結果はスレッド数によらず同じ順番で反映する
+        let mut ranges = ranges.into_iter();
+        for (&i, results) in targets.iter().zip(results.chunks(2)) {
+            let (r1, r2) = (ranges.next().unwrap(), ranges.next().unwrap());
+            let (result1, result2) = (&results[0], &results[1]);
+
+            if result1.estimate.is_nan() || result2.estimate.is_nan() {
+                error = Some(NanValueEncountered);
+                break;
+            }
+
+            let info = &ws.subranges[i];
+            let current_level = info.level + 1;
+            let area12 = result1.estimate + result2.estimate;
+            let delta12 = result1.delta + result2.delta;
+            deltasum += delta12 - info.delta;
+            area += area12 - info.estimate;
+
+            // resascの値とerrorの値は理論上一致するはず
+            // => しかし丸め誤差により異なる値になる場合がある
+            if result1.asc != result1.delta && result2.asc != result2.delta {
+                if (info.estimate - area12).abs() <= 1e-5 * area12.abs()
+                    && delta12 >= 0.99 * info.delta
+                {
+                    roundoff_type1 += 1;
+                }
+                if iteration >= 10 && delta12 > info.delta {
+                    roundoff_type2 += 1;
+                }
+            }
+            iteration += 1;
+
+            too_small |= subrange_too_small(r1.begin, r1.end, r2.end);
+
+            ws.subranges[i] = SubRangeInfo::new(r1, result1.estimate, result1.delta, current_level);
+            ws.push(SubRangeInfo::new(
+                r2,
+                result2.estimate,
+                result2.delta,
+                current_level,
+            ));
+        }
+
+        sort_by_delta(ws);
+
+        let tolerance = config.tolerance.to_abs(area.abs());
+        if error.is_some() || deltasum <= tolerance {
+            break;
+        }
+
+        // 丸め誤差が多数発生してなおかつ収束しない場合、即座にエラー終了する
+        if roundoff_type1 >= 6 || roundoff_type2 >= 20 {
+            error = Some(RoundoffError);
+            break;
+        } else if too_small {
+            error = Some(SubrangeTooSmall);
+            break;
+        }
+    }
+
+    // 再度結果を足し合わせて正確な推定値を得る
+    finish(ws.sum_results(), deltasum, nevals, error)
+}
+
+/// sort the subranges into order of decreasing error
+///
+/// `WorkSpace::sort_results` assumes that `order` is the identity permutation,
+/// so it cannot be used after the subranges are updated.
+fn sort_by_delta(ws: &mut WorkSpace) {
+    let subranges = &ws.subranges;
+    ws.order.sort_by(|&a, &b| {
+        subranges[b]
+            .delta
+            .partial_cmp(&subranges[a].delta)
+            .unwrap_or(Ordering::Equal)
+    });
+    ws.i = ws.order[0];
+}
+
+#[inline]
+#[must_use]
+fn finish(
+    estimate: f64,
+    delta: f64,
+    nevals: usize,
+    error: Option<RuntimeError>,
+) -> IntegrationResult {
+    IntegrationResult {
+        value: Solution {
+            estimate,
+            delta,
+            nevals,
+        },
+        error,
+    }
+}
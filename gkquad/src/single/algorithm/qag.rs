@@ -7,7 +7,7 @@ use crate::single::algorithm::Algorithm;
 use crate::single::common::{Integrand, IntegrationConfig, Range};
 use crate::single::util::{bisect, subrange_too_small, transform_range, IntegrandWrapper};
 use crate::single::workspace::{SubRangeInfo, WorkSpace};
-use crate::single::{qk17, qk25, QKResult};
+use crate::single::{GaussKronrod, QKResult};
 use crate::utils::CowMut;
 
 #[cfg(not(feature = "std"))]
@@ -16,6 +16,7 @@ use crate::float::Float;
 #[derive(Clone)]
 #[deprecated(since = "0.0.3", note = "QAG algorithm is always worse than QAGS.")]
 pub struct QAG<'a> {
+    rule: Option<GaussKronrod>,
     workspace: CowMut<'a, WorkSpace>,
 }
 
@@ -23,6 +24,20 @@ impl<'a> QAG<'a> {
     #[inline]
     pub fn new() -> Self {
         Self {
+            rule: None,
+            workspace: CowMut::Owned(WorkSpace::new()),
+        }
+    }
+
+    /// Create a new `QAG` object which uses the given Gauss-Kronrod rule
+    ///
+    /// By default, the 17-point rule is used for the initial integral and the
+    /// 25-point rule for the subranges. If the rule is specified, it is used
+    /// for both of them.
+    #[inline]
+    pub fn with_rule(rule: GaussKronrod) -> Self {
+        Self {
+            rule: Some(rule),
             workspace: CowMut::Owned(WorkSpace::new()),
         }
     }
@@ -31,9 +46,25 @@ impl<'a> QAG<'a> {
     #[doc(hidden)]
     pub fn with_workspace(ws: &'a mut WorkSpace) -> Self {
         Self {
+            rule: None,
+            workspace: CowMut::Borrowed(ws),
+        }
+    }
+
+    #[inline]
+    #[doc(hidden)]
+    pub fn with_workspace_and_rule(ws: &'a mut WorkSpace, rule: Option<GaussKronrod>) -> Self {
+        Self {
+            rule,
             workspace: CowMut::Borrowed(ws),
         }
     }
+
+    /// Gauss-Kronrod rule specified by the user
+    #[inline]
+    pub fn rule(&self) -> Option<GaussKronrod> {
+        self.rule
+    }
 }
 
 impl<'a, F: Integrand + ?Sized> Algorithm<F> for QAG<'a> {
@@ -43,28 +74,36 @@ impl<'a, F: Integrand + ?Sized> Algorithm<F> for QAG<'a> {
         range: &Range,
         config: &IntegrationConfig,
     ) -> IntegrationResult {
-        let transform = !range.begin.is_finite() || !range.end.is_finite();
+        let transform = if range.begin.is_finite() && range.end.is_finite() {
+            None
+        } else {
+            Some(&config.range_transform)
+        };
         let wrapper = UnsafeCell::new(IntegrandWrapper {
             inner: f,
             transform,
         });
-        let range = if transform {
-            Cow::Owned(transform_range(range))
-        } else {
-            Cow::Borrowed(range)
+        let range = match transform {
+            Some(transform) => Cow::Owned(transform_range(range, transform)),
+            None => Cow::Borrowed(range),
+        };
+
+        let (initial, rule) = match self.rule {
+            Some(rule) => (rule, rule),
+            None => (GaussKronrod::K17, GaussKronrod::K25),
         };
 
-        let qk17 = |r: &Range| unsafe { qk17(&mut *wrapper.get(), r) };
-        let qk25 = |r: &Range| unsafe { qk25(&mut *wrapper.get(), r) };
-        integrate_impl(&qk17, &qk25, &range, config, &mut *self.workspace)
+        let qk = |rule: GaussKronrod, r: &Range| unsafe { rule.integrate(&mut *wrapper.get(), r) };
+        integrate_impl(&qk, initial, rule, &range, config, &mut *self.workspace)
     }
 }
 
-extra_traits!(QAG<'a>);
+rule_traits!(QAG<'a>);
 
 fn integrate_impl(
-    qk17: &dyn Fn(&Range) -> QKResult,
-    qk25: &dyn Fn(&Range) -> QKResult,
+    qk: &dyn Fn(GaussKronrod, &Range) -> QKResult,
+    initial: GaussKronrod,
+    rule: GaussKronrod,
     range: &Range,
     config: &IntegrationConfig,
     ws: &mut WorkSpace,
@@ -72,12 +111,12 @@ fn integrate_impl(
     let (mut roundoff_type1, mut roundoff_type2) = (0_i32, 0_i32);
     let mut error = None;
 
-    if config.max_evals < 17 {
+    if config.max_evals < initial.points() {
         return IntegrationResult::with_error(Solution::default(), InsufficientIteration);
     }
 
     // initial integral
-    let (result0, finished) = initial_integral(qk17, qk25, range, config);
+    let (result0, finished) = initial_integral(qk, initial, rule, range, config);
     if finished {
         return result0;
     }
@@ -94,7 +133,7 @@ fn integrate_impl(
     let mut nevals = result0.nevals;
 
     ws.clear();
-    ws.reserve((config.max_evals - nevals) / 50 + 1);
+    ws.reserve((config.max_evals - nevals) / (2 * rule.points()) + 1);
 
     ws.push(SubRangeInfo::new(
         range.clone(),
@@ -103,7 +142,7 @@ fn integrate_impl(
         0,
     ));
 
-    let max_iters = (config.max_evals - nevals) / 50;
+    let max_iters = (config.max_evals - nevals) / (2 * rule.points());
     for _ in 1..=max_iters {
         // 最も誤差が大きい部分区間を取り出す
         let info = ws.get();
@@ -113,9 +152,9 @@ fn integrate_impl(
         let (r1, r2) = bisect(&info.range);
 
         // 各部分区間でGauss-Kronrod積分
-        let result1 = qk25(&r1);
-        let result2 = qk25(&r2);
-        nevals += 50;
+        let result1 = qk(rule, &r1);
+        let result2 = qk(rule, &r2);
+        nevals += 2 * rule.points();
 
         if result1.estimate.is_nan() || result2.estimate.is_nan() {
             error = Some(NanValueEncountered);
@@ -176,23 +215,21 @@ fn integrate_impl(
 
 // initial integral
 fn initial_integral(
-    qk17: &dyn Fn(&Range) -> QKResult,
-    qk25: &dyn Fn(&Range) -> QKResult,
+    qk: &dyn Fn(GaussKronrod, &Range) -> QKResult,
+    initial: GaussKronrod,
+    rule: GaussKronrod,
     range: &Range,
     config: &IntegrationConfig,
 ) -> (IntegrationResult, bool) {
     let mut solution = Solution::default();
 
-    for i in 0..2 {
-        let result0 = if i == 0 {
-            solution.nevals += 17;
-            qk17(&range)
-        } else if i == 1 {
-            solution.nevals += 25;
-            qk25(&range)
-        } else {
-            unreachable!();
-        };
+    // skip the second step if the same rule is used
+    let steps = if initial == rule { 1 } else { 2 };
+
+    for i in 0..steps {
+        let rule0 = if i == 0 { initial } else { rule };
+        solution.nevals += rule0.points();
+        let result0 = qk(rule0, &range);
 
         solution.estimate = result0.estimate;
         solution.delta = result0.delta;
@@ -215,7 +252,7 @@ fn initial_integral(
             return (IntegrationResult::with_error(solution, RoundoffError), true);
         }
 
-        if config.max_evals < 42 + i * 25 {
+        if config.max_evals < solution.nevals + rule.points() {
             return (
                 IntegrationResult::with_error(solution, InsufficientIteration),
                 true,
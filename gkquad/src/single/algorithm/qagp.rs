@@ -1,12 +1,16 @@
 use alloc::borrow::Cow;
 use core::cell::UnsafeCell;
 
-use crate::common::{IntegrationResult, Solution};
+use crate::common::{ComplexIntegrationResult, IntegrationResult, Solution};
 use crate::error::RuntimeError::{self, *};
-use crate::single::algorithm::Algorithm;
-use crate::single::common::{Integrand, IntegrationConfig, Points, Range};
+use crate::float::Float;
+use crate::single::algorithm::qags_vec::integrate_complex_impl;
+use crate::single::algorithm::{Algorithm, AlgorithmComplex};
+use crate::single::common::{
+    Integrand, IntegrandComplex, IntegrationConfig, Points, Range, RangeTransform,
+};
 use crate::single::qelg::ExtrapolationTable;
-use crate::single::qk::{qk25, QKResult};
+use crate::single::qk::{GaussKronrod, QKCoefficients, QKResult};
 use crate::single::util::{
     bisect, insert_sort, subrange_too_small, test_positivity, transform_point, transform_range,
     IntegrandWrapper,
@@ -14,76 +18,133 @@ use crate::single::util::{
 use crate::single::workspace::{SubRangeInfo, WorkSpace};
 use crate::utils::CowMut;
 
-#[cfg(not(feature = "std"))]
-use crate::float::Float;
-
+/// `QAGP` algorithm
+///
+/// This algorithm is generic over the floating-point type `T` in the same way
+/// as [`QAGS`](struct.QAGS.html).
 #[derive(Clone)]
-pub struct QAGP<'a> {
-    workspace: CowMut<'a, WorkSpace>,
+pub struct QAGP<'a, T: Float = f64> {
+    rule: Option<GaussKronrod>,
+    workspace: CowMut<'a, WorkSpace<T>>,
 }
 
-impl<'a> QAGP<'a> {
+impl<'a, T: Float> QAGP<'a, T> {
     #[inline]
     pub fn new() -> Self {
         Self {
+            rule: None,
             workspace: CowMut::Owned(WorkSpace::new()),
         }
     }
 
+    /// Create a new `QAGP` object which uses the given Gauss-Kronrod rule
+    ///
+    /// By default, the 25-point rule is used.
+    #[inline]
+    pub fn with_rule(rule: GaussKronrod) -> Self {
+        Self {
+            rule: Some(rule),
+            workspace: CowMut::Owned(WorkSpace::new()),
+        }
+    }
+
+    #[inline]
+    #[doc(hidden)]
+    pub fn with_workspace(ws: &'a mut WorkSpace<T>) -> Self {
+        Self {
+            rule: None,
+            workspace: CowMut::Borrowed(ws),
+        }
+    }
+
     #[inline]
     #[doc(hidden)]
-    pub fn with_workspace(ws: &'a mut WorkSpace) -> Self {
+    pub fn with_workspace_and_rule(ws: &'a mut WorkSpace<T>, rule: Option<GaussKronrod>) -> Self {
         Self {
+            rule,
             workspace: CowMut::Borrowed(ws),
         }
     }
+
+    /// Gauss-Kronrod rule specified by the user
+    #[inline]
+    pub fn rule(&self) -> Option<GaussKronrod> {
+        self.rule
+    }
 }
 
-impl<'a, F: Integrand + ?Sized> Algorithm<F> for QAGP<'a> {
+impl<'a, T: Float, F: Integrand<T> + ?Sized> Algorithm<F, T> for QAGP<'a, T> {
     #[inline]
     fn integrate(
         &mut self,
         f: &mut F,
-        range: &Range,
-        config: &IntegrationConfig,
-    ) -> IntegrationResult {
-        let transform = !range.begin.is_finite() || !range.end.is_finite();
+        range: &Range<T>,
+        config: &IntegrationConfig<T>,
+    ) -> IntegrationResult<T> {
+        let transform = if range.begin.is_finite() && range.end.is_finite() {
+            None
+        } else {
+            Some(&config.range_transform)
+        };
         let wrapper = UnsafeCell::new(IntegrandWrapper {
             inner: f,
             transform,
         });
-        let range = if transform {
-            Cow::Owned(transform_range(range))
-        } else {
-            Cow::Borrowed(range)
+        let range = match transform {
+            Some(transform) => Cow::Owned(transform_range(range, transform)),
+            None => Cow::Borrowed(range),
         };
 
-        let qk25 = |r: &Range| unsafe { qk25(&mut *wrapper.get(), r) };
-        integrate_impl(&qk25, &range, config, transform, &mut *self.workspace)
+        let rule = self.rule.unwrap_or(GaussKronrod::K25);
+        let coefficients = QKCoefficients::new(rule);
+        let qk = |r: &Range<T>| unsafe { coefficients.integrate(&mut *wrapper.get(), r) };
+        integrate_impl(
+            &qk,
+            rule.points(),
+            &range,
+            config,
+            transform,
+            &mut *self.workspace,
+        )
     }
 }
 
-extra_traits!(QAGP<'a>);
+/// The real and imaginary parts are integrated over the same subranges, which
+/// are subdivided by the modulus of the error.
+impl<'a, F: IntegrandComplex + ?Sized> AlgorithmComplex<F> for QAGP<'a> {
+    fn integrate_complex(
+        &mut self,
+        f: &mut F,
+        range: &Range,
+        config: &IntegrationConfig,
+    ) -> ComplexIntegrationResult {
+        let rule = self.rule.unwrap_or(GaussKronrod::K25);
+        integrate_complex_impl(f, rule, range, &config.points, config, &mut *self.workspace)
+    }
+}
 
-fn integrate_impl(
-    qk25: &dyn Fn(&Range) -> QKResult,
-    range: &Range,
-    config: &IntegrationConfig,
-    transform: bool,
-    ws: &mut WorkSpace,
-) -> IntegrationResult {
+rule_traits!(QAGP<'a, T>);
+
+fn integrate_impl<T: Float>(
+    qk: &dyn Fn(&Range<T>) -> QKResult<T>,
+    npoints: usize,
+    range: &Range<T>,
+    config: &IntegrationConfig<T>,
+    transform: Option<&RangeTransform>,
+    ws: &mut WorkSpace<T>,
+) -> IntegrationResult<T> {
     let pts = make_sorted_points(range, &config.points, transform);
     let nint = pts.len() - 1; // number of ranges
     let mut nevals = 0usize;
 
-    if config.max_evals < nint * 25 {
+    if config.max_evals < nint * npoints {
         return IntegrationResult::with_error(Solution::default(), InsufficientIteration);
     }
 
     ws.clear();
-    ws.reserve(nint + (config.max_evals - nint * 25) / 50);
+    ws.reserve(nint + (config.max_evals - nint * npoints) / (2 * npoints));
 
-    let mut correc = 0.0;
+    let mut correc = T::ZERO;
     let mut ktmin = 0;
     let (mut roundoff_type1, mut roundoff_type2, mut roundoff_type3) = (0, 0, 0);
     let mut error = None;
@@ -93,21 +154,21 @@ fn integrate_impl(
     let mut disallow_extrapolation = false;
 
     let mut result0 = QKResult {
-        estimate: 0.,
-        delta: 0.,
-        absvalue: 0.,
-        asc: 0.,
+        estimate: T::ZERO,
+        delta: T::ZERO,
+        absvalue: T::ZERO,
+        asc: T::ZERO,
     };
 
     for w in pts.windows(2) {
         // ignore small range
-        if (w[1] - w[0]).abs() < 100. * core::f64::MIN_POSITIVE {
+        if (w[1] - w[0]).abs() < T::from_f64(100.) * T::MIN_POSITIVE {
             continue;
         }
 
         let range = unsafe { Range::new_unchecked(w[0], w[1]) };
-        let result1 = qk25(&range);
-        nevals += 25;
+        let result1 = qk(&range);
+        nevals += npoints;
 
         if result1.estimate.is_nan() {
             return finish(
@@ -118,7 +179,7 @@ fn integrate_impl(
             );
         }
 
-        let current_level = (result1.delta == result1.asc && result1.delta != 0.0) as usize;
+        let current_level = (result1.delta == result1.asc && result1.delta != T::ZERO) as usize;
         add_qkresult(&mut result0, &result1);
 
         ws.push(SubRangeInfo::new(
@@ -130,7 +191,7 @@ fn integrate_impl(
     }
 
     //# Compute the initial error estimate
-    let mut deltasum = 0.;
+    let mut deltasum = T::ZERO;
 
     for si in ws.subranges.iter_mut() {
         if si.level > 0 {
@@ -151,11 +212,12 @@ fn integrate_impl(
 
     let tolerance = config.tolerance.to_abs(result0.estimate.abs());
 
-    let round_off = 100. * core::f64::EPSILON * result0.absvalue;
+    let round_off = T::from_f64(100.) * T::EPSILON * result0.absvalue;
 
     if result0.delta <= round_off && result0.delta > tolerance {
         return finish(result0.estimate, result0.delta, nevals, Some(RoundoffError));
-    } else if result0.delta <= tolerance && result0.delta != result0.asc || result0.delta == 0.0 {
+    } else if result0.delta <= tolerance && result0.delta != result0.asc || result0.delta == T::ZERO
+    {
         return finish(result0.estimate, result0.delta, nevals, None);
     } else if nevals == config.max_evals {
         return finish(
@@ -173,10 +235,10 @@ fn integrate_impl(
 
     let mut area = result0.estimate;
     let mut res_ext = result0.estimate;
-    let mut err_ext = core::f64::MAX;
+    let mut err_ext = T::MAX;
     let mut error_over_large_ranges = deltasum;
     let mut ertest = tolerance;
-    let max_iters = nint + (config.max_evals - nevals) / 50;
+    let max_iters = nint + (config.max_evals - nevals) / (2 * npoints);
 
     for iteration in nint..=max_iters {
         let info = ws.get();
@@ -184,9 +246,9 @@ fn integrate_impl(
         let current_level = info.level + 1;
         let (r1, r2) = bisect(&info.range);
 
-        let result1 = qk25(&r1);
-        let result2 = qk25(&r2);
-        nevals += 50;
+        let result1 = qk(&r1);
+        let result2 = qk(&r2);
+        nevals += 2 * npoints;
 
         if result1.estimate.is_nan() || result2.estimate.is_nan() {
             error = Some(NanValueEncountered);
@@ -206,7 +268,8 @@ fn integrate_impl(
 
         // roundoff check
         if result1.asc != result1.delta && result2.asc != result2.delta {
-            if (info.estimate - area12).abs() <= 1e-5 * area12.abs() && error12 >= 0.99 * info.delta
+            if (info.estimate - area12).abs() <= T::from_f64(1e-5) * area12.abs()
+                && error12 >= T::from_f64(0.99) * info.delta
             {
                 if !extrapolate {
                     roundoff_type1 += 1;
@@ -300,11 +363,11 @@ fn integrate_impl(
         }
 
         // temporary value for rex_ext and err_ext
-        let (mut reseps, mut abseps) = (0.0, 0.0);
+        let (mut reseps, mut abseps) = (T::ZERO, T::ZERO);
         table.qelg(&mut reseps, &mut abseps);
         ktmin += 1;
 
-        if ktmin > 5 && err_ext < 0.001 * deltasum {
+        if ktmin > 5 && err_ext < T::from_f64(0.001) * deltasum {
             error = Some(RoundoffError);
         }
 
@@ -335,7 +398,7 @@ fn integrate_impl(
         error_over_large_ranges = deltasum;
     }
 
-    if err_ext == core::f64::MAX {
+    if err_ext == T::MAX {
         return finish(ws.sum_results(), deltasum, nevals, error);
     }
     if error.is_some() || error2 {
@@ -347,24 +410,26 @@ fn integrate_impl(
             error = Some(RoundoffError);
         }
 
-        if res_ext != 0. && area != 0. {
+        if res_ext != T::ZERO && area != T::ZERO {
             if err_ext / res_ext.abs() > deltasum / area.abs() {
                 return finish(ws.sum_results(), deltasum, nevals, error);
             }
         } else if err_ext > deltasum {
             return finish(ws.sum_results(), deltasum, nevals, error);
-        } else if area == 0.0 {
+        } else if area == T::ZERO {
             return finish(res_ext, err_ext, nevals, error);
         }
     }
 
     let positive_integrand = test_positivity(result0.estimate, result0.absvalue);
-    if !positive_integrand && f64::max(res_ext.abs(), area.abs()) < 0.01 * result0.absvalue {
+    if !positive_integrand && res_ext.abs().max(area.abs()) < T::from_f64(0.01) * result0.absvalue {
         return finish(res_ext, err_ext, nevals, error);
     }
 
     let ratio = res_ext / area;
-    if (ratio < 0.01 || ratio > 100. || deltasum > area.abs()) && error.is_none() {
+    if (ratio < T::from_f64(0.01) || ratio > T::from_f64(100.) || deltasum > area.abs())
+        && error.is_none()
+    {
         error = Some(Divergent);
     }
 
@@ -372,7 +437,11 @@ fn integrate_impl(
 }
 
 #[inline]
-fn make_sorted_points(range: &Range, pts: &[f64], transform: bool) -> Points {
+pub(crate) fn make_sorted_points<T: Float>(
+    range: &Range<T>,
+    pts: &[T],
+    transform: Option<&RangeTransform>,
+) -> Points<T> {
     let (min, max) = if range.begin < range.end {
         (range.begin, range.end)
     } else {
@@ -382,10 +451,9 @@ fn make_sorted_points(range: &Range, pts: &[f64], transform: bool) -> Points {
     let mut pts2 = Points::with_capacity(pts.len() + 2);
     pts2.push(range.begin);
 
-    if transform {
-        pts2.extend(pts.iter().map(|v| transform_point(*v)));
-    } else {
-        pts2.extend_from_slice(pts);
+    match transform {
+        Some(transform) => pts2.extend(pts.iter().map(|v| transform_point(*v, transform))),
+        None => pts2.extend_from_slice(pts),
     }
 
     if range.begin < range.end {
@@ -400,7 +468,7 @@ fn make_sorted_points(range: &Range, pts: &[f64], transform: bool) -> Points {
 }
 
 #[inline]
-fn add_qkresult(result1: &mut QKResult, result2: &QKResult) {
+fn add_qkresult<T: Float>(result1: &mut QKResult<T>, result2: &QKResult<T>) {
     result1.estimate += result2.estimate;
     result1.delta += result2.delta;
     result1.absvalue += result2.absvalue;
@@ -409,12 +477,12 @@ fn add_qkresult(result1: &mut QKResult, result2: &QKResult) {
 
 #[inline]
 #[must_use]
-fn finish(
-    estimate: f64,
-    delta: f64,
+fn finish<T>(
+    estimate: T,
+    delta: T,
     nevals: usize,
     error: Option<RuntimeError>,
-) -> IntegrationResult {
+) -> IntegrationResult<T> {
     IntegrationResult {
         value: Solution {
             estimate,
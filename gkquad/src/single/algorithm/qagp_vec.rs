@@ -0,0 +1,98 @@
+use crate::common::VecIntegrationResult;
+use crate::single::algorithm::qags_vec::{integrate_impl, Strategy};
+use crate::single::algorithm::{AlgorithmVec, ErrorNorm};
+use crate::single::common::{IntegrandVec, IntegrationConfig, Range};
+use crate::single::qk::GaussKronrod;
+use crate::single::workspace::WorkSpace;
+use crate::utils::CowMut;
+
+/// `QAGP` algorithm for the vector-valued integrand
+///
+/// The range is first separated by the singular points given by
+/// [`Integrator::points`](../struct.Integrator.html#method.points), and then
+/// subdivided in the same way as [`QAGSVec`](struct.QAGSVec.html).
+#[derive(Clone)]
+pub struct QAGPVec<'a> {
+    rule: Option<GaussKronrod>,
+    norm: ErrorNorm,
+    workspace: CowMut<'a, WorkSpace>,
+}
+
+impl<'a> QAGPVec<'a> {
+    #[inline]
+    pub fn new() -> Self {
+        Self {
+            rule: None,
+            norm: ErrorNorm::default(),
+            workspace: CowMut::Owned(WorkSpace::new()),
+        }
+    }
+
+    /// Create a new `QAGPVec` object which uses the given Gauss-Kronrod rule
+    ///
+    /// By default, the 25-point rule is used.
+    #[inline]
+    pub fn with_rule(rule: GaussKronrod) -> Self {
+        Self {
+            rule: Some(rule),
+            norm: ErrorNorm::default(),
+            workspace: CowMut::Owned(WorkSpace::new()),
+        }
+    }
+
+    #[inline]
+    #[doc(hidden)]
+    pub fn with_workspace(ws: &'a mut WorkSpace) -> Self {
+        Self {
+            rule: None,
+            norm: ErrorNorm::default(),
+            workspace: CowMut::Borrowed(ws),
+        }
+    }
+
+    /// Set the norm of the component errors
+    #[inline]
+    pub fn error_norm(mut self, norm: ErrorNorm) -> Self {
+        self.norm = norm;
+        self
+    }
+
+    /// Gauss-Kronrod rule specified by the user
+    #[inline]
+    pub fn rule(&self) -> Option<GaussKronrod> {
+        self.rule
+    }
+
+    /// Norm of the component errors
+    #[inline]
+    pub fn get_error_norm(&self) -> ErrorNorm {
+        self.norm
+    }
+}
+
+impl<'a, F: IntegrandVec + ?Sized> AlgorithmVec<F> for QAGPVec<'a> {
+    fn integrate(
+        &mut self,
+        f: &mut F,
+        dim: usize,
+        range: &Range,
+        config: &IntegrationConfig,
+    ) -> VecIntegrationResult {
+        let strategy = Strategy {
+            rule: self.rule.unwrap_or(GaussKronrod::K25),
+            norm: self.norm,
+            combined: false,
+        };
+        integrate_impl(
+            f,
+            dim,
+            &strategy,
+            range,
+            &config.points,
+            config,
+            &mut *self.workspace,
+        )
+    }
+}
+
+vec_traits!(QAGPVec<'a>);
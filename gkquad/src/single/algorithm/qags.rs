@@ -1,79 +1,144 @@
 use alloc::borrow::Cow;
 use core::cell::UnsafeCell;
 
-use crate::common::{IntegrationResult, Solution};
+use crate::common::{ComplexIntegrationResult, IntegrationResult, Solution};
 use crate::error::RuntimeError::{self, *};
-use crate::single::algorithm::Algorithm;
-use crate::single::common::{Integrand, IntegrationConfig, Range};
+use crate::float::Float;
+use crate::single::algorithm::qags_vec::integrate_complex_impl;
+use crate::single::algorithm::{Algorithm, AlgorithmComplex};
+use crate::single::common::{Integrand, IntegrandComplex, IntegrationConfig, Range};
 use crate::single::qelg::ExtrapolationTable;
-use crate::single::qk::{qk17, qk25, QKResult};
+use crate::single::qk::{GaussKronrod, QKCoefficients, QKResult};
 use crate::single::util::{
     bisect, subrange_too_small, test_positivity, transform_range, IntegrandWrapper,
 };
 use crate::single::workspace::{SubRangeInfo, WorkSpace};
 use crate::utils::CowMut;
 
-#[cfg(not(feature = "std"))]
-use crate::float::Float;
-
+/// `QAGS` algorithm
+///
+/// This algorithm is generic over the floating-point type `T`, so that it can
+/// be used with `f32` or the types more precise than `f64`.
 #[derive(Clone)]
-pub struct QAGS<'a> {
-    workspace: CowMut<'a, WorkSpace>,
+pub struct QAGS<'a, T: Float = f64> {
+    rule: Option<GaussKronrod>,
+    workspace: CowMut<'a, WorkSpace<T>>,
 }
 
-impl<'a> QAGS<'a> {
+impl<'a, T: Float> QAGS<'a, T> {
     #[inline]
     pub fn new() -> Self {
         Self {
+            rule: None,
+            workspace: CowMut::Owned(WorkSpace::new()),
+        }
+    }
+
+    /// Create a new `QAGS` object which uses the given Gauss-Kronrod rule
+    ///
+    /// By default, the 17-point rule is used for the initial integral and the
+    /// 25-point rule for the subranges. If the rule is specified, it is used
+    /// for both of them.
+    #[inline]
+    pub fn with_rule(rule: GaussKronrod) -> Self {
+        Self {
+            rule: Some(rule),
             workspace: CowMut::Owned(WorkSpace::new()),
         }
     }
 
     #[inline]
     #[doc(hidden)]
-    pub fn with_workspace(ws: &'a mut WorkSpace) -> Self {
+    pub fn with_workspace(ws: &'a mut WorkSpace<T>) -> Self {
         Self {
+            rule: None,
             workspace: CowMut::Borrowed(ws),
         }
     }
+
+    #[inline]
+    #[doc(hidden)]
+    pub fn with_workspace_and_rule(ws: &'a mut WorkSpace<T>, rule: Option<GaussKronrod>) -> Self {
+        Self {
+            rule,
+            workspace: CowMut::Borrowed(ws),
+        }
+    }
+
+    /// Gauss-Kronrod rule specified by the user
+    #[inline]
+    pub fn rule(&self) -> Option<GaussKronrod> {
+        self.rule
+    }
 }
 
-impl<'a, F: Integrand + ?Sized> Algorithm<F> for QAGS<'a> {
+impl<'a, T: Float, F: Integrand<T> + ?Sized> Algorithm<F, T> for QAGS<'a, T> {
     fn integrate(
         &mut self,
         f: &mut F,
-        range: &Range,
-        config: &IntegrationConfig,
-    ) -> IntegrationResult {
-        let transform = !range.begin.is_finite() || !range.end.is_finite();
+        range: &Range<T>,
+        config: &IntegrationConfig<T>,
+    ) -> IntegrationResult<T> {
+        let transform = if range.begin.is_finite() && range.end.is_finite() {
+            None
+        } else {
+            Some(&config.range_transform)
+        };
         let wrapper = UnsafeCell::new(IntegrandWrapper {
             inner: f,
             transform,
         });
-        let range = if transform {
-            Cow::Owned(transform_range(range))
-        } else {
-            Cow::Borrowed(range)
+        let range = match transform {
+            Some(transform) => Cow::Owned(transform_range(range, transform)),
+            None => Cow::Borrowed(range),
         };
 
-        let qk17 = |r: &Range| unsafe { qk17(&mut *wrapper.get(), r) };
-        let qk25 = |r: &Range| unsafe { qk25(&mut *wrapper.get(), r) };
-        integrate_impl(&qk17, &qk25, &range, config, &mut *self.workspace)
+        let (initial, rule) = match self.rule {
+            Some(rule) => (rule, rule),
+            None => (GaussKronrod::K17, GaussKronrod::K25),
+        };
+
+        let coefficients = (QKCoefficients::new(initial), QKCoefficients::new(rule));
+        let qk = |rule: GaussKronrod, r: &Range<T>| {
+            let c = if rule == initial {
+                &coefficients.0
+            } else {
+                &coefficients.1
+            };
+            unsafe { c.integrate(&mut *wrapper.get(), r) }
+        };
+        integrate_impl(&qk, initial, rule, &range, config, &mut *self.workspace)
     }
 }
 
-extra_traits!(QAGS<'a>);
+/// The real and imaginary parts are integrated over the same subranges, which
+/// are subdivided by the modulus of the error. The rule specified by the user
+/// (or the 25-point rule by default) is used for all of the subranges.
+impl<'a, F: IntegrandComplex + ?Sized> AlgorithmComplex<F> for QAGS<'a> {
+    fn integrate_complex(
+        &mut self,
+        f: &mut F,
+        range: &Range,
+        config: &IntegrationConfig,
+    ) -> ComplexIntegrationResult {
+        let rule = self.rule.unwrap_or(GaussKronrod::K25);
+        integrate_complex_impl(f, rule, range, &[], config, &mut *self.workspace)
+    }
+}
+
+rule_traits!(QAGS<'a, T>);
 
-fn integrate_impl(
-    qk17: &dyn Fn(&Range) -> QKResult,
-    qk25: &dyn Fn(&Range) -> QKResult,
-    range: &Range,
-    config: &IntegrationConfig,
-    ws: &mut WorkSpace,
-) -> IntegrationResult {
-    let mut ertest = 0f64;
-    let mut error_over_large_ranges = 0f64;
-    let mut correc = 0.;
+fn integrate_impl<T: Float>(
+    qk: &dyn Fn(GaussKronrod, &Range<T>) -> QKResult<T>,
+    initial: GaussKronrod,
+    rule: GaussKronrod,
+    range: &Range<T>,
+    config: &IntegrationConfig<T>,
+    ws: &mut WorkSpace<T>,
+) -> IntegrationResult<T> {
+    let mut ertest = T::ZERO;
+    let mut error_over_large_ranges = T::ZERO;
+    let mut correc = T::ZERO;
 
     let mut ktmin = 0usize;
     let (mut roundoff_type1, mut roundoff_type2, mut roundoff_type3) = (0i32, 0i32, 0i32);
@@ -83,11 +148,11 @@ fn integrate_impl(
     let mut extrapolate = false;
     let mut disallow_extrapolation = false;
 
-    if config.max_evals < 17 {
+    if config.max_evals < initial.points() {
         return IntegrationResult::with_error(Solution::default(), InsufficientIteration);
     }
 
-    let (result0, absvalue, finished) = initial_integral(qk17, qk25, range, config);
+    let (result0, absvalue, finished) = initial_integral(qk, initial, rule, range, config);
     if finished {
         return result0;
     }
@@ -96,7 +161,7 @@ fn integrate_impl(
     let mut nevals = result0.nevals;
 
     ws.clear();
-    ws.reserve((config.max_evals - nevals) / 50 + 1);
+    ws.reserve((config.max_evals - nevals) / (2 * rule.points()) + 1);
 
     ws.push(SubRangeInfo::new(
         range.clone(),
@@ -114,8 +179,8 @@ fn integrate_impl(
 
     // 現在の計算結果を保存
     let mut res_ext = result0.estimate;
-    let mut err_ext = core::f64::MAX;
-    let max_iters = (config.max_evals - nevals) / 50;
+    let mut err_ext = T::MAX;
+    let max_iters = (config.max_evals - nevals) / (2 * rule.points());
 
     for iteration in 1..=max_iters {
         // Bisect the subrange with the largest error estimate
@@ -125,9 +190,9 @@ fn integrate_impl(
         let (r1, r2) = bisect(&info.range);
 
         // 各部分区間でGauss-Kronrod積分
-        let result1 = qk25(&r1);
-        let result2 = qk25(&r2);
-        nevals += 50;
+        let result1 = qk(rule, &r1);
+        let result2 = qk(rule, &r2);
+        nevals += 2 * rule.points();
 
         if result1.estimate.is_nan() || result2.estimate.is_nan() {
             error = Some(NanValueEncountered);
@@ -147,7 +212,8 @@ fn integrate_impl(
         // resascの値とerrorの値は理論上一致するはず
         // => しかし丸め誤差により異なる値になる場合がある
         if result1.asc != result1.delta && result2.asc != result2.delta {
-            if (info.estimate - area12).abs() <= 1e-5 * area12.abs() && error12 >= 0.99 * info.delta
+            if (info.estimate - area12).abs() <= T::from_f64(1e-5) * area12.abs()
+                && error12 >= T::from_f64(0.99) * info.delta
             {
                 if !extrapolate {
                     roundoff_type1 += 1;
@@ -235,12 +301,12 @@ fn integrate_impl(
         }
 
         // 今までの計算結果から収束値を推定する
-        let (mut reseps, mut abseps) = (0., 0.);
+        let (mut reseps, mut abseps) = (T::ZERO, T::ZERO);
         table.append(area);
         table.qelg(&mut reseps, &mut abseps);
 
         ktmin += 1;
-        if ktmin > 5 && err_ext < 0.001 * errsum {
+        if ktmin > 5 && err_ext < T::from_f64(0.001) * errsum {
             error = Some(RoundoffError);
         }
 
@@ -271,7 +337,7 @@ fn integrate_impl(
         error_over_large_ranges = errsum;
     }
 
-    if err_ext == core::f64::MAX {
+    if err_ext == T::MAX {
         return finish(ws.sum_results(), errsum, nevals, error);
     }
 
@@ -284,13 +350,13 @@ fn integrate_impl(
             error = Some(RoundoffError);
         }
 
-        if res_ext != 0.0 && area != 0.0 {
+        if res_ext != T::ZERO && area != T::ZERO {
             if err_ext / res_ext.abs() > errsum / area.abs() {
                 return finish(ws.sum_results(), errsum, nevals, error);
             }
         } else if err_ext > errsum {
             return finish(ws.sum_results(), errsum, nevals, error);
-        } else if area == 0.0 {
+        } else if area == T::ZERO {
             return finish(res_ext, err_ext, nevals, error);
         }
     }
@@ -298,12 +364,14 @@ fn integrate_impl(
     //  Test on divergence.
     let positive_integrand = test_positivity(result0.estimate, absvalue);
 
-    if !positive_integrand && f64::max(res_ext.abs(), area.abs()) < 0.01 * absvalue {
+    if !positive_integrand && res_ext.abs().max(area.abs()) < T::from_f64(0.01) * absvalue {
         return finish(res_ext, err_ext, nevals, error);
     }
 
     let ratio = res_ext / area;
-    if (ratio < 0.01 || ratio > 100.0 || errsum > area.abs()) && error.is_none() {
+    if (ratio < T::from_f64(0.01) || ratio > T::from_f64(100.0) || errsum > area.abs())
+        && error.is_none()
+    {
         error = Some(Divergent);
     }
 
@@ -312,25 +380,23 @@ fn integrate_impl(
 
 // initial integral
 #[inline]
-fn initial_integral(
-    qk17: &dyn Fn(&Range) -> QKResult,
-    qk25: &dyn Fn(&Range) -> QKResult,
-    range: &Range,
-    config: &IntegrationConfig,
-) -> (IntegrationResult, f64, bool) {
+fn initial_integral<T: Float>(
+    qk: &dyn Fn(GaussKronrod, &Range<T>) -> QKResult<T>,
+    initial: GaussKronrod,
+    rule: GaussKronrod,
+    range: &Range<T>,
+    config: &IntegrationConfig<T>,
+) -> (IntegrationResult<T>, T, bool) {
     let mut solution = Solution::default();
-    let mut absvalue = 0.0;
-
-    for i in 0..2 {
-        let result0 = if i == 0 {
-            solution.nevals += 17;
-            qk17(&range)
-        } else if i == 1 {
-            solution.nevals += 25;
-            qk25(&range)
-        } else {
-            unreachable!();
-        };
+    let mut absvalue = T::ZERO;
+
+    // skip the second step if the same rule is used
+    let steps = if initial == rule { 1 } else { 2 };
+
+    for i in 0..steps {
+        let rule0 = if i == 0 { initial } else { rule };
+        solution.nevals += rule0.points();
+        let result0 = qk(rule0, &range);
 
         solution.estimate = result0.estimate;
         solution.delta = result0.delta;
@@ -339,35 +405,35 @@ fn initial_integral(
         if result0.estimate.is_nan() {
             return (
                 IntegrationResult::with_error(solution, NanValueEncountered),
-                0.0,
+                T::ZERO,
                 true,
             );
         }
 
         let tolerance = config.tolerance.to_abs(result0.estimate.abs());
-        if result0.delta <= tolerance && result0.delta != result0.asc || result0.delta == 0.0 {
-            return (IntegrationResult::new(solution), 0.0, true);
+        if result0.delta <= tolerance && result0.delta != result0.asc || result0.delta == T::ZERO {
+            return (IntegrationResult::new(solution), T::ZERO, true);
         }
 
-        let round_off = 100. * core::f64::EPSILON * result0.absvalue;
+        let round_off = T::from_f64(100.) * T::EPSILON * result0.absvalue;
         if result0.delta <= round_off && result0.delta > tolerance {
             // 精度の限界によりこれ以上誤差を減らすことは不可能
             return (
                 IntegrationResult::with_error(solution, RoundoffError),
-                0.0,
+                T::ZERO,
                 true,
             );
         }
 
-        if config.max_evals < 42 + i * 25 {
+        if config.max_evals < solution.nevals + rule.points() {
             return (
                 IntegrationResult::with_error(solution, InsufficientIteration),
-                0.0,
+                T::ZERO,
                 true,
             );
         }
 
-        if i == 0 && result0.delta > tolerance * 1024. {
+        if i == 0 && result0.delta > tolerance * T::from_f64(1024.) {
             break;
         }
     }
@@ -377,12 +443,12 @@ fn initial_integral(
 
 #[inline]
 #[must_use]
-fn finish(
-    estimate: f64,
-    delta: f64,
+fn finish<T>(
+    estimate: T,
+    delta: T,
     nevals: usize,
     error: Option<RuntimeError>,
-) -> IntegrationResult {
+) -> IntegrationResult<T> {
     IntegrationResult {
         value: Solution {
             estimate,
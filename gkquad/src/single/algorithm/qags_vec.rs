@@ -0,0 +1,596 @@
+use alloc::vec;
+use alloc::vec::Vec;
+
+use crate::common::{
+    ComplexIntegrationResult, ComplexSolution, Tolerance, VecIntegrationResult, VecSolution,
+};
+use crate::error::RuntimeError::{self, *};
+use crate::single::algorithm::qagp::make_sorted_points;
+use crate::single::algorithm::AlgorithmVec;
+use crate::single::common::{IntegrandComplex, IntegrandVec, IntegrationConfig, Range};
+use crate::single::qelg::ExtrapolationTable;
+use crate::single::qk::{qk_vec, GaussKronrod, QKResult};
+use crate::single::util::{
+    bisect, subrange_too_small, transform_range, ComplexWrapper, IntegrandVecWrapper,
+};
+use crate::single::workspace::{SubRangeInfo, WorkSpace};
+use crate::utils::CowMut;
+
+#[cfg(not(feature = "std"))]
+use crate::float::Float;
+
+/// Norm of the component errors, which is used to select the subrange to be
+/// bisected.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+pub enum ErrorNorm {
+    /// sum of the errors
+    L1,
+    /// square root of the sum of the squared errors
+    L2,
+    /// maximum of the errors
+    Max,
+}
+
+impl ErrorNorm {
+    #[inline]
+    fn apply(self, results: &[QKResult]) -> f64 {
+        let deltas = results.iter().map(|r| r.delta);
+        match self {
+            ErrorNorm::L1 => deltas.sum(),
+            ErrorNorm::L2 => deltas.map(|d| d * d).sum::<f64>().sqrt(),
+            ErrorNorm::Max => deltas.fold(0.0, f64::max),
+        }
+    }
+}
+
+impl Default for ErrorNorm {
+    #[inline]
+    fn default() -> Self {
+        ErrorNorm::Max
+    }
+}
+
+/// `QAGS` algorithm for the vector-valued integrand
+///
+/// All components are integrated over the same subranges, so that the
+/// integrand is evaluated only once at each point. The subrange with the
+/// largest norm of the component errors is bisected next, and the epsilon
+/// algorithm is applied to each component.
+///
+/// The calculation finishes when every component satisfies the tolerance.
+///
+/// # Examples
+///
+/// ```
+/// use gkquad::single::algorithm::{AlgorithmVec, QAGSVec};
+/// use gkquad::single::{IntegrationConfig, Range};
+///
+/// // ∫ x^k dx (k = 0, 1, 2, 3) over [0, 1]
+/// let mut f = |x: f64, out: &mut [f64]| {
+///     let mut y = 1.0;
+///     for v in out.iter_mut() {
+///         *v = y;
+///         y *= x;
+///     }
+/// };
+///
+/// let range = Range::new(0.0, 1.0).unwrap();
+/// let result = QAGSVec::new()
+///     .integrate(&mut f, 4, &range, &IntegrationConfig::default())
+///     .unwrap();
+/// assert!((result.estimate[3] - 0.25).abs() < 1e-15);
+/// ```
+#[derive(Clone)]
+pub struct QAGSVec<'a> {
+    rule: Option<GaussKronrod>,
+    norm: ErrorNorm,
+    workspace: CowMut<'a, WorkSpace>,
+}
+
+impl<'a> QAGSVec<'a> {
+    #[inline]
+    pub fn new() -> Self {
+        Self {
+            rule: None,
+            norm: ErrorNorm::default(),
+            workspace: CowMut::Owned(WorkSpace::new()),
+        }
+    }
+
+    /// Create a new `QAGSVec` object which uses the given Gauss-Kronrod rule
+    ///
+    /// By default, the 25-point rule is used.
+    #[inline]
+    pub fn with_rule(rule: GaussKronrod) -> Self {
+        Self {
+            rule: Some(rule),
+            norm: ErrorNorm::default(),
+            workspace: CowMut::Owned(WorkSpace::new()),
+        }
+    }
+
+    #[inline]
+    #[doc(hidden)]
+    pub fn with_workspace(ws: &'a mut WorkSpace) -> Self {
+        Self {
+            rule: None,
+            norm: ErrorNorm::default(),
+            workspace: CowMut::Borrowed(ws),
+        }
+    }
+
+    /// Set the norm of the component errors
+    #[inline]
+    pub fn error_norm(mut self, norm: ErrorNorm) -> Self {
+        self.norm = norm;
+        self
+    }
+
+    /// Gauss-Kronrod rule specified by the user
+    #[inline]
+    pub fn rule(&self) -> Option<GaussKronrod> {
+        self.rule
+    }
+
+    /// Norm of the component errors
+    #[inline]
+    pub fn get_error_norm(&self) -> ErrorNorm {
+        self.norm
+    }
+}
+
+impl<'a, F: IntegrandVec + ?Sized> AlgorithmVec<F> for QAGSVec<'a> {
+    fn integrate(
+        &mut self,
+        f: &mut F,
+        dim: usize,
+        range: &Range,
+        config: &IntegrationConfig,
+    ) -> VecIntegrationResult {
+        let strategy = Strategy {
+            rule: self.rule.unwrap_or(GaussKronrod::K25),
+            norm: self.norm,
+            combined: false,
+        };
+        integrate_impl(f, dim, &strategy, range, &[], config, &mut *self.workspace)
+    }
+}
+
+vec_traits!(QAGSVec<'a>);
+
+/// How the vector-valued integrand is subdivided
+pub(crate) struct Strategy {
+    /// Gauss-Kronrod rule applied to each subrange
+    pub rule: GaussKronrod,
+    /// norm of the component errors of each subrange
+    pub norm: ErrorNorm,
+    /// If `true`, the components are regarded as the parts of a single value
+    /// (e.g. the real and imaginary parts of a complex number), and the
+    /// tolerance for the Euclidean norm of the value is shared among them.
+    pub combined: bool,
+}
+
+impl Strategy {
+    /// calculate the absolute tolerance for each component
+    fn tolerance(&self, tolerance: &Tolerance, values: &[f64], out: &mut [f64]) {
+        if self.combined {
+            let norm = values.iter().map(|v| v * v).sum::<f64>().sqrt();
+            let t = tolerance.to_abs(norm) / (values.len() as f64).sqrt();
+            out.iter_mut().for_each(|x| *x = t);
+        } else {
+            out.iter_mut()
+                .zip(values)
+                .for_each(|(x, v)| *x = tolerance.to_abs(v.abs()));
+        }
+    }
+}
+
+/// Adaptive integration over the ranges separated by `points`, shared by the
+/// algorithms for the vector-valued integrand
+pub(crate) fn integrate_impl<F: IntegrandVec + ?Sized>(
+    f: &mut F,
+    dim: usize,
+    strategy: &Strategy,
+    range: &Range,
+    points: &[f64],
+    config: &IntegrationConfig,
+    ws: &mut WorkSpace,
+) -> VecIntegrationResult {
+    let transform = if range.begin.is_finite() && range.end.is_finite() {
+        None
+    } else {
+        Some(&config.range_transform)
+    };
+    let mut wrapper = IntegrandVecWrapper {
+        inner: f,
+        transform,
+    };
+    let f = &mut wrapper;
+    let pts = match transform {
+        Some(transform) => {
+            make_sorted_points(&transform_range(range, transform), points, Some(transform))
+        }
+        None => make_sorted_points(range, points, None),
+    };
+
+    let (rule, norm) = (strategy.rule, strategy.norm);
+    let npoints = rule.points();
+    let nint = pts.len() - 1; // number of ranges
+    let mut nevals = 0usize;
+
+    let mut area = vec![0.0; dim];
+    let mut errsum = vec![0.0; dim];
+    let mut absvalue = vec![0.0; dim];
+
+    if config.max_evals < nint * npoints {
+        let delta = vec![core::f64::MAX; dim];
+        return finish(area, delta, nevals, Some(InsufficientIteration));
+    }
+
+    ws.clear();
+    ws.reserve(nint + (config.max_evals - nint * npoints) / (2 * npoints));
+
+    // estimates and errors of each component, in the same order as
+    // `ws.subranges`
+    let mut estimates = Vec::with_capacity(ws.capacity() * dim);
+    let mut deltas = Vec::with_capacity(ws.capacity() * dim);
+    let mut buf = Vec::new();
+
+    for w in pts.windows(2) {
+        // ignore small range
+        if (w[1] - w[0]).abs() < 100. * core::f64::MIN_POSITIVE {
+            continue;
+        }
+
+        let range = unsafe { Range::new_unchecked(w[0], w[1]) };
+        let results = qk_vec(f, &range, rule, dim, &mut buf);
+        nevals += npoints;
+
+        if results.iter().any(|r| r.estimate.is_nan()) {
+            return finish(area, errsum, nevals, Some(NanValueEncountered));
+        }
+
+        for (k, r) in results.iter().enumerate() {
+            area[k] += r.estimate;
+            errsum[k] += r.delta;
+            absvalue[k] += r.absvalue;
+        }
+
+        ws.push(SubRangeInfo::new(range, 0.0, norm.apply(&results), 0));
+        estimates.extend(results.iter().map(|r| r.estimate));
+        deltas.extend(results.iter().map(|r| r.delta));
+    }
+
+    ws.sort_results();
+
+    // Test on accuracy
+    let mut tolerance = vec![0.0; dim];
+    strategy.tolerance(&config.tolerance, &area, &mut tolerance);
+
+    if (0..dim).all(|k| errsum[k] <= tolerance[k]) {
+        return finish(area, errsum, nevals, None);
+    }
+
+    if (0..dim)
+        .all(|k| errsum[k] <= tolerance[k] || errsum[k] <= 100. * core::f64::EPSILON * absvalue[k])
+    {
+        return finish(area, errsum, nevals, Some(RoundoffError));
+    }
+
+    // Initialization
+
+    let mut tables: Vec<ExtrapolationTable> = area
+        .iter()
+        .map(|&a| {
+            let mut table = ExtrapolationTable::default();
+            table.append(a);
+            table
+        })
+        .collect();
+
+    let mut res_ext = area.clone();
+    let mut err_ext = vec![core::f64::MAX; dim];
+    let mut correc = vec![0.0; dim];
+    let mut error_over_large_ranges = errsum.clone();
+    let mut ertest = tolerance.clone();
+
+    let mut ktmin = 0;
+    let (mut roundoff_type1, mut roundoff_type2, mut roundoff_type3) = (0, 0, 0);
+    let mut error = None;
+    let mut error2 = false;
+
+    let mut extrapolate = false;
+    let mut disallow_extrapolation = false;
+
+    let max_iters = nint + (config.max_evals - nevals) / (2 * npoints);
+
+    for iteration in nint..max_iters {
+        let i = ws.i;
+        let info = ws.get();
+        let current_level = info.level + 1;
+        let (r1, r2) = bisect(&info.range);
+
+        let results1 = qk_vec(f, &r1, rule, dim, &mut buf);
+        let results2 = qk_vec(f, &r2, rule, dim, &mut buf);
+        nevals += 2 * npoints;
+
+        if results1
+            .iter()
+            .chain(results2.iter())
+            .any(|r| r.estimate.is_nan())
+        {
+            error = Some(NanValueEncountered);
+            break;
+        }
+
+        // The roundoff error is suspected only if all of the components which
+        // have not converged yet suffer from it.
+        let (mut active, mut suspect1, mut suspect3) = (false, true, true);
+
+        for k in 0..dim {
+            let (result1, result2) = (&results1[k], &results2[k]);
+            let area12 = result1.estimate + result2.estimate;
+            let error12 = result1.delta + result2.delta;
+            let (estimate, delta) = (estimates[i * dim + k], deltas[i * dim + k]);
+
+            errsum[k] += error12 - delta;
+            area[k] += area12 - estimate;
+
+            error_over_large_ranges[k] -= delta;
+            if current_level < ws.maximum_level() {
+                error_over_large_ranges[k] += error12;
+            }
+        }
+
+        strategy.tolerance(&config.tolerance, &area, &mut tolerance);
+
+        for k in 0..dim {
+            let (result1, result2) = (&results1[k], &results2[k]);
+            let area12 = result1.estimate + result2.estimate;
+            let error12 = result1.delta + result2.delta;
+            let (estimate, delta) = (estimates[i * dim + k], deltas[i * dim + k]);
+
+            if errsum[k] <= tolerance[k] {
+                continue;
+            }
+
+            active = true;
+            if result1.asc != result1.delta && result2.asc != result2.delta {
+                suspect1 &=
+                    (estimate - area12).abs() <= 1e-5 * area12.abs() && error12 >= 0.99 * delta;
+                suspect3 &= iteration > 10 && error12 > delta;
+            } else {
+                suspect1 = false;
+                suspect3 = false;
+            }
+        }
+
+        if active && suspect1 {
+            if !extrapolate {
+                roundoff_type1 += 1;
+            } else {
+                roundoff_type2 += 1;
+            }
+        }
+
+        if active && suspect3 {
+            roundoff_type3 += 1;
+        }
+
+        // Test for roundoff and eventually set error flag
+
+        if roundoff_type1 + roundoff_type2 >= 10 || roundoff_type3 >= 20 {
+            error = Some(RoundoffError);
+        }
+
+        if roundoff_type2 >= 5 {
+            error2 = true;
+        }
+
+        // set error flag in the case of bad integrand behaviour at
+        // a point of the integration range
+
+        if subrange_too_small(r1.begin, r1.end, r2.end) {
+            error = Some(SubrangeTooSmall);
+        }
+
+        // append the newly-created ranges to the list
+        let s1 = SubRangeInfo::new(r1, 0.0, norm.apply(&results1), current_level);
+        let s2 = SubRangeInfo::new(r2, 0.0, norm.apply(&results2), current_level);
+        let (first, second) = if s2.delta > s1.delta {
+            (&results2, &results1)
+        } else {
+            (&results1, &results2)
+        };
+        ws.update(s1, s2);
+
+        for (k, r) in first.iter().enumerate() {
+            estimates[i * dim + k] = r.estimate;
+            deltas[i * dim + k] = r.delta;
+        }
+        estimates.extend(second.iter().map(|r| r.estimate));
+        deltas.extend(second.iter().map(|r| r.delta));
+
+        if (0..dim).all(|k| errsum[k] <= tolerance[k]) {
+            return finish(sum_results(&estimates, dim), errsum, nevals, error);
+        }
+
+        if error.is_some() {
+            break;
+        }
+
+        if disallow_extrapolation {
+            continue;
+        }
+
+        if !extrapolate {
+            // 次に分割する区間が最小区間である場合のみ、補外を行う
+            if ws.get().level < ws.maximum_level() {
+                continue;
+            }
+
+            extrapolate = true;
+            ws.nrmax = 1;
+        }
+
+        // The smallest range has the largest error. Before bisecting decrease
+        // the sum of the errors over the larger ranges
+        // (error_over_large_ranges) and perform extrapolation.
+        if !error2
+            && (0..dim).any(|k| error_over_large_ranges[k] > ertest[k])
+            && ws.increase_nrmax()
+        {
+            continue;
+        }
+
+        // Perform extrapolation for each component
+        let mut extrapolated = false;
+        let mut improved = false;
+
+        for k in 0..dim {
+            let table = &mut tables[k];
+            table.append(area[k]);
+            if table.n < 3 {
+                continue;
+            }
+
+            let (mut reseps, mut abseps) = (0.0, 0.0);
+            table.qelg(&mut reseps, &mut abseps);
+            extrapolated = true;
+
+            if abseps < err_ext[k] {
+                improved = true;
+                err_ext[k] = abseps;
+                res_ext[k] = reseps;
+                correc[k] = error_over_large_ranges[k];
+            }
+        }
+
+        if extrapolated {
+            if improved {
+                ktmin = 0;
+                strategy.tolerance(&config.tolerance, &res_ext, &mut ertest);
+            } else {
+                ktmin += 1;
+            }
+
+            if ktmin > 5 && (0..dim).all(|k| err_ext[k] < 0.001 * errsum[k]) {
+                error = Some(RoundoffError);
+            }
+
+            if (0..dim).all(|k| err_ext[k] <= ertest[k]) {
+                break;
+            }
+        }
+
+        // Prepare bisection of the smallest range.
+        if tables.iter().all(|t| t.n == 1) {
+            disallow_extrapolation = true;
+        }
+
+        if error.is_some() {
+            break;
+        }
+
+        ws.reset_nrmax();
+        extrapolate = false;
+        error_over_large_ranges.copy_from_slice(&errsum);
+    }
+
+    // choose the better one of the extrapolated value and the sum over the
+    // subranges for each component
+    let mut estimate = sum_results(&estimates, dim);
+    let mut delta = errsum;
+
+    for k in 0..dim {
+        if err_ext[k] == core::f64::MAX {
+            continue;
+        }
+
+        if error2 {
+            err_ext[k] += correc[k];
+        }
+
+        if err_ext[k] < delta[k] {
+            estimate[k] = res_ext[k];
+            delta[k] = err_ext[k];
+        }
+    }
+
+    // NaN error estimate is also regarded as not converged
+    strategy.tolerance(&config.tolerance, &estimate, &mut tolerance);
+    if error.is_none() && !(0..dim).all(|k| delta[k] <= tolerance[k]) {
+        error = Some(InsufficientIteration);
+    }
+
+    finish(estimate, delta, nevals, error)
+}
+
+/// Adaptive integration of the complex-valued integrand, shared by `QAGS` and
+/// `QAGP`
+///
+/// The real and imaginary parts are integrated over the same subranges, and
+/// the tolerance is applied to the modulus of the error.
+pub(crate) fn integrate_complex_impl<F: IntegrandComplex + ?Sized>(
+    f: &mut F,
+    rule: GaussKronrod,
+    range: &Range,
+    points: &[f64],
+    config: &IntegrationConfig,
+    ws: &mut WorkSpace,
+) -> ComplexIntegrationResult {
+    let strategy = Strategy {
+        rule,
+        norm: ErrorNorm::L2,
+        combined: true,
+    };
+    let mut wrapper = ComplexWrapper { inner: f };
+    let result = integrate_impl(&mut wrapper, 2, &strategy, range, points, config, ws);
+
+    let VecSolution {
+        estimate,
+        delta,
+        nevals,
+    } = result.value;
+    let delta = f64::min(
+        (delta[0] * delta[0] + delta[1] * delta[1]).sqrt(),
+        core::f64::MAX,
+    );
+
+    ComplexIntegrationResult {
+        value: ComplexSolution {
+            estimate: (estimate[0], estimate[1]),
+            delta,
+            nevals,
+        },
+        error: result.error,
+    }
+}
+
+/// calculate the sum of integral estimates over all subranges for each component
+#[inline]
+fn sum_results(estimates: &[f64], dim: usize) -> Vec<f64> {
+    let mut sum = vec![0.0; dim];
+    for chunk in estimates.chunks(dim.max(1)) {
+        sum.iter_mut().zip(chunk).for_each(|(s, e)| *s += e);
+    }
+    sum
+}
+
+#[inline]
+#[must_use]
+fn finish(
+    estimate: Vec<f64>,
+    delta: Vec<f64>,
+    nevals: usize,
+    error: Option<RuntimeError>,
+) -> VecIntegrationResult {
+    VecIntegrationResult {
+        value: VecSolution {
+            estimate,
+            delta,
+            nevals,
+        },
+        error,
+    }
+}
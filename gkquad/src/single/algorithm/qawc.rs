@@ -0,0 +1,348 @@
+use smallvec::SmallVec;
+
+use crate::common::{IntegrationResult, Solution};
+use crate::error::RuntimeError::{self, *};
+use crate::single::algorithm::Algorithm;
+use crate::single::common::{Integrand, IntegrationConfig, Range};
+use crate::single::qcheb::qcheb;
+use crate::single::qk::qk25;
+use crate::single::util::subrange_too_small;
+use crate::single::workspace::{SubRangeInfo, WorkSpace};
+use crate::utils::CowMut;
+
+/// Cauchy principal value of `f(x)/(x-c)` over the finite range.
+///
+/// The subranges which contain (or are close to) the pole `c` are integrated
+/// with the modified Clenshaw-Curtis rule, and the others with 25-point
+/// Gauss-Kronrod rule.
+///
+/// Note that the integrand `f` must not contain the weight function `1/(x-c)`.
+/// If `c` lies on the end point of the range, `PoleOnBoundary` error is
+/// returned.
+///
+/// # Examples
+///
+/// ```
+/// use gkquad::single::algorithm::QAWC;
+/// use gkquad::single::Integrator;
+///
+/// // P.V. ∫ 1 / (x (x + 2)) dx (-1 < x < 3)
+/// let result = Integrator::with_algorithm(|x: f64| 1. / (x + 2.), QAWC::new(0.))
+///     .run(-1.0..3.0)
+///     .unwrap()
+///     .estimate;
+/// ```
+#[derive(Clone)]
+pub struct QAWC<'a> {
+    pole: f64,
+    workspace: CowMut<'a, WorkSpace>,
+}
+
+impl<'a> QAWC<'a> {
+    /// Create a new `QAWC` object with the weight function `1/(x-c)`
+    ///
+    /// # Panics
+    ///
+    /// Panics if `c` is NaN.
+    #[inline]
+    pub fn new(c: f64) -> Self {
+        assert!(!c.is_nan(), "pole of the weight function must not be NaN");
+        Self {
+            pole: c,
+            workspace: CowMut::Owned(WorkSpace::new()),
+        }
+    }
+
+    #[inline]
+    #[doc(hidden)]
+    pub fn with_workspace(c: f64, ws: &'a mut WorkSpace) -> Self {
+        assert!(!c.is_nan(), "pole of the weight function must not be NaN");
+        Self {
+            pole: c,
+            workspace: CowMut::Borrowed(ws),
+        }
+    }
+
+    /// location of the pole
+    #[inline]
+    pub fn pole(&self) -> f64 {
+        self.pole
+    }
+}
+
+impl<'a, F: Integrand + ?Sized> Algorithm<F> for QAWC<'a> {
+    fn integrate(
+        &mut self,
+        f: &mut F,
+        range: &Range,
+        config: &IntegrationConfig,
+    ) -> IntegrationResult {
+        if !range.begin.is_finite() || !range.end.is_finite() {
+            return IntegrationResult::with_error(Solution::default(), InvalidRange);
+        }
+
+        if self.pole == range.begin || self.pole == range.end {
+            return IntegrationResult::with_error(Solution::default(), PoleOnBoundary);
+        }
+
+        if range.begin > range.end {
+            let range = unsafe { Range::new_unchecked(range.end, range.begin) };
+            let mut result = integrate_impl(f, &range, config, self.pole, &mut *self.workspace);
+            result.value.estimate = -result.value.estimate;
+            result
+        } else {
+            integrate_impl(f, range, config, self.pole, &mut *self.workspace)
+        }
+    }
+}
+
+impl<'a> core::fmt::Debug for QAWC<'a> {
+    fn fmt(&self, f: &mut core::fmt::Formatter) -> core::fmt::Result {
+        f.debug_struct("QAWC").field("pole", &self.pole).finish()
+    }
+}
+
+impl<'a> PartialEq for QAWC<'a> {
+    #[inline]
+    fn eq(&self, other: &Self) -> bool {
+        self.pole == other.pole
+    }
+}
+
+/// `f(x)/(x-c)`
+struct CauchyIntegrand<'a, F: Integrand + ?Sized> {
+    inner: &'a mut F,
+    c: f64,
+}
+
+impl<'a, F: Integrand + ?Sized> Integrand for CauchyIntegrand<'a, F> {
+    #[inline]
+    fn apply(&mut self, x: f64) -> f64 {
+        self.inner.apply(x) / (x - self.c)
+    }
+
+    fn apply_to_slice(&mut self, s: &mut [f64]) {
+        let denoms: SmallVec<[f64; 32]> = s.iter().map(|&x| x - self.c).collect();
+        self.inner.apply_to_slice(s);
+        s.iter_mut().zip(denoms).for_each(|(y, d)| *y /= d);
+    }
+}
+
+/// result of `qc25c`
+struct QCResult {
+    estimate: f64,
+    delta: f64,
+    /// false if the error estimate may be unreliable because of the roundoff
+    reliable: bool,
+}
+
+/// Integrate `f(x)/(x-c)` over the subrange `r`.
+fn qc25c<F: Integrand + ?Sized>(f: &mut F, r: &Range, c: f64) -> QCResult {
+    // location of the pole in [-1, 1] scale
+    let cc = (2. * c - r.end - r.begin) / (r.end - r.begin);
+
+    if cc.abs() > 1.1 {
+        let mut wrapper = CauchyIntegrand { inner: f, c };
+        let result = qk25(&mut wrapper, r);
+        QCResult {
+            estimate: result.estimate,
+            delta: result.delta,
+            reliable: result.delta != result.asc,
+        }
+    } else {
+        let series = qcheb(f, r);
+        let moments = compute_moments(cc);
+
+        let res12: f64 = series
+            .cheb12
+            .iter()
+            .zip(moments.iter())
+            .map(|(c, m)| c * m)
+            .sum();
+        let res24: f64 = series
+            .cheb24
+            .iter()
+            .zip(moments.iter())
+            .map(|(c, m)| c * m)
+            .sum();
+
+        QCResult {
+            estimate: res24,
+            delta: (res24 - res12).abs(),
+            reliable: false,
+        }
+    }
+}
+
+/// compute the modified Chebyshev moments `P.V. ∫ T_k(t)/(t-cc) dt` (-1 < t < 1)
+/// for k = 0, ..., 24
+fn compute_moments(cc: f64) -> [f64; 25] {
+    let mut moments = [0.0; 25];
+
+    let mut a0 = ((1.0 - cc) / (1.0 + cc)).abs().ln();
+    let mut a1 = 2.0 + a0 * cc;
+
+    moments[0] = a0;
+    moments[1] = a1;
+
+    for (k, m) in moments.iter_mut().enumerate().skip(2) {
+        let a2 = if k % 2 == 0 {
+            2.0 * cc * a1 - a0
+        } else {
+            let km1 = k as f64 - 1.0;
+            2.0 * cc * a1 - a0 - 4.0 / (km1 * km1 - 1.0)
+        };
+
+        *m = a2;
+        a0 = a1;
+        a1 = a2;
+    }
+
+    moments
+}
+
+fn integrate_impl<F: Integrand + ?Sized>(
+    f: &mut F,
+    range: &Range,
+    config: &IntegrationConfig,
+    c: f64,
+    ws: &mut WorkSpace,
+) -> IntegrationResult {
+    let (mut roundoff_type1, mut roundoff_type2) = (0i32, 0i32);
+    let mut error = None;
+
+    if config.max_evals < 25 {
+        return IntegrationResult::with_error(Solution::default(), InsufficientIteration);
+    }
+
+    let result0 = qc25c(f, range, c);
+    let mut nevals = 25;
+
+    if result0.estimate.is_nan() {
+        return finish(
+            result0.estimate,
+            result0.delta,
+            nevals,
+            Some(NanValueEncountered),
+        );
+    }
+
+    let max_iters = (config.max_evals - nevals) / 50;
+
+    ws.clear();
+    ws.reserve(max_iters + 1);
+    ws.push(SubRangeInfo::new(
+        range.clone(),
+        result0.estimate,
+        result0.delta,
+        0,
+    ));
+
+    // use 0.01 relative error as an extra safety margin on the first iteration
+    let mut tolerance = config.tolerance.to_abs(result0.estimate.abs());
+    if result0.delta < tolerance && result0.delta < 0.01 * result0.estimate.abs() {
+        return finish(result0.estimate, result0.delta, nevals, None);
+    } else if max_iters == 0 {
+        return finish(
+            result0.estimate,
+            result0.delta,
+            nevals,
+            Some(InsufficientIteration),
+        );
+    }
+
+    let mut area = result0.estimate;
+    let mut errsum = result0.delta;
+
+    for iteration in 1..=max_iters {
+        // Bisect the subrange with the largest error estimate
+        let info = ws.get();
+        let current_level = info.level + 1;
+
+        let (a1, b2) = (info.range.begin, info.range.end);
+        let mut mid = 0.5 * (a1 + b2);
+
+        // the pole must not lie on the boundary of subranges
+        if c > a1 && c <= mid {
+            mid = 0.5 * (c + b2);
+        } else if c > mid && c < b2 {
+            mid = 0.5 * (a1 + c);
+        }
+
+        let r1 = unsafe { Range::new_unchecked(a1, mid) };
+        let r2 = unsafe { Range::new_unchecked(mid, b2) };
+
+        let result1 = qc25c(f, &r1, c);
+        let result2 = qc25c(f, &r2, c);
+        nevals += 50;
+
+        if result1.estimate.is_nan() || result2.estimate.is_nan() {
+            error = Some(NanValueEncountered);
+            break;
+        }
+
+        let area12 = result1.estimate + result2.estimate;
+        let error12 = result1.delta + result2.delta;
+
+        errsum += error12 - info.delta;
+        area += area12 - info.estimate;
+
+        if result1.reliable && result2.reliable {
+            if (info.estimate - area12).abs() <= 1e-5 * area12.abs() && error12 >= 0.99 * info.delta
+            {
+                roundoff_type1 += 1;
+            }
+
+            if iteration >= 10 && error12 > info.delta {
+                roundoff_type2 += 1;
+            }
+        }
+
+        tolerance = config.tolerance.to_abs(area.abs());
+
+        if errsum > tolerance {
+            if roundoff_type1 >= 6 || roundoff_type2 >= 20 {
+                error = Some(RoundoffError);
+            }
+
+            // set error flag in the case of bad integrand behaviour at
+            // a point of the integration range
+            if subrange_too_small(a1, mid, b2) {
+                error = Some(SubrangeTooSmall);
+            }
+        }
+
+        ws.update(
+            SubRangeInfo::new(r1, result1.estimate, result1.delta, current_level),
+            SubRangeInfo::new(r2, result2.estimate, result2.delta, current_level),
+        );
+
+        if error.is_some() || errsum <= tolerance {
+            break;
+        }
+    }
+
+    if error.is_none() && errsum > tolerance {
+        error = Some(InsufficientIteration);
+    }
+
+    finish(ws.sum_results(), errsum, nevals, error)
+}
+
+#[inline]
+#[must_use]
+fn finish(
+    estimate: f64,
+    delta: f64,
+    nevals: usize,
+    error: Option<RuntimeError>,
+) -> IntegrationResult {
+    IntegrationResult {
+        value: Solution {
+            estimate,
+            delta,
+            nevals,
+        },
+        error,
+    }
+}
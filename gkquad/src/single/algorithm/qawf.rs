@@ -0,0 +1,347 @@
+use crate::common::{IntegrationResult, Solution, Tolerance};
+use crate::error::RuntimeError::{self, *};
+use crate::single::algorithm::{Algorithm, Oscillation, QAGS, QAWO};
+use crate::single::common::{Integrand, IntegrationConfig, Range};
+use crate::single::qelg::ExtrapolationTable;
+use crate::single::workspace::WorkSpace;
+
+/// Fourier integral of `f(x)cos(ωx)` or `f(x)sin(ωx)` over the semi-infinite
+/// range `[a, ∞)` (or `(-∞, b]`).
+///
+/// The integral is calculated cycle by cycle with `QAWO`, and the series of
+/// the partial sums is accelerated by the epsilon algorithm.
+///
+/// This algorithm only handles the absolute tolerance. If the relative
+/// tolerance is specified, it is converted into the absolute one with the
+/// integral over the first cycle.
+///
+/// # Examples
+///
+/// ```
+/// use core::f64::INFINITY;
+///
+/// use gkquad::single::algorithm::{Oscillation, QAWF};
+/// use gkquad::single::Integrator;
+/// use gkquad::Tolerance;
+///
+/// // ∫ cos(x) / (1 + x^2) dx (0 < x < ∞)
+/// let result = Integrator::with_algorithm(
+///     |x: f64| 1. / (1. + x * x),
+///     QAWF::new(1., Oscillation::Cosine),
+/// )
+/// .tolerance(Tolerance::Absolute(1e-10))
+/// .run(0.0..INFINITY)
+/// .unwrap()
+/// .estimate;
+/// ```
+#[derive(Clone)]
+pub struct QAWF<'a> {
+    qawo: QAWO<'a>,
+}
+
+impl<'a> QAWF<'a> {
+    /// Create a new `QAWF` object with the weight function `cos(ωx)` or `sin(ωx)`
+    #[inline]
+    pub fn new(omega: f64, oscillation: Oscillation) -> Self {
+        Self {
+            qawo: QAWO::new(omega, oscillation),
+        }
+    }
+
+    /// `ws` is used for the integration over each cycle.
+    #[inline]
+    #[doc(hidden)]
+    pub fn with_workspace(omega: f64, oscillation: Oscillation, ws: &'a mut WorkSpace) -> Self {
+        Self {
+            qawo: QAWO::with_workspace(omega, oscillation, ws),
+        }
+    }
+
+    /// angular frequency of the weight function
+    #[inline]
+    pub fn omega(&self) -> f64 {
+        self.qawo.omega()
+    }
+
+    /// kind of the weight function
+    #[inline]
+    pub fn oscillation(&self) -> Oscillation {
+        self.qawo.oscillation()
+    }
+}
+
+impl<'a, F: Integrand + ?Sized> Algorithm<F> for QAWF<'a> {
+    fn integrate(
+        &mut self,
+        f: &mut F,
+        range: &Range,
+        config: &IntegrationConfig,
+    ) -> IntegrationResult {
+        let (begin, end) = (range.begin, range.end);
+
+        if begin.is_finite() && end == core::f64::INFINITY {
+            integrate_impl(f, begin, config, &mut self.qawo)
+        } else if begin == core::f64::INFINITY && end.is_finite() {
+            negate(integrate_impl(f, end, config, &mut self.qawo))
+        } else if begin == core::f64::NEG_INFINITY && end.is_finite() {
+            // ∫ f(x)w(x) dx (-∞ < x < b) = ∫ f(-t)w(-t) dt (-b < t < ∞)
+            let mut g = Reflected { inner: f };
+            let result = integrate_impl(&mut g, -end, config, &mut self.qawo);
+            match self.oscillation() {
+                Oscillation::Cosine => result,
+                Oscillation::Sine => negate(result),
+            }
+        } else if begin.is_finite() && end == core::f64::NEG_INFINITY {
+            let range = unsafe { Range::new_unchecked(end, begin) };
+            negate(self.integrate(f, &range, config))
+        } else {
+            IntegrationResult::with_error(Solution::default(), InvalidRange)
+        }
+    }
+}
+
+impl<'a> core::fmt::Debug for QAWF<'a> {
+    fn fmt(&self, f: &mut core::fmt::Formatter) -> core::fmt::Result {
+        f.debug_struct("QAWF")
+            .field("omega", &self.omega())
+            .field("oscillation", &self.oscillation())
+            .finish()
+    }
+}
+
+impl<'a> PartialEq for QAWF<'a> {
+    #[inline]
+    fn eq(&self, other: &Self) -> bool {
+        self.qawo == other.qawo
+    }
+}
+
+/// `f(-x)`
+struct Reflected<'a, F: Integrand + ?Sized> {
+    inner: &'a mut F,
+}
+
+impl<'a, F: Integrand + ?Sized> Integrand for Reflected<'a, F> {
+    #[inline]
+    fn apply(&mut self, x: f64) -> f64 {
+        self.inner.apply(-x)
+    }
+
+    #[inline]
+    fn apply_to_slice(&mut self, s: &mut [f64]) {
+        s.iter_mut().for_each(|x| *x = -*x);
+        self.inner.apply_to_slice(s);
+    }
+}
+
+fn integrate_impl<F: Integrand + ?Sized>(
+    f: &mut F,
+    a: f64,
+    config: &IntegrationConfig,
+    qawo: &mut QAWO,
+) -> IntegrationResult {
+    // maximum number of cycles
+    const MAX_CYCLES: usize = 50;
+    // decreasing factor of the tolerance for each cycle
+    const P: f64 = 0.9;
+
+    let omega = qawo.omega();
+
+    if omega == 0.0 {
+        return match qawo.oscillation() {
+            // sin(ωx)f(x) is always zero
+            Oscillation::Sine => IntegrationResult::new(Solution {
+                estimate: 0.0,
+                delta: 0.0,
+                nevals: 0,
+            }),
+            // cos(ωx)f(x) is always f(x)
+            Oscillation::Cosine => {
+                let range = unsafe { Range::new_unchecked(a, core::f64::INFINITY) };
+                QAGS::new().integrate(f, &range, config)
+            }
+        };
+    }
+
+    let mut area = 0.;
+    let mut errsum = 0.;
+    let mut res_ext = 0.;
+    let mut err_ext = core::f64::MAX;
+    let mut correc = 0.;
+    let mut total_error = 0.;
+    let mut truncation_error = 0.;
+
+    let mut ktmin = 0;
+    let mut nevals = 0;
+    let mut error = None;
+    let mut cycle_error = false;
+
+    let mut epsabs = 0.;
+    let mut initial_eps = 0.;
+    let mut eps = 0.;
+    let mut factor = 1.;
+
+    let cycle = (2. * omega.abs().floor() + 1.) * core::f64::consts::PI / omega.abs();
+
+    let mut table = ExtrapolationTable::default();
+    let mut cycle_config = IntegrationConfig::default();
+    let mut converged = false;
+
+    for iteration in 0..MAX_CYCLES {
+        if config.max_evals - nevals < 25 {
+            error = Some(InsufficientIteration);
+            break;
+        }
+
+        let a1 = a + iteration as f64 * cycle;
+        let b1 = a1 + cycle;
+        let range = unsafe { Range::new_unchecked(a1, b1) };
+
+        cycle_config.tolerance = if iteration == 0 {
+            scale_tolerance(&config.tolerance, 1. - P)
+        } else {
+            Tolerance::Absolute(eps * factor)
+        };
+        cycle_config.max_evals = config.max_evals - nevals;
+
+        let result = qawo.integrate(f, &range, &cycle_config);
+        let status = result.error;
+        let result = unsafe { result.unwrap_unchecked() };
+        nevals += result.nevals;
+
+        if status == Some(NanValueEncountered) {
+            return finish(area + result.estimate, core::f64::MAX, nevals, status);
+        }
+
+        let (area1, error1) = (result.estimate, result.delta);
+
+        if iteration == 0 {
+            epsabs = config.tolerance.to_abs(area1.abs());
+            eps = if epsabs > core::f64::MIN_POSITIVE / (1. - P) {
+                epsabs * (1. - P)
+            } else {
+                epsabs
+            };
+            initial_eps = eps;
+        }
+
+        factor *= P;
+        area += area1;
+        errsum += error1;
+
+        // estimate the truncation error as 50 times the final term
+        truncation_error = 50. * area1.abs();
+        total_error = errsum + truncation_error;
+
+        if total_error < epsabs && iteration > 4 {
+            converged = true;
+            break;
+        }
+
+        if error1 > correc {
+            correc = error1;
+        }
+
+        if status.is_some() {
+            cycle_error = true;
+            eps = f64::max(initial_eps, correc * (1. - P));
+
+            if total_error < 10. * correc && iteration > 3 {
+                converged = true;
+                break;
+            }
+        }
+
+        table.append(area);
+        if table.n < 2 {
+            continue;
+        }
+
+        let (mut reseps, mut erreps) = (0., 0.);
+        table.qelg(&mut reseps, &mut erreps);
+
+        ktmin += 1;
+        if ktmin >= 15 && err_ext < 0.001 * total_error {
+            error = Some(RoundoffError);
+        }
+
+        if erreps < err_ext {
+            ktmin = 0;
+            err_ext = erreps;
+            res_ext = reseps;
+
+            if err_ext + 10. * correc <= epsabs || err_ext <= epsabs && 10. * correc >= epsabs {
+                break;
+            }
+        }
+
+        if iteration == MAX_CYCLES - 1 {
+            error = Some(InsufficientIteration);
+        }
+    }
+
+    if cycle_error && error.is_none() {
+        error = Some(CycleFailure);
+    }
+
+    if converged || err_ext == core::f64::MAX {
+        return finish(area, total_error, nevals, error);
+    }
+
+    err_ext += 10. * correc;
+
+    if error.is_none() {
+        return finish(res_ext, err_ext, nevals, None);
+    }
+
+    if res_ext != 0.0 && area != 0.0 {
+        if err_ext / res_ext.abs() > errsum / area.abs() {
+            return finish(area, total_error, nevals, error);
+        }
+    } else if err_ext > errsum {
+        return finish(area, total_error, nevals, error);
+    } else if area == 0.0 {
+        return finish(res_ext, err_ext, nevals, error);
+    }
+
+    if error == Some(RoundoffError) {
+        err_ext += truncation_error;
+    }
+
+    finish(res_ext, err_ext, nevals, error)
+}
+
+/// multiply the tolerance by `s`
+#[inline]
+fn scale_tolerance(t: &Tolerance, s: f64) -> Tolerance {
+    match *t {
+        Tolerance::Absolute(x) => Tolerance::Absolute(x * s),
+        Tolerance::Relative(y) => Tolerance::Relative(y * s),
+        Tolerance::AbsOrRel(x, y) => Tolerance::AbsOrRel(x * s, y * s),
+        Tolerance::AbsAndRel(x, y) => Tolerance::AbsAndRel(x * s, y * s),
+    }
+}
+
+#[inline]
+fn negate(mut result: IntegrationResult) -> IntegrationResult {
+    result.value.estimate = -result.value.estimate;
+    result
+}
+
+#[inline]
+#[must_use]
+fn finish(
+    estimate: f64,
+    delta: f64,
+    nevals: usize,
+    error: Option<RuntimeError>,
+) -> IntegrationResult {
+    IntegrationResult {
+        value: Solution {
+            estimate,
+            delta,
+            nevals,
+        },
+        error,
+    }
+}
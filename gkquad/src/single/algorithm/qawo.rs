@@ -0,0 +1,743 @@
+use alloc::vec::Vec;
+use smallvec::SmallVec;
+
+use crate::common::{IntegrationResult, Solution};
+use crate::error::RuntimeError::{self, *};
+use crate::single::algorithm::Algorithm;
+use crate::single::common::{Integrand, IntegrationConfig, Range};
+use crate::single::qcheb::qcheb;
+use crate::single::qelg::ExtrapolationTable;
+use crate::single::qk::{qk25, QKResult};
+use crate::single::util::{bisect, subrange_too_small, test_positivity};
+use crate::single::workspace::{SubRangeInfo, WorkSpace};
+use crate::utils::CowMut;
+
+/// Oscillatory weight function
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+pub enum Oscillation {
+    /// `cos(ωx)`
+    Cosine,
+    /// `sin(ωx)`
+    Sine,
+}
+
+/// Integrate `f(x)cos(ωx)` or `f(x)sin(ωx)` over the finite range.
+///
+/// The subranges which are long compared to the period are integrated with
+/// modified Clenshaw-Curtis rule, and the others with 25-point Gauss-Kronrod
+/// rule. The convergence is accelerated by the epsilon algorithm as `QAGS`.
+///
+/// Note that the integrand `f` must not contain the weight function.
+///
+/// # Examples
+///
+/// ```
+/// use gkquad::single::algorithm::{Oscillation, QAWO};
+/// use gkquad::single::Integrator;
+///
+/// // ∫ x cos(100x) dx (0 < x < 1)
+/// let result = Integrator::with_algorithm(|x: f64| x, QAWO::new(100., Oscillation::Cosine))
+///     .run(0.0..1.0)
+///     .unwrap()
+///     .estimate;
+/// ```
+#[derive(Clone)]
+pub struct QAWO<'a> {
+    weight: OscillatoryWeight,
+    moments: MomentTable,
+    workspace: CowMut<'a, WorkSpace>,
+}
+
+impl<'a> QAWO<'a> {
+    /// Create a new `QAWO` object with the weight function `cos(ωx)` or `sin(ωx)`
+    #[inline]
+    pub fn new(omega: f64, oscillation: Oscillation) -> Self {
+        assert!(!omega.is_nan(), "omega must not be a NAN value.");
+
+        Self {
+            weight: OscillatoryWeight { omega, oscillation },
+            moments: MomentTable::new(),
+            workspace: CowMut::Owned(WorkSpace::new()),
+        }
+    }
+
+    #[inline]
+    #[doc(hidden)]
+    pub fn with_workspace(omega: f64, oscillation: Oscillation, ws: &'a mut WorkSpace) -> Self {
+        assert!(!omega.is_nan(), "omega must not be a NAN value.");
+
+        Self {
+            weight: OscillatoryWeight { omega, oscillation },
+            moments: MomentTable::new(),
+            workspace: CowMut::Borrowed(ws),
+        }
+    }
+
+    /// angular frequency of the weight function
+    #[inline]
+    pub fn omega(&self) -> f64 {
+        self.weight.omega
+    }
+
+    /// kind of the weight function
+    #[inline]
+    pub fn oscillation(&self) -> Oscillation {
+        self.weight.oscillation
+    }
+}
+
+impl<'a, F: Integrand + ?Sized> Algorithm<F> for QAWO<'a> {
+    fn integrate(
+        &mut self,
+        f: &mut F,
+        range: &Range,
+        config: &IntegrationConfig,
+    ) -> IntegrationResult {
+        if !range.begin.is_finite() || !range.end.is_finite() {
+            return IntegrationResult::with_error(Solution::default(), InvalidRange);
+        }
+
+        self.moments.reset(range.end - range.begin);
+        integrate_impl(
+            f,
+            range,
+            config,
+            &self.weight,
+            &mut self.moments,
+            &mut *self.workspace,
+        )
+    }
+}
+
+impl<'a> core::fmt::Debug for QAWO<'a> {
+    fn fmt(&self, f: &mut core::fmt::Formatter) -> core::fmt::Result {
+        f.debug_struct("QAWO")
+            .field("omega", &self.weight.omega)
+            .field("oscillation", &self.weight.oscillation)
+            .finish()
+    }
+}
+
+impl<'a> PartialEq for QAWO<'a> {
+    #[inline]
+    fn eq(&self, other: &Self) -> bool {
+        self.weight == other.weight
+    }
+}
+
+#[derive(Clone, Debug, PartialEq)]
+pub(crate) struct OscillatoryWeight {
+    pub omega: f64,
+    pub oscillation: Oscillation,
+}
+
+/// integrand multiplied by the oscillatory weight function
+struct WeightedIntegrand<'a, F: Integrand + ?Sized> {
+    inner: &'a mut F,
+    weight: &'a OscillatoryWeight,
+}
+
+impl<'a, F: Integrand + ?Sized> WeightedIntegrand<'a, F> {
+    #[inline]
+    fn weight(&self, x: f64) -> f64 {
+        match self.weight.oscillation {
+            Oscillation::Cosine => (self.weight.omega * x).cos(),
+            Oscillation::Sine => (self.weight.omega * x).sin(),
+        }
+    }
+}
+
+impl<'a, F: Integrand + ?Sized> Integrand for WeightedIntegrand<'a, F> {
+    #[inline]
+    fn apply(&mut self, x: f64) -> f64 {
+        self.inner.apply(x) * self.weight(x)
+    }
+
+    fn apply_to_slice(&mut self, s: &mut [f64]) {
+        let weights: SmallVec<[f64; 32]> = s.iter().map(|&x| self.weight(x)).collect();
+        self.inner.apply_to_slice(s);
+        s.iter_mut().zip(weights).for_each(|(y, w)| *y *= w);
+    }
+}
+
+/// Chebyshev moments of the weight function for each subdivision level.
+///
+/// `chebmo[level][k]` holds `∫ T_k(t) cos(par t) dt` for even `k` and
+/// `∫ T_k(t) sin(par t) dt` for odd `k` (-1 < t < 1), where `par` is
+/// `ω * length / 2^(level + 1)`.
+#[derive(Clone)]
+pub(crate) struct MomentTable {
+    length: f64,
+    chebmo: Vec<[f64; 25]>,
+}
+
+impl MomentTable {
+    #[inline]
+    pub fn new() -> Self {
+        Self {
+            length: 0.0,
+            chebmo: Vec::new(),
+        }
+    }
+
+    /// discard the moments if the length of the range has been changed
+    #[inline]
+    pub fn reset(&mut self, length: f64) {
+        if self.length != length {
+            self.length = length;
+            self.chebmo.clear();
+        }
+    }
+
+    fn get(&mut self, omega: f64, level: usize) -> &[f64; 25] {
+        while self.chebmo.len() <= level {
+            let par = 0.5 * omega * self.length * 0.5f64.powi(self.chebmo.len() as i32);
+            self.chebmo.push(compute_moments(par));
+        }
+
+        &self.chebmo[level]
+    }
+}
+
+/// Integrate `f(x)w(x)` over the subrange with the modified Clenshaw-Curtis rule
+/// (or 25-point Gauss-Kronrod rule if the subrange is short).
+pub(crate) fn qc25f<F: Integrand + ?Sized>(
+    f: &mut F,
+    range: &Range,
+    weight: &OscillatoryWeight,
+    level: usize,
+    moments: &mut MomentTable,
+) -> QKResult {
+    let center = 0.5 * (range.begin + range.end);
+    let half_length = 0.5 * (range.end - range.begin);
+    let omega = weight.omega;
+    let par = omega * half_length;
+
+    if par.abs() < 2. {
+        let mut wrapper = WeightedIntegrand { inner: f, weight };
+        return qk25(&mut wrapper, range);
+    }
+
+    let series = qcheb(f, range);
+    let (cheb12, cheb24) = (&series.cheb12, &series.cheb24);
+    let moment = moments.get(omega, level);
+
+    let mut res12_cos = cheb12[12] * moment[12];
+    let mut res12_sin = 0.;
+    for i in 0..6 {
+        let k = 10 - 2 * i;
+        res12_cos += cheb12[k] * moment[k];
+        res12_sin += cheb12[k + 1] * moment[k + 1];
+    }
+
+    let mut res24_cos = cheb24[24] * moment[24];
+    let mut res24_sin = 0.;
+    let mut result_abs = cheb24[24].abs();
+    for i in 0..12 {
+        let k = 22 - 2 * i;
+        res24_cos += cheb24[k] * moment[k];
+        res24_sin += cheb24[k + 1] * moment[k + 1];
+        result_abs += cheb24[k].abs() + cheb24[k + 1].abs();
+    }
+
+    let est_cos = (res24_cos - res12_cos).abs();
+    let est_sin = (res24_sin - res12_sin).abs();
+
+    let c = half_length * (center * omega).cos();
+    let s = half_length * (center * omega).sin();
+
+    let (estimate, delta) = match weight.oscillation {
+        Oscillation::Cosine => (
+            c * res24_cos - s * res24_sin,
+            (c * est_cos).abs() + (s * est_sin).abs(),
+        ),
+        Oscillation::Sine => (
+            c * res24_sin + s * res24_cos,
+            (c * est_sin).abs() + (s * est_cos).abs(),
+        ),
+    };
+
+    QKResult {
+        estimate,
+        delta,
+        absvalue: result_abs * half_length.abs(),
+        asc: core::f64::MAX,
+    }
+}
+
+/// compute the Chebyshev moments of cos(par t) and sin(par t) over (-1, 1)
+fn compute_moments(par: f64) -> [f64; 25] {
+    const NOEQ: usize = 25;
+
+    let mut chebmo = [0.0; 25];
+    let mut v = [0.0; 28];
+    let mut d = [0.0; 25];
+    let mut d1 = [0.0; 25];
+    let mut d2 = [0.0; 25];
+
+    let par2 = par * par;
+    let par4 = par2 * par2;
+    let par22 = par2 + 2.0;
+
+    let sinpar = par.sin();
+    let cospar = par.cos();
+
+    // moments with respect to cosine
+    let mut ac = 8. * cospar;
+    let mut as_ = 24. * par * sinpar;
+
+    v[0] = 2. * sinpar / par;
+    v[1] = (8. * cospar + (2. * par2 - 8.) * sinpar / par) / par2;
+    v[2] =
+        (32. * (par2 - 12.) * cospar + (2. * ((par2 - 80.) * par2 + 192.) * sinpar) / par) / par4;
+
+    if par.abs() <= 24. {
+        // solve the boundary value problem using the asymptotic expansion
+        // as an end point
+        let mut an = 6.;
+        for k in 0..NOEQ - 1 {
+            let an2 = an * an;
+            d[k] = -2. * (an2 - 4.) * (par22 - 2. * an2);
+            d2[k] = (an - 1.) * (an - 2.) * par2;
+            d1[k + 1] = (an + 3.) * (an + 4.) * par2;
+            v[k + 3] = as_ - (an2 - 4.) * ac;
+            an += 2.;
+        }
+
+        let an2 = an * an;
+        d[NOEQ - 1] = -2. * (an2 - 4.) * (par22 - 2. * an2);
+        v[NOEQ + 2] = as_ - (an2 - 4.) * ac;
+        v[3] -= 56. * par2 * v[2];
+
+        let ass = par * sinpar;
+        let asap = (((((210. * par2 - 1.) * cospar - (105. * par2 - 63.) * ass) / an2
+            - (1. - 15. * par2) * cospar
+            + 15. * ass)
+            / an2
+            - cospar
+            + 3. * ass)
+            / an2
+            - cospar)
+            / an2;
+        v[NOEQ + 2] -= 2. * asap * par2 * (an - 1.) * (an - 2.);
+
+        dgtsl(&mut d1, &mut d, &mut d2, &mut v[3..]);
+    } else {
+        // forward recursion
+        let mut an = 4.;
+        for k in 3..13 {
+            let an2 = an * an;
+            v[k] = ((an2 - 4.) * (2. * (par22 - 2. * an2) * v[k - 1] - ac) + as_
+                - par2 * (an + 1.) * (an + 2.) * v[k - 2])
+                / (par2 * (an - 1.) * (an - 2.));
+            an += 2.;
+        }
+    }
+
+    for i in 0..13 {
+        chebmo[2 * i] = v[i];
+    }
+
+    // moments with respect to sine
+    v[0] = 2. * (sinpar - par * cospar) / par2;
+    v[1] = (18. - 48. / par2) * sinpar / par2 + (-2. + 48. / par2) * cospar / par;
+
+    ac = -24. * par * cospar;
+    as_ = -8. * sinpar;
+
+    if par.abs() <= 24. {
+        let mut an = 5.;
+        for k in 0..NOEQ - 1 {
+            let an2 = an * an;
+            d[k] = -2. * (an2 - 4.) * (par22 - 2. * an2);
+            d2[k] = (an - 1.) * (an - 2.) * par2;
+            d1[k + 1] = (an + 3.) * (an + 4.) * par2;
+            v[k + 2] = ac + (an2 - 4.) * as_;
+            an += 2.;
+        }
+
+        let an2 = an * an;
+        d[NOEQ - 1] = -2. * (an2 - 4.) * (par22 - 2. * an2);
+        v[NOEQ + 1] = ac + (an2 - 4.) * as_;
+        v[2] -= 42. * par2 * v[1];
+
+        let ass = par * cospar;
+        let asap = (((((105. * par2 - 63.) * ass - (210. * par2 - 1.) * sinpar) / an2
+            + (15. * par2 - 1.) * sinpar
+            - 15. * ass)
+            / an2
+            - sinpar
+            - 3. * ass)
+            / an2
+            - sinpar)
+            / an2;
+        v[NOEQ + 1] -= 2. * asap * par2 * (an - 1.) * (an - 2.);
+
+        dgtsl(&mut d1, &mut d, &mut d2, &mut v[2..]);
+    } else {
+        let mut an = 3.;
+        for k in 2..12 {
+            let an2 = an * an;
+            v[k] = ((an2 - 4.) * (2. * (par22 - 2. * an2) * v[k - 1] + as_) + ac
+                - par2 * (an + 1.) * (an + 2.) * v[k - 2])
+                / (par2 * (an - 1.) * (an - 2.));
+            an += 2.;
+        }
+    }
+
+    for i in 0..12 {
+        chebmo[2 * i + 1] = v[i];
+    }
+
+    chebmo
+}
+
+/// Solve the tridiagonal linear system with Gaussian elimination with partial
+/// pivoting (LINPACK dgtsl).
+///
+/// * `c` - subdiagonal (`c[1..n]`)
+/// * `d` - diagonal
+/// * `e` - superdiagonal (`e[0..n - 1]`)
+/// * `b` - right hand side, which is overwritten by the solution
+fn dgtsl(c: &mut [f64], d: &mut [f64], e: &mut [f64], b: &mut [f64]) {
+    let n = d.len();
+
+    c[0] = d[0];
+    if n == 1 {
+        b[0] /= d[0];
+        return;
+    }
+
+    d[0] = e[0];
+    e[0] = 0.;
+    e[n - 1] = 0.;
+
+    for k in 0..n - 1 {
+        let k1 = k + 1;
+
+        if c[k1].abs() >= c[k].abs() {
+            c.swap(k, k1);
+            d.swap(k, k1);
+            e.swap(k, k1);
+            b.swap(k, k1);
+        }
+
+        if c[k] == 0. {
+            // the matrix is singular (never occurs for the moment equations)
+            return;
+        }
+
+        let t = -c[k1] / c[k];
+        c[k1] = d[k1] + t * d[k];
+        d[k1] = e[k1] + t * e[k];
+        e[k1] = 0.;
+        b[k1] += t * b[k];
+    }
+
+    b[n - 1] /= c[n - 1];
+    b[n - 2] = (b[n - 2] - d[n - 2] * b[n - 1]) / c[n - 2];
+
+    for k in (0..n - 2).rev() {
+        b[k] = (b[k] - d[k] * b[k + 1] - e[k] * b[k + 2]) / c[k];
+    }
+}
+
+fn integrate_impl<F: Integrand + ?Sized>(
+    f: &mut F,
+    range: &Range,
+    config: &IntegrationConfig,
+    weight: &OscillatoryWeight,
+    moments: &mut MomentTable,
+    ws: &mut WorkSpace,
+) -> IntegrationResult {
+    let abs_omega = weight.omega.abs();
+
+    let mut ertest = 0f64;
+    let mut error_over_large_ranges = 0f64;
+    let mut correc = 0.;
+
+    let mut ktmin = 0usize;
+    let (mut roundoff_type1, mut roundoff_type2, mut roundoff_type3) = (0i32, 0i32, 0i32);
+    let mut error = None;
+    let mut error2 = false;
+
+    let mut extrapolate = false;
+    let mut extrapolate_all = false;
+    let mut disallow_extrapolation = false;
+
+    if config.max_evals < 25 {
+        return IntegrationResult::with_error(Solution::default(), InsufficientIteration);
+    }
+
+    // initial integral
+    let result0 = qc25f(f, range, weight, 0, moments);
+    let mut nevals = 25;
+
+    if result0.estimate.is_nan() {
+        return finish(
+            result0.estimate,
+            result0.delta,
+            nevals,
+            Some(NanValueEncountered),
+        );
+    }
+
+    let tolerance = config.tolerance.to_abs(result0.estimate.abs());
+    let round_off = 100. * core::f64::EPSILON * result0.absvalue;
+    if result0.delta <= round_off && result0.delta > tolerance {
+        return finish(result0.estimate, result0.delta, nevals, Some(RoundoffError));
+    } else if result0.delta <= tolerance && result0.delta != result0.asc || result0.delta == 0.0 {
+        return finish(result0.estimate, result0.delta, nevals, None);
+    } else if config.max_evals < 75 {
+        return finish(
+            result0.estimate,
+            result0.delta,
+            nevals,
+            Some(InsufficientIteration),
+        );
+    }
+
+    let max_iters = (config.max_evals - nevals) / 50;
+
+    ws.clear();
+    ws.reserve(max_iters + 1);
+
+    ws.push(SubRangeInfo::new(
+        range.clone(),
+        result0.estimate,
+        result0.delta,
+        0,
+    ));
+
+    // Extrapolation is started after all subranges become shorter than the
+    // period of the weight function.
+    let mut table = ExtrapolationTable::default();
+    if 0.5 * abs_omega * (range.end - range.begin).abs() <= 2. {
+        table.append(result0.estimate);
+        extrapolate_all = true;
+    }
+
+    let mut area = result0.estimate;
+    let mut errsum = result0.delta;
+
+    let mut res_ext = result0.estimate;
+    let mut err_ext = core::f64::MAX;
+
+    for iteration in 2..=max_iters + 1 {
+        // Bisect the subrange with the largest error estimate
+        let info = ws.get();
+        let current_level = info.level + 1;
+
+        let (r1, r2) = bisect(&info.range);
+
+        let result1 = qc25f(f, &r1, weight, current_level, moments);
+        let result2 = qc25f(f, &r2, weight, current_level, moments);
+        nevals += 50;
+
+        if result1.estimate.is_nan() || result2.estimate.is_nan() {
+            error = Some(NanValueEncountered);
+            break;
+        }
+
+        let area12 = result1.estimate + result2.estimate;
+        let error12 = result1.delta + result2.delta;
+        let last_e_i = info.delta;
+
+        errsum += error12 - info.delta;
+        area += area12 - info.estimate;
+
+        let tolerance = config.tolerance.to_abs(area.abs());
+
+        if result1.asc != result1.delta && result2.asc != result2.delta {
+            if (info.estimate - area12).abs() <= 1e-5 * area12.abs() && error12 >= 0.99 * info.delta
+            {
+                if !extrapolate {
+                    roundoff_type1 += 1;
+                } else {
+                    roundoff_type2 += 1;
+                }
+            }
+
+            if iteration > 10 && error12 > info.delta {
+                roundoff_type3 += 1;
+            }
+        }
+
+        // Test for roundoff and eventually set error flag
+        if roundoff_type1 + roundoff_type2 >= 10 || roundoff_type3 >= 20 {
+            error = Some(RoundoffError);
+        }
+
+        if roundoff_type2 >= 5 {
+            error2 = true;
+        }
+
+        // set error flag in the case of bad integrand behaviour at a point of
+        // the integration range
+        if subrange_too_small(r1.begin, r1.end, r2.end) {
+            error = Some(SubrangeTooSmall);
+        }
+
+        // append the newly-created ranges to the list
+        ws.update(
+            SubRangeInfo::new(r1, result1.estimate, result1.delta, current_level),
+            SubRangeInfo::new(r2, result2.estimate, result2.delta, current_level),
+        );
+
+        if errsum <= tolerance {
+            return finish(ws.sum_results(), errsum, nevals, error);
+        }
+
+        if error.is_some() {
+            break;
+        }
+
+        if iteration > max_iters {
+            error = Some(InsufficientIteration);
+            break;
+        }
+
+        // set up variables on first iteration
+        if iteration == 2 && extrapolate_all {
+            error_over_large_ranges = errsum;
+            ertest = tolerance;
+            table.append(area);
+            continue;
+        }
+
+        if disallow_extrapolation {
+            continue;
+        }
+
+        if extrapolate_all {
+            error_over_large_ranges -= last_e_i;
+
+            if current_level < ws.maximum_level() {
+                error_over_large_ranges += error12;
+            }
+        }
+
+        if !(extrapolate_all && extrapolate) {
+            // 次に分割する区間が最小区間である場合のみ、補外を行う
+            if ws.get().level < ws.maximum_level() {
+                continue;
+            }
+
+            if extrapolate_all {
+                extrapolate = true;
+                ws.nrmax = 1;
+            } else {
+                // start extrapolation once the subrange to be bisected next
+                // is shorter than the period
+                let width = ws.get().range.end - ws.get().range.begin;
+                if 0.25 * width.abs() * abs_omega > 2. {
+                    continue;
+                }
+
+                extrapolate_all = true;
+                error_over_large_ranges = errsum;
+                ertest = tolerance;
+                continue;
+            }
+        }
+
+        // 大区間のみの誤差がまだ要求値を上回っている場合、大区間の分割を優先する
+        if !error2 && error_over_large_ranges > ertest && ws.increase_nrmax() {
+            continue;
+        }
+
+        // 今までの計算結果から収束値を推定する
+        table.append(area);
+        if table.n >= 3 {
+            let (mut reseps, mut abseps) = (0., 0.);
+            table.qelg(&mut reseps, &mut abseps);
+
+            ktmin += 1;
+            if ktmin > 5 && err_ext < 0.001 * errsum {
+                error = Some(RoundoffError);
+            }
+
+            // 補外の精度が前回（の補外）を上回った場合、結果を置き換える
+            if abseps < err_ext {
+                ktmin = 0;
+                err_ext = abseps;
+                res_ext = reseps;
+                correc = error_over_large_ranges;
+                ertest = config.tolerance.to_abs(reseps.abs());
+                if err_ext <= ertest {
+                    break;
+                }
+            }
+
+            // Prepare bisection of the smallest range.
+            if table.n == 1 {
+                disallow_extrapolation = true;
+            }
+
+            if error.is_some() {
+                break;
+            }
+        }
+
+        // work on range with largest error
+        ws.reset_nrmax();
+        extrapolate = false;
+        error_over_large_ranges = errsum;
+    }
+
+    if err_ext == core::f64::MAX {
+        return finish(ws.sum_results(), errsum, nevals, error);
+    }
+
+    if error.is_some() || error2 {
+        if error2 {
+            err_ext += correc;
+        }
+
+        if error.is_none() {
+            error = Some(RoundoffError);
+        }
+
+        if res_ext != 0.0 && area != 0.0 {
+            if err_ext / res_ext.abs() > errsum / area.abs() {
+                return finish(ws.sum_results(), errsum, nevals, error);
+            }
+        } else if err_ext > errsum {
+            return finish(ws.sum_results(), errsum, nevals, error);
+        } else if area == 0.0 {
+            return finish(res_ext, err_ext, nevals, error);
+        }
+    }
+
+    //  Test on divergence.
+    let positive_integrand = test_positivity(result0.estimate, result0.absvalue);
+
+    if !positive_integrand && f64::max(res_ext.abs(), area.abs()) < 0.01 * result0.absvalue {
+        return finish(res_ext, err_ext, nevals, error);
+    }
+
+    let ratio = res_ext / area;
+    if (!(0.01..=100.0).contains(&ratio) || errsum > area.abs()) && error.is_none() {
+        error = Some(Divergent);
+    }
+
+    finish(res_ext, err_ext, nevals, error)
+}
+
+#[inline]
+#[must_use]
+fn finish(
+    estimate: f64,
+    delta: f64,
+    nevals: usize,
+    error: Option<RuntimeError>,
+) -> IntegrationResult {
+    IntegrationResult {
+        value: Solution {
+            estimate,
+            delta,
+            nevals,
+        },
+        error,
+    }
+}
@@ -0,0 +1,522 @@
+use smallvec::SmallVec;
+
+use crate::common::{IntegrationResult, Solution};
+use crate::error::RuntimeError::{self, *};
+use crate::single::algorithm::Algorithm;
+use crate::single::common::{Integrand, IntegrationConfig, Range};
+use crate::single::qcheb::{qcheb, ChebyshevSeries};
+use crate::single::qk::qk25;
+use crate::single::util::{bisect, subrange_too_small};
+use crate::single::workspace::{SubRangeInfo, WorkSpace};
+use crate::utils::CowMut;
+
+/// Weight function `(x-a)^α (b-x)^β log(x-a)^μ log(b-x)^ν`, which has
+/// algebraic-logarithmic singularities at the end points of the range `[a, b]`.
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub struct AlgebraicLogWeight {
+    alpha: f64,
+    beta: f64,
+    mu: i32,
+    nu: i32,
+}
+
+impl AlgebraicLogWeight {
+    /// Create a new `AlgebraicLogWeight` object
+    ///
+    /// Return `None` unless `α > -1`, `β > -1`, and both `μ` and `ν` are 0 or 1.
+    pub fn new(alpha: f64, beta: f64, mu: i32, nu: i32) -> Option<AlgebraicLogWeight> {
+        if alpha > -1. && beta > -1. && (mu == 0 || mu == 1) && (nu == 0 || nu == 1) {
+            Some(AlgebraicLogWeight {
+                alpha,
+                beta,
+                mu,
+                nu,
+            })
+        } else {
+            None
+        }
+    }
+
+    /// exponent of `(x-a)`
+    #[inline]
+    pub fn alpha(&self) -> f64 {
+        self.alpha
+    }
+
+    /// exponent of `(b-x)`
+    #[inline]
+    pub fn beta(&self) -> f64 {
+        self.beta
+    }
+
+    /// exponent of `log(x-a)`
+    #[inline]
+    pub fn mu(&self) -> i32 {
+        self.mu
+    }
+
+    /// exponent of `log(b-x)`
+    #[inline]
+    pub fn nu(&self) -> i32 {
+        self.nu
+    }
+
+    /// evaluate the factors of the weight function which are singular at `a`
+    #[inline]
+    fn left(&self, x: f64, a: f64) -> f64 {
+        let mut factor = 1.0;
+        if self.alpha != 0.0 {
+            factor *= (x - a).powf(self.alpha);
+        }
+        if self.mu == 1 {
+            factor *= (x - a).ln();
+        }
+        factor
+    }
+
+    /// evaluate the factors of the weight function which are singular at `b`
+    #[inline]
+    fn right(&self, x: f64, b: f64) -> f64 {
+        let mut factor = 1.0;
+        if self.beta != 0.0 {
+            factor *= (b - x).powf(self.beta);
+        }
+        if self.nu == 1 {
+            factor *= (b - x).ln();
+        }
+        factor
+    }
+}
+
+/// Integrate `(x-a)^α (b-x)^β log(x-a)^μ log(b-x)^ν f(x)` over the finite range
+/// `[a, b]`.
+///
+/// The subranges which contain the end points are integrated with the modified
+/// Clenshaw-Curtis rule based on Chebyshev moments of the weight function, and
+/// the others with 25-point Gauss-Kronrod rule.
+///
+/// Note that the integrand `f` must not contain the weight function.
+///
+/// # Examples
+///
+/// ```
+/// use gkquad::single::algorithm::{AlgebraicLogWeight, QAWS};
+/// use gkquad::single::Integrator;
+///
+/// // ∫ log(x) / √x dx (0 < x < 1)
+/// let weight = AlgebraicLogWeight::new(-0.5, 0.0, 1, 0).unwrap();
+/// let result = Integrator::with_algorithm(|_: f64| 1.0, QAWS::new(weight))
+///     .run(0.0..1.0)
+///     .unwrap()
+///     .estimate;
+/// ```
+#[derive(Clone)]
+pub struct QAWS<'a> {
+    weight: AlgebraicLogWeight,
+    moments: Moments,
+    workspace: CowMut<'a, WorkSpace>,
+}
+
+impl<'a> QAWS<'a> {
+    /// Create a new `QAWS` object with the given weight function
+    #[inline]
+    pub fn new(weight: AlgebraicLogWeight) -> Self {
+        Self {
+            moments: Moments::new(weight.alpha, weight.beta),
+            weight,
+            workspace: CowMut::Owned(WorkSpace::new()),
+        }
+    }
+
+    #[inline]
+    #[doc(hidden)]
+    pub fn with_workspace(weight: AlgebraicLogWeight, ws: &'a mut WorkSpace) -> Self {
+        Self {
+            moments: Moments::new(weight.alpha, weight.beta),
+            weight,
+            workspace: CowMut::Borrowed(ws),
+        }
+    }
+
+    /// weight function of the integrand
+    #[inline]
+    pub fn weight(&self) -> &AlgebraicLogWeight {
+        &self.weight
+    }
+}
+
+impl<'a, F: Integrand + ?Sized> Algorithm<F> for QAWS<'a> {
+    fn integrate(
+        &mut self,
+        f: &mut F,
+        range: &Range,
+        config: &IntegrationConfig,
+    ) -> IntegrationResult {
+        if !range.begin.is_finite() || !range.end.is_finite() || range.begin == range.end {
+            return IntegrationResult::with_error(Solution::default(), InvalidRange);
+        }
+
+        if range.begin > range.end {
+            let range = unsafe { Range::new_unchecked(range.end, range.begin) };
+            let mut result = self.integrate(f, &range, config);
+            result.value.estimate = -result.value.estimate;
+            return result;
+        }
+
+        integrate_impl(
+            f,
+            range,
+            config,
+            &self.weight,
+            &self.moments,
+            &mut *self.workspace,
+        )
+    }
+}
+
+impl<'a> core::fmt::Debug for QAWS<'a> {
+    fn fmt(&self, f: &mut core::fmt::Formatter) -> core::fmt::Result {
+        f.debug_struct("QAWS")
+            .field("weight", &self.weight)
+            .finish()
+    }
+}
+
+impl<'a> PartialEq for QAWS<'a> {
+    #[inline]
+    fn eq(&self, other: &Self) -> bool {
+        self.weight == other.weight
+    }
+}
+
+/// Chebyshev moments of the weight function over (-1, 1)
+///
+/// * `ri[k]` - `∫ (1+t)^α T_k(t) dt`
+/// * `rj[k]` - `∫ (1-t)^β T_k(t) dt`
+/// * `rg[k]` - `∫ (1+t)^α log((1+t)/2) T_k(t) dt`
+/// * `rh[k]` - `∫ (1-t)^β log((1-t)/2) T_k(t) dt`
+#[derive(Clone)]
+struct Moments {
+    ri: [f64; 25],
+    rj: [f64; 25],
+    rg: [f64; 25],
+    rh: [f64; 25],
+}
+
+impl Moments {
+    fn new(alpha: f64, beta: f64) -> Self {
+        let (ri, rg) = algebraic_log_moments(alpha);
+        let (mut rj, mut rh) = algebraic_log_moments(beta);
+
+        // T_k(-t) = (-1)^k T_k(t)
+        for k in (1..25).step_by(2) {
+            rj[k] = -rj[k];
+            rh[k] = -rh[k];
+        }
+
+        Self { ri, rj, rg, rh }
+    }
+}
+
+/// compute `∫ (1+t)^α T_k(t) dt` and `∫ (1+t)^α log((1+t)/2) T_k(t) dt`
+/// (-1 < t < 1) for k = 0, ..., 24
+fn algebraic_log_moments(alpha: f64) -> ([f64; 25], [f64; 25]) {
+    let alpha_p1 = alpha + 1.0;
+    let alpha_p2 = alpha + 2.0;
+    let r_alpha = 2f64.powf(alpha_p1);
+
+    let mut ri = [0.0; 25];
+    let mut rg = [0.0; 25];
+
+    ri[0] = r_alpha / alpha_p1;
+    ri[1] = ri[0] * alpha / alpha_p2;
+
+    let (mut an, mut anm1) = (2.0, 1.0);
+    for i in 2..25 {
+        ri[i] = -(r_alpha + an * (an - alpha_p2) * ri[i - 1]) / (anm1 * (an + alpha_p1));
+        anm1 = an;
+        an += 1.0;
+    }
+
+    rg[0] = -ri[0] / alpha_p1;
+    rg[1] = -rg[0] - 2.0 * r_alpha / (alpha_p2 * alpha_p2);
+
+    let (mut an, mut anm1) = (2.0, 1.0);
+    for i in 2..25 {
+        rg[i] = -(an * (an - alpha_p2) * rg[i - 1] - an * ri[i - 1] + anm1 * ri[i])
+            / (anm1 * (an + alpha_p1));
+        anm1 = an;
+        an += 1.0;
+    }
+
+    (ri, rg)
+}
+
+#[derive(Clone, Copy, PartialEq)]
+enum Factors {
+    /// factors singular at `a`
+    Left,
+    /// factors singular at `b`
+    Right,
+    Both,
+}
+
+/// integrand multiplied by (some factors of) the weight function
+struct WeightedIntegrand<'a, F: Integrand + ?Sized> {
+    inner: &'a mut F,
+    weight: &'a AlgebraicLogWeight,
+    range: &'a Range,
+    factors: Factors,
+}
+
+impl<'a, F: Integrand + ?Sized> WeightedIntegrand<'a, F> {
+    #[inline]
+    fn weight(&self, x: f64) -> f64 {
+        let (a, b) = (self.range.begin, self.range.end);
+        match self.factors {
+            Factors::Left => self.weight.left(x, a),
+            Factors::Right => self.weight.right(x, b),
+            Factors::Both => self.weight.left(x, a) * self.weight.right(x, b),
+        }
+    }
+}
+
+impl<'a, F: Integrand + ?Sized> Integrand for WeightedIntegrand<'a, F> {
+    #[inline]
+    fn apply(&mut self, x: f64) -> f64 {
+        self.inner.apply(x) * self.weight(x)
+    }
+
+    fn apply_to_slice(&mut self, s: &mut [f64]) {
+        let weights: SmallVec<[f64; 32]> = s.iter().map(|&x| self.weight(x)).collect();
+        self.inner.apply_to_slice(s);
+        s.iter_mut().zip(weights).for_each(|(y, w)| *y *= w);
+    }
+}
+
+/// result of `qc25s`
+struct QCResult {
+    estimate: f64,
+    delta: f64,
+    /// false if the error estimate may be unreliable because of the roundoff
+    reliable: bool,
+}
+
+/// Integrate `w(x)f(x)` over the subrange `r` of the range `[a, b]`.
+fn qc25s<F: Integrand + ?Sized>(
+    f: &mut F,
+    range: &Range,
+    r: &Range,
+    weight: &AlgebraicLogWeight,
+    moments: &Moments,
+) -> QCResult {
+    let half_length = 0.5 * (r.end - r.begin);
+
+    let mut wrapper = WeightedIntegrand {
+        inner: f,
+        weight,
+        range,
+        factors: Factors::Both,
+    };
+
+    if r.begin == range.begin && (weight.alpha != 0.0 || weight.mu != 0) {
+        wrapper.factors = Factors::Right;
+        let series = qcheb(&mut wrapper, r);
+        let factor = half_length.powf(weight.alpha + 1.0);
+
+        if weight.mu == 0 {
+            let (res12, res24) = compute_result(&moments.ri, &series);
+            QCResult {
+                estimate: factor * res24,
+                delta: (factor * (res24 - res12)).abs(),
+                reliable: false,
+            }
+        } else {
+            let u = factor * (r.end - r.begin).ln();
+            let v = factor;
+
+            let (res12a, res24a) = compute_result(&moments.ri, &series);
+            let (res12b, res24b) = compute_result(&moments.rg, &series);
+            QCResult {
+                estimate: u * res24a + v * res24b,
+                delta: (u * (res24a - res12a)).abs() + (v * (res24b - res12b)).abs(),
+                reliable: false,
+            }
+        }
+    } else if r.end == range.end && (weight.beta != 0.0 || weight.nu != 0) {
+        wrapper.factors = Factors::Left;
+        let series = qcheb(&mut wrapper, r);
+        let factor = half_length.powf(weight.beta + 1.0);
+
+        if weight.nu == 0 {
+            let (res12, res24) = compute_result(&moments.rj, &series);
+            QCResult {
+                estimate: factor * res24,
+                delta: (factor * (res24 - res12)).abs(),
+                reliable: false,
+            }
+        } else {
+            let u = factor * (r.end - r.begin).ln();
+            let v = factor;
+
+            let (res12a, res24a) = compute_result(&moments.rj, &series);
+            let (res12b, res24b) = compute_result(&moments.rh, &series);
+            QCResult {
+                estimate: u * res24a + v * res24b,
+                delta: (u * (res24a - res12a)).abs() + (v * (res24b - res12b)).abs(),
+                reliable: false,
+            }
+        }
+    } else {
+        let result = qk25(&mut wrapper, r);
+        QCResult {
+            estimate: result.estimate,
+            delta: result.delta,
+            reliable: result.delta != result.asc,
+        }
+    }
+}
+
+#[inline]
+fn compute_result(r: &[f64; 25], series: &ChebyshevSeries) -> (f64, f64) {
+    let res12 = r.iter().zip(series.cheb12.iter()).map(|(r, c)| r * c).sum();
+    let res24 = r.iter().zip(series.cheb24.iter()).map(|(r, c)| r * c).sum();
+    (res12, res24)
+}
+
+fn integrate_impl<F: Integrand + ?Sized>(
+    f: &mut F,
+    range: &Range,
+    config: &IntegrationConfig,
+    weight: &AlgebraicLogWeight,
+    moments: &Moments,
+    ws: &mut WorkSpace,
+) -> IntegrationResult {
+    let (mut roundoff_type1, mut roundoff_type2) = (0i32, 0i32);
+    let mut error = None;
+
+    if config.max_evals < 50 {
+        return IntegrationResult::with_error(Solution::default(), InsufficientIteration);
+    }
+
+    // initial integral over the two halves of the range, so that each
+    // subrange contains at most one singular end point
+    let (r1, r2) = bisect(range);
+    let result1 = qc25s(f, range, &r1, weight, moments);
+    let result2 = qc25s(f, range, &r2, weight, moments);
+    let mut nevals = 50;
+
+    let result0 = result1.estimate + result2.estimate;
+    let delta0 = result1.delta + result2.delta;
+
+    if result0.is_nan() {
+        return finish(result0, delta0, nevals, Some(NanValueEncountered));
+    }
+
+    let max_iters = (config.max_evals - nevals) / 50;
+
+    ws.clear();
+    ws.reserve(max_iters + 2);
+
+    let s1 = SubRangeInfo::new(r1, result1.estimate, result1.delta, 0);
+    let s2 = SubRangeInfo::new(r2, result2.estimate, result2.delta, 0);
+    if result1.delta > result2.delta {
+        ws.push(s1);
+        ws.push(s2);
+    } else {
+        ws.push(s2);
+        ws.push(s1);
+    }
+
+    // use 0.01 relative error as an extra safety margin on the first iteration
+    let mut tolerance = config.tolerance.to_abs(result0.abs());
+    if delta0 < tolerance && delta0 < 0.01 * result0.abs() {
+        return finish(result0, delta0, nevals, None);
+    } else if max_iters == 0 {
+        return finish(result0, delta0, nevals, Some(InsufficientIteration));
+    }
+
+    let mut area = result0;
+    let mut errsum = delta0;
+
+    for iteration in 2..=max_iters + 1 {
+        // Bisect the subrange with the largest error estimate
+        let info = ws.get();
+        let current_level = info.level + 1;
+
+        let (r1, r2) = bisect(&info.range);
+
+        let result1 = qc25s(f, range, &r1, weight, moments);
+        let result2 = qc25s(f, range, &r2, weight, moments);
+        nevals += 50;
+
+        if result1.estimate.is_nan() || result2.estimate.is_nan() {
+            error = Some(NanValueEncountered);
+            break;
+        }
+
+        let area12 = result1.estimate + result2.estimate;
+        let error12 = result1.delta + result2.delta;
+
+        errsum += error12 - info.delta;
+        area += area12 - info.estimate;
+
+        if result1.reliable && result2.reliable {
+            if (info.estimate - area12).abs() <= 1e-5 * area12.abs() && error12 >= 0.99 * info.delta
+            {
+                roundoff_type1 += 1;
+            }
+
+            if iteration >= 10 && error12 > info.delta {
+                roundoff_type2 += 1;
+            }
+        }
+
+        tolerance = config.tolerance.to_abs(area.abs());
+
+        if errsum > tolerance {
+            if roundoff_type1 >= 6 || roundoff_type2 >= 20 {
+                error = Some(RoundoffError);
+            }
+
+            // set error flag in the case of bad integrand behaviour at
+            // a point of the integration range
+            if subrange_too_small(r1.begin, r1.end, r2.end) {
+                error = Some(SubrangeTooSmall);
+            }
+        }
+
+        ws.update(
+            SubRangeInfo::new(r1, result1.estimate, result1.delta, current_level),
+            SubRangeInfo::new(r2, result2.estimate, result2.delta, current_level),
+        );
+
+        if error.is_some() || errsum <= tolerance {
+            break;
+        }
+    }
+
+    if error.is_none() && errsum > tolerance {
+        error = Some(InsufficientIteration);
+    }
+
+    finish(ws.sum_results(), errsum, nevals, error)
+}
+
+#[inline]
+#[must_use]
+fn finish(
+    estimate: f64,
+    delta: f64,
+    nevals: usize,
+    error: Option<RuntimeError>,
+) -> IntegrationResult {
+    IntegrationResult {
+        value: Solution {
+            estimate,
+            delta,
+            nevals,
+        },
+        error,
+    }
+}
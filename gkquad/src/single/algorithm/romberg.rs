@@ -0,0 +1,200 @@
+use alloc::vec;
+use alloc::vec::Vec;
+
+use crate::common::{IntegrationResult, Solution};
+use crate::error::RuntimeError::{self, *};
+use crate::single::algorithm::Algorithm;
+use crate::single::common::{Integrand, IntegrationConfig, Range};
+
+/// the convergence is not tested until the range is divided into `2^MIN_LEVEL`
+/// intervals
+const MIN_LEVEL: usize = 4;
+/// maximum number of the halvings
+const MAX_LEVEL: usize = 30;
+
+/// Romberg integration
+///
+/// The trapezoid rule is applied with the number of intervals doubled in turn,
+/// reusing all the previous function values, and each row of the table is
+/// extrapolated by Richardson extrapolation. The difference between the last
+/// two diagonal entries is returned as the error estimate.
+///
+/// For the periodic integrand over a full period, the trapezoid rule itself
+/// converges exponentially. In that case, create the object with
+/// [`periodic`](#method.periodic) so that the extrapolation is skipped and the
+/// difference between the last two trapezoid sums is used as the error
+/// estimate.
+///
+/// Since the trapezoid rule uses the end points of the range, the integrand
+/// must be finite at the end points. This algorithm cannot be applied to the
+/// infinite range.
+///
+/// # Examples
+///
+/// ```
+/// use core::f64::consts::PI;
+/// use gkquad::single::algorithm::Romberg;
+/// use gkquad::single::Integrator;
+///
+/// // ∫ e^cos(x) dx (0 < x < 2π)
+/// let result = Integrator::with_algorithm(|x: f64| x.cos().exp(), Romberg::periodic())
+///     .run(0.0..2.0 * PI)
+///     .unwrap()
+///     .estimate;
+/// ```
+#[derive(Clone)]
+pub struct Romberg {
+    periodic: bool,
+}
+
+impl Romberg {
+    #[inline]
+    pub fn new() -> Self {
+        Self { periodic: false }
+    }
+
+    /// Create a new `Romberg` object for the periodic integrand
+    ///
+    /// The range must be a full period of the integrand, i.e. `f(a) = f(b)`.
+    /// The integrand is evaluated only at the beginning of the range.
+    #[inline]
+    pub fn periodic() -> Self {
+        Self { periodic: true }
+    }
+
+    /// Returns `true` if the integrand is treated as periodic
+    #[inline]
+    pub fn is_periodic(&self) -> bool {
+        self.periodic
+    }
+}
+
+impl<F: Integrand + ?Sized> Algorithm<F> for Romberg {
+    fn integrate(
+        &mut self,
+        f: &mut F,
+        range: &Range,
+        config: &IntegrationConfig,
+    ) -> IntegrationResult {
+        if !range.begin.is_finite() || !range.end.is_finite() {
+            return IntegrationResult::with_error(Solution::default(), InvalidRange);
+        }
+
+        integrate_impl(f, range, config, self.periodic)
+    }
+}
+
+impl Default for Romberg {
+    #[inline]
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl core::fmt::Debug for Romberg {
+    fn fmt(&self, f: &mut core::fmt::Formatter) -> core::fmt::Result {
+        f.debug_struct("Romberg")
+            .field("periodic", &self.periodic)
+            .finish()
+    }
+}
+
+impl PartialEq for Romberg {
+    #[inline]
+    fn eq(&self, other: &Self) -> bool {
+        self.periodic == other.periodic
+    }
+}
+
+fn integrate_impl<F: Integrand + ?Sized>(
+    f: &mut F,
+    range: &Range,
+    config: &IntegrationConfig,
+    periodic: bool,
+) -> IntegrationResult {
+    let (a, b) = (range.begin, range.end);
+    let h = b - a;
+
+    let mut ends = if periodic { vec![a] } else { vec![a, b] };
+    if config.max_evals < ends.len() {
+        return IntegrationResult::with_error(Solution::default(), InsufficientIteration);
+    }
+    f.apply_to_slice(&mut ends);
+    let mut nevals = ends.len();
+
+    // sum of the function values, where the end points are halved
+    let mut sum = if periodic {
+        ends[0]
+    } else {
+        0.5 * (ends[0] + ends[1])
+    };
+
+    // previous row of the Romberg table
+    let mut row: Vec<f64> = Vec::with_capacity(MAX_LEVEL + 1);
+    row.push(h * sum);
+
+    let mut estimate = row[0];
+    let mut delta = core::f64::MAX;
+
+    for level in 1..=MAX_LEVEL {
+        // midpoints of the intervals of the previous level
+        let n = 1usize << (level - 1);
+        if nevals + n > config.max_evals {
+            return finish(estimate, delta, nevals, Some(InsufficientIteration));
+        }
+
+        let step = h / n as f64;
+        let mut x: Vec<f64> = (0..n).map(|i| a + (i as f64 + 0.5) * step).collect();
+        f.apply_to_slice(&mut x);
+        nevals += n;
+
+        sum += x.iter().sum::<f64>();
+        let trapezoid = 0.5 * step * sum;
+
+        let previous = estimate;
+        if periodic {
+            estimate = trapezoid;
+        } else {
+            // Richardson extrapolation
+            let mut value = trapezoid;
+            let mut factor = 1.0;
+            for prev in row.iter_mut() {
+                factor *= 4.0;
+                let next = value + (value - *prev) / (factor - 1.0);
+                *prev = value;
+                value = next;
+            }
+            row.push(value);
+            estimate = value;
+        }
+
+        if estimate.is_nan() {
+            return finish(estimate, delta, nevals, Some(NanValueEncountered));
+        }
+
+        delta = (estimate - previous).abs();
+        if level >= MIN_LEVEL && delta <= config.tolerance.to_abs(estimate.abs()) {
+            return finish(estimate, delta, nevals, None);
+        }
+    }
+
+    finish(estimate, delta, nevals, Some(InsufficientIteration))
+}
+
+#[inline]
+#[must_use]
+fn finish(
+    estimate: f64,
+    delta: f64,
+    nevals: usize,
+    error: Option<RuntimeError>,
+) -> IntegrationResult {
+    IntegrationResult {
+        value: Solution {
+            estimate,
+            delta,
+            nevals,
+        },
+        error,
+    }
+}
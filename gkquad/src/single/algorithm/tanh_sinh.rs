@@ -0,0 +1,376 @@
+use alloc::vec::Vec;
+use core::f64::consts::FRAC_PI_2;
+
+use crate::common::{IntegrationResult, Solution};
+use crate::error::RuntimeError::{self, *};
+use crate::single::algorithm::Algorithm;
+use crate::single::common::{Integrand, IntegrationConfig, Range};
+
+/// Tanh-sinh (double exponential) quadrature
+///
+/// The integrand is transformed by `x = tanh(π/2 sinh(t))` so that the
+/// transformed integrand decays double exponentially, and then integrated by
+/// the trapezoidal rule. The step size is halved at each level, and the points
+/// evaluated in the previous levels are reused.
+///
+/// This algorithm is robust against the singularities at the end points of
+/// the range. Infinite ranges are handled in the same way as
+/// [`DoubleExponential`](struct.DoubleExponential.html).
+///
+/// # Examples
+///
+/// ```
+/// use gkquad::single::algorithm::TanhSinh;
+/// use gkquad::single::Integrator;
+///
+/// // ∫ 1 / √x dx (0 < x < 1)
+/// let result = Integrator::with_algorithm(|x: f64| 1. / x.sqrt(), TanhSinh::new())
+///     .run(0.0..1.0)
+///     .unwrap()
+///     .estimate;
+/// ```
+#[derive(Clone)]
+pub struct TanhSinh {
+    _private: (),
+}
+
+impl TanhSinh {
+    #[inline]
+    pub fn new() -> Self {
+        Self { _private: () }
+    }
+}
+
+impl<F: Integrand + ?Sized> Algorithm<F> for TanhSinh {
+    #[inline]
+    fn integrate(
+        &mut self,
+        f: &mut F,
+        range: &Range,
+        config: &IntegrationConfig,
+    ) -> IntegrationResult {
+        integrate_range(f, range, config)
+    }
+}
+
+extra_traits!(TanhSinh);
+
+/// Double exponential quadrature for infinite and semi-infinite ranges
+///
+/// The semi-infinite range is mapped by exp-sinh transformation
+/// (`x = a ± exp(π/2 sinh(t))`), and (-∞, ∞) by sinh-sinh transformation
+/// (`x = sinh(π/2 sinh(t))`). The transformed integrand decays double
+/// exponentially even if the original integrand decays slowly, like
+/// `1/(1+x²)^0.6`, for which `transform_range` produces the end point
+/// singularities and QAGS fails with `Divergent` error.
+///
+/// The decay of the transformed integrand is detected automatically, and no
+/// scale of the transformation is required. After each level, the nodes where
+/// the integrand is negligible compared to the current estimate are excluded
+/// from the following levels. If the integrand turns out not to be negligible
+/// at the boundary of the excluded nodes, the level is computed again with
+/// all the nodes.
+///
+/// If the range is finite, `InvalidRange` error is returned. Use
+/// [`TanhSinh`](struct.TanhSinh.html) for the finite ranges.
+///
+/// # Examples
+///
+/// ```
+/// use core::f64::{INFINITY, NEG_INFINITY};
+/// use gkquad::single::algorithm::DoubleExponential;
+/// use gkquad::single::Integrator;
+///
+/// // ∫ 1 / (1 + x²)^0.6 dx (-∞ < x < ∞)
+/// let result = Integrator::with_algorithm(
+///     |x: f64| (1. + x * x).powf(-0.6),
+///     DoubleExponential::new(),
+/// )
+/// .run(NEG_INFINITY..INFINITY)
+/// .unwrap()
+/// .estimate;
+/// ```
+#[derive(Clone)]
+pub struct DoubleExponential {
+    _private: (),
+}
+
+impl DoubleExponential {
+    #[inline]
+    pub fn new() -> Self {
+        Self { _private: () }
+    }
+}
+
+impl<F: Integrand + ?Sized> Algorithm<F> for DoubleExponential {
+    fn integrate(
+        &mut self,
+        f: &mut F,
+        range: &Range,
+        config: &IntegrationConfig,
+    ) -> IntegrationResult {
+        if range.begin.is_finite() && range.end.is_finite() {
+            return IntegrationResult::with_error(Solution::default(), InvalidRange);
+        }
+
+        integrate_range(f, range, config)
+    }
+}
+
+extra_traits!(DoubleExponential);
+
+/// choose the transformation for the range, and integrate over it
+fn integrate_range<F: Integrand + ?Sized>(
+    f: &mut F,
+    range: &Range,
+    config: &IntegrationConfig,
+) -> IntegrationResult {
+    let (begin, end) = (range.begin, range.end);
+    let (transform, negative) = if begin.is_finite() && end.is_finite() {
+        if begin <= end {
+            (Transform::Finite { a: begin, b: end }, false)
+        } else {
+            (Transform::Finite { a: end, b: begin }, true)
+        }
+    } else if begin.is_finite() || end.is_finite() {
+        let (a, infinity) = if begin.is_finite() {
+            (begin, end)
+        } else {
+            (end, begin)
+        };
+        let sign = infinity.signum();
+        // ∫ f dx (a < x < ∞) is positive, and ∫ f dx (-∞ < x < a) as well.
+        (
+            Transform::HalfInfinite { a, sign },
+            begin.is_finite() != (sign > 0.0),
+        )
+    } else {
+        (Transform::Infinite, begin > end)
+    };
+
+    let mut result = integrate_impl(f, &transform, config);
+    if negative {
+        result.value.estimate = -result.value.estimate;
+    }
+    result
+}
+
+/// variable transformation which maps `t` in (-∞, ∞) to `x` in the range
+enum Transform {
+    /// tanh-sinh: `x = (a + b) / 2 + (b - a) / 2 * tanh(π/2 sinh(t))`
+    Finite { a: f64, b: f64 },
+    /// exp-sinh: `x = a + sign * exp(π/2 sinh(t))`
+    HalfInfinite { a: f64, sign: f64 },
+    /// sinh-sinh: `x = sinh(π/2 sinh(t))`
+    Infinite,
+}
+
+impl Transform {
+    /// truncation point of the trapezoidal rule
+    ///
+    /// The abscissae beyond this point underflow to the end points or
+    /// overflow to infinity.
+    #[inline]
+    fn t_max(&self) -> f64 {
+        match *self {
+            Transform::Finite { .. } => 6.1,
+            _ => 6.7,
+        }
+    }
+
+    /// Returns `true` if the truncation point should be determined from the
+    /// decay of the integrand
+    #[inline]
+    fn detect_decay(&self) -> bool {
+        !matches!(*self, Transform::Finite { .. })
+    }
+
+    /// Return the abscissa and the weight at `t`, or `None` if the abscissa
+    /// collapses into the end point of the range.
+    #[inline]
+    fn node(&self, t: f64) -> Option<(f64, f64)> {
+        let u = FRAC_PI_2 * t.sinh();
+        let dudt = FRAC_PI_2 * t.cosh();
+
+        let (x, w) = match *self {
+            Transform::Finite { a, b } => {
+                // distance from the nearest end point, which is calculated
+                // without cancellation
+                let half_length = 0.5 * (b - a);
+                let d = 2. * half_length / (1. + (2. * u.abs()).exp());
+                let x = if t >= 0.0 { b - d } else { a + d };
+                if x == a || x == b {
+                    return None;
+                }
+
+                (x, dudt * d * (2. - d / half_length))
+            }
+            Transform::HalfInfinite { a, sign } => {
+                let e = u.exp();
+                let x = a + sign * e;
+                if x == a {
+                    return None;
+                }
+
+                (x, dudt * e)
+            }
+            Transform::Infinite => (u.sinh(), dudt * u.cosh()),
+        };
+
+        if w == 0.0 || !x.is_finite() {
+            None
+        } else {
+            Some((x, w))
+        }
+    }
+}
+
+fn integrate_impl<F: Integrand + ?Sized>(
+    f: &mut F,
+    transform: &Transform,
+    config: &IntegrationConfig,
+) -> IntegrationResult {
+    // minimum level at which the convergence is tested
+    const MIN_LEVEL: usize = 3;
+    // maximum level of the refinement
+    const MAX_LEVEL: usize = 20;
+
+    let t_max = transform.t_max();
+    // truncation points of the negative and positive side
+    let (mut t_left, mut t_right) = (-t_max, t_max);
+    // magnitude of the terms at the truncation points
+    let (mut left_term, mut right_term) = (0.0, 0.0);
+
+    // positions, abscissae and weights of the current level
+    let mut nodes: Vec<(f64, f64, f64)> = Vec::new();
+    let mut fv = Vec::new();
+
+    let mut sum = 0.0;
+    let mut estimate = 0.0;
+    let mut delta = core::f64::MAX;
+    let mut nevals = 0;
+
+    let mut level = 0;
+    // true if the nodes of the previous levels must be evaluated again
+    let mut restart = false;
+
+    while level <= MAX_LEVEL {
+        let h = 0.5f64.powi(level as i32);
+
+        // level 0 uses t = 0, ±1, ±2, ..., and the other levels use only the
+        // odd multiples of h which were not evaluated in the previous levels.
+        let (first, step) = if level == 0 || restart {
+            (0, 1)
+        } else {
+            (1, 2)
+        };
+        let n = (t_max / h) as usize;
+
+        nodes.clear();
+        for m in (first..=n).step_by(step) {
+            let t = m as f64 * h;
+            if t <= t_right {
+                nodes.extend(transform.node(t).map(|(x, w)| (t, x, w)));
+            }
+            if m != 0 && -t >= t_left {
+                nodes.extend(transform.node(-t).map(|(x, w)| (-t, x, w)));
+            }
+        }
+
+        if nevals + nodes.len() > config.max_evals {
+            return finish(estimate, delta, nevals, Some(InsufficientIteration));
+        }
+
+        fv.clear();
+        fv.extend(nodes.iter().map(|&(_, x, _)| x));
+        f.apply_to_slice(&mut fv);
+        nevals += fv.len();
+
+        let terms = fv
+            .iter()
+            .zip(nodes.iter())
+            .map(|(y, &(_, _, w))| y * w)
+            .sum::<f64>();
+        if restart {
+            sum = terms;
+        } else {
+            sum += terms;
+        }
+
+        let previous = estimate;
+        estimate = h * sum;
+
+        if estimate.is_nan() {
+            return finish(estimate, delta, nevals, Some(NanValueEncountered));
+        }
+
+        if level > 0 {
+            delta = (estimate - previous).abs();
+        }
+        restart = false;
+
+        if transform.detect_decay() && estimate != 0.0 {
+            let threshold = core::f64::EPSILON * estimate.abs();
+
+            if left_term > threshold || right_term > threshold {
+                // the terms at the truncation points are not negligible
+                // compared to the refined estimate, so evaluate this level
+                // again over the whole range
+                t_left = -t_max;
+                t_right = t_max;
+                left_term = 0.0;
+                right_term = 0.0;
+                restart = true;
+                continue;
+            }
+
+            // outermost nodes with the significant terms
+            let (mut lo, mut hi) = (0.0f64, 0.0f64);
+            for (&y, &(t, _, w)) in fv.iter().zip(nodes.iter()) {
+                if (y * w).abs() > threshold {
+                    lo = lo.min(t);
+                    hi = hi.max(t);
+                }
+            }
+
+            // the truncation points are moved to the nodes next to the
+            // outermost significant ones, since the terms between them are
+            // not negligible in the finer levels
+            for (&y, &(t, _, w)) in fv.iter().zip(nodes.iter()) {
+                if t < lo && t > t_left {
+                    t_left = t;
+                    left_term = (y * w).abs();
+                } else if t > hi && t < t_right {
+                    t_right = t;
+                    right_term = (y * w).abs();
+                }
+            }
+        }
+
+        if level >= MIN_LEVEL && delta <= config.tolerance.to_abs(estimate.abs()) {
+            return finish(estimate, delta, nevals, None);
+        }
+
+        level += 1;
+    }
+
+    finish(estimate, delta, nevals, Some(InsufficientIteration))
+}
+
+#[inline]
+#[must_use]
+fn finish(
+    estimate: f64,
+    delta: f64,
+    nevals: usize,
+    error: Option<RuntimeError>,
+) -> IntegrationResult {
+    IntegrationResult {
+        value: Solution {
+            estimate,
+            delta,
+            nevals,
+        },
+        error,
+    }
+}
@@ -6,29 +6,30 @@ use core::marker::PhantomData;
 use core::ops::{Bound, RangeBounds};
 use smallvec::SmallVec;
 
+use crate::float::Float;
 use crate::Tolerance;
 
 /// Singular points
-pub type Points = SmallVec<[f64; 8]>;
+pub type Points<T = f64> = SmallVec<[T; 8]>;
 
 /// Represent the range for which the integral is estimated.
 ///
 /// Both `begin` and `end` are not NaN values (but may be infinite).
 #[derive(Clone, PartialEq)]
-pub struct Range {
+pub struct Range<T = f64> {
     /// beginning of the range
-    pub begin: f64,
+    pub begin: T,
     /// end of the range
-    pub end: f64,
+    pub end: T,
     _private: PhantomData<()>,
 }
 
-impl Range {
+impl<T: Float> Range<T> {
     /// Create a new `Range` object
     ///
     /// Return `None` if either begin or end is NaN.
     #[inline]
-    pub fn new(begin: f64, end: f64) -> Option<Range> {
+    pub fn new(begin: T, end: T) -> Option<Range<T>> {
         if begin.is_nan() || end.is_nan() {
             None
         } else {
@@ -42,7 +43,7 @@ impl Range {
     ///
     /// Arguments must not be a NaN value, otherwise causes an undefined behaviour
     #[inline]
-    pub unsafe fn new_unchecked(begin: f64, end: f64) -> Range {
+    pub unsafe fn new_unchecked(begin: T, end: T) -> Range<T> {
         Range {
             begin,
             end,
@@ -51,24 +52,25 @@ impl Range {
     }
 }
 
-impl Eq for Range {}
+impl<T: Float> Eq for Range<T> {}
 
-impl Display for Range {
+impl<T: Debug> Display for Range<T> {
     fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
         write!(f, "[{:?}, {:?}]", self.begin, self.end)
     }
 }
 
-impl Debug for Range {
+impl<T: Debug> Debug for Range<T> {
     fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
         <Self as Display>::fmt(self, f)
     }
 }
 
-impl Hash for Range {
+impl<T: Float> Hash for Range<T> {
     #[inline]
     fn hash<H: Hasher>(&self, h: &mut H) {
         for &x in &[self.begin, self.end] {
+            let x = x.to_f64();
             let bits = if x == 0.0 {
                 0 // this accounts for +0.0 and -0.0
             } else {
@@ -79,25 +81,35 @@ impl Hash for Range {
     }
 }
 
-impl<R: RangeBounds<f64>> From<R> for Range {
-    fn from(r: R) -> Range {
-        let a = match r.start_bound() {
-            Bound::Excluded(&x) | Bound::Included(&x) => x,
-            Bound::Unbounded => core::f64::NEG_INFINITY,
-        };
+macro_rules! impl_from_range_bounds {
+    ($($t:ident)*) => {
+        $(
+            impl<R: RangeBounds<$t>> From<R> for Range<$t> {
+                fn from(r: R) -> Range<$t> {
+                    let a = match r.start_bound() {
+                        Bound::Excluded(&x) | Bound::Included(&x) => x,
+                        Bound::Unbounded => core::$t::NEG_INFINITY,
+                    };
 
-        let b = match r.end_bound() {
-            Bound::Excluded(&x) | Bound::Included(&x) => x,
-            Bound::Unbounded => core::f64::INFINITY,
-        };
+                    let b = match r.end_bound() {
+                        Bound::Excluded(&x) | Bound::Included(&x) => x,
+                        Bound::Unbounded => core::$t::INFINITY,
+                    };
 
-        Range::new(a, b).expect("cannot create Range object from Range which contains NaN value.")
-    }
+                    Range::new(a, b).expect(
+                        "cannot create Range object from Range which contains NaN value.",
+                    )
+                }
+            }
+        )*
+    };
 }
 
-impl<'a> From<&'a Range> for Range {
+impl_from_range_bounds!(f64 f32);
+
+impl<'a, T: Float> From<&'a Range<T>> for Range<T> {
     #[inline]
-    fn from(other: &'a Range) -> Self {
+    fn from(other: &'a Range<T>) -> Self {
         other.clone()
     }
 }
@@ -105,41 +117,301 @@ impl<'a> From<&'a Range> for Range {
 /// Integration configuration
 #[non_exhaustive]
 #[derive(Clone, Debug, PartialEq)]
-pub struct IntegrationConfig {
+pub struct IntegrationConfig<T = f64> {
     /// the tolerance to be satisfied
-    pub tolerance: Tolerance,
+    pub tolerance: Tolerance<T>,
     /// maximum number of subdivisions
     pub max_evals: usize,
     /// specify singular points
-    pub points: Points,
+    pub points: Points<T>,
+    /// whether the integrand is periodic and the range is a full period
+    ///
+    /// If `true`, `AUTO` algorithm uses the trapezoid rule, which converges
+    /// exponentially for the smooth periodic integrand. Otherwise the trapezoid
+    /// rule does not converge, and up to a quarter of `max_evals` is wasted
+    /// before falling back to `QAGS`.
+    pub periodic: bool,
+    /// transformation applied to the infinite range
+    pub range_transform: RangeTransform,
 }
 
-impl Default for IntegrationConfig {
+impl<T: Float> Default for IntegrationConfig<T> {
     #[inline]
     fn default() -> Self {
         Self {
             tolerance: Tolerance::default(),
             max_evals: 2000,
             points: Points::new(),
+            periodic: false,
+            range_transform: RangeTransform::default(),
+        }
+    }
+}
+
+/// Transformation which maps the infinite range into (-1, 1)
+///
+/// The integral over the infinite range is calculated by changing the variable
+/// to `t` in (-1, 1). `center` and `scale` should be chosen so that the mass
+/// of the integrand lies within a few `scale` from `center`.
+///
+/// For the floating-point types other than `f64`, `Exponential` and `Tan`
+/// transformations are evaluated in `f64` precision.
+#[derive(Clone, Debug, PartialEq)]
+pub enum RangeTransform {
+    /// `x = center + scale * t / (1 - |t|)`, which suits the integrands
+    /// decaying algebraically
+    Rational { center: f64, scale: f64 },
+    /// `x = center - scale * sign(t) * ln(1 - |t|)`, which suits the
+    /// integrands decaying exponentially
+    #[cfg(feature = "std")]
+    #[cfg_attr(docsrs, doc(cfg(feature = "std")))]
+    Exponential { center: f64, scale: f64 },
+    /// `x = center + scale * tan(πt / 2)`
+    #[cfg(feature = "std")]
+    #[cfg_attr(docsrs, doc(cfg(feature = "std")))]
+    Tan { center: f64, scale: f64 },
+}
+
+impl RangeTransform {
+    /// Returns `true` if `center` is finite and `scale` is positive and finite
+    pub fn is_valid(&self) -> bool {
+        let (center, scale) = match *self {
+            RangeTransform::Rational { center, scale } => (center, scale),
+            #[cfg(feature = "std")]
+            RangeTransform::Exponential { center, scale } => (center, scale),
+            #[cfg(feature = "std")]
+            RangeTransform::Tan { center, scale } => (center, scale),
+        };
+        center.is_finite() && scale.is_finite() && scale > 0.0
+    }
+
+    /// Returns `x` corresponding to `t`, and the derivative `dx/dt` split into
+    /// two factors, which are multiplied to the function value in turn
+    #[inline]
+    pub(crate) fn forward<T: Float>(&self, t: T) -> (T, T, T) {
+        match *self {
+            RangeTransform::Rational { center, scale } => {
+                let (center, scale) = (T::from_f64(center), T::from_f64(scale));
+                let coef = T::ONE / (T::ONE - t.abs());
+                (center + scale * t * coef, scale * coef, coef)
+            }
+            #[cfg(feature = "std")]
+            _ => {
+                let (x, coef1, coef2) = self.forward_f64(t.to_f64());
+                (T::from_f64(x), T::from_f64(coef1), T::from_f64(coef2))
+            }
+        }
+    }
+
+    #[cfg(feature = "std")]
+    #[inline]
+    fn forward_f64(&self, t: f64) -> (f64, f64, f64) {
+        match *self {
+            RangeTransform::Rational { .. } => self.forward(t),
+            RangeTransform::Exponential { center, scale } => {
+                let d = -scale * (-t.abs()).ln_1p();
+                let x = if t < 0.0 { center - d } else { center + d };
+                (x, scale, 1. / (1. - t.abs()))
+            }
+            RangeTransform::Tan { center, scale } => {
+                let u = (core::f64::consts::FRAC_PI_2 * t).tan();
+                (
+                    center + scale * u,
+                    scale * core::f64::consts::FRAC_PI_2,
+                    1. + u * u,
+                )
+            }
+        }
+    }
+
+    /// Returns `t` corresponding to `x`
+    pub(crate) fn inverse<T: Float>(&self, x: T) -> T {
+        if x == T::NEG_INFINITY {
+            return -T::ONE;
+        } else if x == T::INFINITY {
+            return T::ONE;
+        }
+
+        match *self {
+            RangeTransform::Rational { center, scale } => {
+                let u = (x - T::from_f64(center)) / T::from_f64(scale);
+                u / (T::ONE + u.abs())
+            }
+            #[cfg(feature = "std")]
+            _ => T::from_f64(self.inverse_f64(x.to_f64())),
+        }
+    }
+
+    #[cfg(feature = "std")]
+    #[inline]
+    fn inverse_f64(&self, x: f64) -> f64 {
+        match *self {
+            RangeTransform::Rational { .. } => self.inverse(x),
+            RangeTransform::Exponential { center, scale } => {
+                let t = -(-(x - center).abs() / scale).exp_m1();
+                if x < center {
+                    -t
+                } else {
+                    t
+                }
+            }
+            RangeTransform::Tan { center, scale } => {
+                ((x - center) / scale).atan() / core::f64::consts::FRAC_PI_2
+            }
+        }
+    }
+}
+
+impl Default for RangeTransform {
+    /// `x = t / (1 - |t|)`
+    #[inline]
+    fn default() -> Self {
+        RangeTransform::Rational {
+            center: 0.0,
+            scale: 1.0,
         }
     }
 }
 
 /// The function that is to be integrated
-pub trait Integrand {
+pub trait Integrand<T: Float = f64> {
     /// apply function to explanatory variable `x`
-    fn apply(&mut self, x: f64) -> f64;
+    fn apply(&mut self, x: T) -> T;
 
     /// apply function to each of the elements of `s`.
     #[inline]
-    fn apply_to_slice(&mut self, s: &mut [f64]) {
+    fn apply_to_slice(&mut self, s: &mut [T]) {
         s.iter_mut().for_each(|x| *x = self.apply(*x));
     }
 }
 
-impl<F: FnMut(f64) -> f64> Integrand for F {
+impl<T: Float, F: FnMut(T) -> T> Integrand<T> for F {
+    #[inline]
+    fn apply(&mut self, x: T) -> T {
+        (*self)(x)
+    }
+}
+
+/// The integrand which evaluates the function at many points at once
+///
+/// The wrapped closure receives the explanatory variables, and overwrites
+/// them with the function values. The algorithms pass all the points of a
+/// quadrature rule in a single call, so that the integrand can vectorize the
+/// evaluation internally.
+///
+/// # Examples
+///
+/// ```
+/// use gkquad::single::{integral, BatchIntegrand};
+///
+/// let f = BatchIntegrand::new(|s: &mut [f64]| s.iter_mut().for_each(|x| *x = x.sqrt()));
+/// let result = integral(f, 0.0..1.0).unwrap();
+/// assert!((result.estimate - 2.0 / 3.0).abs() < 1e-10);
+/// ```
+#[derive(Clone, Debug, Default, PartialEq, Eq)]
+pub struct BatchIntegrand<F> {
+    inner: F,
+}
+
+impl<F> BatchIntegrand<F> {
+    #[inline]
+    pub fn new(inner: F) -> Self {
+        Self { inner }
+    }
+
+    /// Extract the inner closure
+    #[inline]
+    pub fn into_inner(self) -> F {
+        self.inner
+    }
+}
+
+impl<T: Float, F: FnMut(&mut [T])> Integrand<T> for BatchIntegrand<F> {
+    #[inline]
+    fn apply(&mut self, x: T) -> T {
+        let mut s = [x];
+        (self.inner)(&mut s);
+        s[0]
+    }
+
+    #[inline]
+    fn apply_to_slice(&mut self, s: &mut [T]) {
+        (self.inner)(s)
+    }
+}
+
+/// The function that is to be integrated, which may fail to be evaluated
+///
+/// The algorithms stop at the first error, and the integrand is never called
+/// again after that.
+pub trait TryIntegrand<T: Float = f64> {
+    /// error type returned by the integrand
+    type Error;
+
+    /// apply function to explanatory variable `x`
+    fn try_apply(&mut self, x: T) -> Result<T, Self::Error>;
+
+    /// apply function to each of the elements of `s`.
+    ///
+    /// If the function fails, returns the index of the element and the error.
+    /// The elements after it need not be evaluated.
+    #[inline]
+    fn try_apply_to_slice(&mut self, s: &mut [T]) -> Result<(), (usize, Self::Error)> {
+        for (i, x) in s.iter_mut().enumerate() {
+            *x = self.try_apply(*x).map_err(|e| (i, e))?;
+        }
+        Ok(())
+    }
+}
+
+impl<T: Float, E, F: FnMut(T) -> Result<T, E>> TryIntegrand<T> for F {
+    type Error = E;
+
+    #[inline]
+    fn try_apply(&mut self, x: T) -> Result<T, E> {
+        (*self)(x)
+    }
+}
+
+/// The vector-valued function that is to be integrated
+///
+/// The components share the explanatory variable, so that the expensive part
+/// of the evaluation can be reused among them.
+pub trait IntegrandVec {
+    /// apply function to explanatory variable `x`, and store the value of each
+    /// component into `out`
+    fn apply(&mut self, x: f64, out: &mut [f64]);
+
+    /// apply function to each of the elements of `xs`, and store the values at
+    /// `xs[j]` into `out[j * dim..(j + 1) * dim]`, where `dim` is
+    /// `out.len() / xs.len()`.
+    #[inline]
+    fn apply_to_slice(&mut self, xs: &[f64], out: &mut [f64]) {
+        let dim = out.len() / xs.len().max(1);
+        for (j, &x) in xs.iter().enumerate() {
+            self.apply(x, &mut out[j * dim..(j + 1) * dim]);
+        }
+    }
+}
+
+impl<F: FnMut(f64, &mut [f64])> IntegrandVec for F {
+    #[inline]
+    fn apply(&mut self, x: f64, out: &mut [f64]) {
+        (*self)(x, out)
+    }
+}
+
+/// The complex-valued function that is to be integrated
+///
+/// The function returns the real and imaginary parts as `(re, im)`.
+pub trait IntegrandComplex {
+    /// apply function to explanatory variable `x`
+    fn apply(&mut self, x: f64) -> (f64, f64);
+}
+
+impl<F: FnMut(f64) -> (f64, f64)> IntegrandComplex for F {
     #[inline]
-    fn apply(&mut self, x: f64) -> f64 {
+    fn apply(&mut self, x: f64) -> (f64, f64) {
         (*self)(x)
     }
 }
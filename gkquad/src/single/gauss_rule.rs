@@ -0,0 +1,328 @@
+use alloc::vec;
+use alloc::vec::Vec;
+
+use crate::common::Tolerance;
+use crate::single::algorithm::{Algorithm, QAGS};
+use crate::single::common::{Integrand, IntegrationConfig, Range};
+use crate::single::orthopoly::{
+    golub_welsch, hermite_recurrence, laguerre_recurrence, legendre_recurrence, modified_chebyshev,
+};
+
+#[cfg(feature = "std")]
+use crate::single::orthopoly::jacobi_recurrence;
+
+/// Gaussian quadrature rule for the weight function
+///
+/// `∫ w(x) f(x) dx` is approximated by `Σ w_i f(x_i)`, where the weight
+/// function `w(x)` is determined by the constructor. The integrand `f` must
+/// not contain the weight function.
+///
+/// The nodes whose weight underflows to zero are omitted, so the number of
+/// nodes may be smaller than the order for the large order.
+///
+/// # Examples
+///
+/// ```
+/// use gkquad::single::GaussRule;
+///
+/// // ∫ e^-x cos(x) dx (0 < x < ∞)
+/// let rule = GaussRule::laguerre(20);
+/// let result = rule.integrate(&mut |x: f64| x.cos());
+/// assert!((result - 0.5).abs() < 1e-6);
+/// ```
+#[derive(Clone, Debug, PartialEq)]
+pub struct GaussRule {
+    nodes: Vec<f64>,
+    weights: Vec<f64>,
+}
+
+impl GaussRule {
+    /// `n`-point Gauss-Laguerre rule for the weight function `e^-x` over
+    /// [0, ∞)
+    ///
+    /// # Panics
+    ///
+    /// Panics if `n` is zero.
+    pub fn laguerre(n: usize) -> Self {
+        assert!(n > 0, "order of the rule must be positive");
+        let (a, b) = laguerre_recurrence(n);
+        Self::from_recurrence(&a, &b).expect("failed to compute Gauss-Laguerre rule")
+    }
+
+    /// `n`-point Gauss-Hermite rule for the weight function `e^-x²` over
+    /// (-∞, ∞)
+    ///
+    /// # Panics
+    ///
+    /// Panics if `n` is zero.
+    pub fn hermite(n: usize) -> Self {
+        assert!(n > 0, "order of the rule must be positive");
+        let (a, b) = hermite_recurrence(n);
+        Self::from_recurrence(&a, &b).expect("failed to compute Gauss-Hermite rule")
+    }
+
+    /// `n`-point Gauss-Jacobi rule for the weight function `(1-x)^α (1+x)^β`
+    /// over [-1, 1]
+    ///
+    /// Use [`jacobi_on`](#method.jacobi_on) to integrate over the other finite
+    /// range.
+    ///
+    /// # Panics
+    ///
+    /// Panics if `n` is zero, or either `alpha` or `beta` is not greater than
+    /// -1.
+    #[cfg(feature = "std")]
+    #[cfg_attr(docsrs, doc(cfg(feature = "std")))]
+    pub fn jacobi(n: usize, alpha: f64, beta: f64) -> Self {
+        assert!(n > 0, "order of the rule must be positive");
+        assert!(
+            alpha > -1.0 && beta > -1.0,
+            "exponents of the weight function must be greater than -1"
+        );
+        let (a, b) = jacobi_recurrence(n, alpha, beta);
+        Self::from_recurrence(&a, &b).expect("failed to compute Gauss-Jacobi rule")
+    }
+
+    /// `n`-point Gauss-Jacobi rule for the weight function `(b-x)^α (x-a)^β`
+    /// over the finite range [a, b]
+    ///
+    /// The nodes of [`jacobi`](#method.jacobi) are mapped by
+    /// `x = (a+b)/2 + (b-a)/2 * t`, and the weights are multiplied by
+    /// `((b-a)/2)^(α+β+1)`. If `range.begin > range.end`, the signs of the
+    /// weights are inverted.
+    ///
+    /// # Panics
+    ///
+    /// Panics if `n` is zero, either `alpha` or `beta` is not greater than
+    /// -1, or the range is not finite.
+    #[cfg(feature = "std")]
+    #[cfg_attr(docsrs, doc(cfg(feature = "std")))]
+    pub fn jacobi_on(n: usize, alpha: f64, beta: f64, range: &Range) -> Self {
+        assert!(
+            range.begin.is_finite() && range.end.is_finite(),
+            "range must be finite"
+        );
+
+        let sign = if range.begin <= range.end { 1.0 } else { -1.0 };
+        let center = 0.5 * (range.begin + range.end);
+        let half = 0.5 * (range.end - range.begin).abs();
+        let factor = sign * half.powf(alpha + beta + 1.);
+
+        let mut rule = Self::jacobi(n, alpha, beta);
+        rule.nodes.iter_mut().for_each(|x| *x = center + half * *x);
+        rule.weights.iter_mut().for_each(|w| *w *= factor);
+        rule
+    }
+
+    /// Gauss rule for the weight function whose moments are given
+    ///
+    /// `moments[k]` must be `∫ x^k w(x) dx` over `range`, and the
+    /// `moments.len() / 2`-point rule is computed from them by modified
+    /// Chebyshev algorithm. If the range is finite, the moments are converted
+    /// into those of the variable scaled to [-1, 1] before the computation.
+    ///
+    /// Since the rule is very sensitive to the errors of the moments, only
+    /// the low order rule can be computed in practice. Use
+    /// [`from_weight`](#method.from_weight) if the weight function can be
+    /// evaluated.
+    ///
+    /// Returns `None` if less than 2 moments are given, any of the moments is
+    /// not finite, or the moments are not consistent with a positive weight
+    /// function over the range.
+    pub fn from_moments(moments: &[f64], range: &Range) -> Option<Self> {
+        let n = moments.len() / 2;
+        if n == 0 {
+            return None;
+        }
+
+        let (center, half, sign) = scaling(range)?;
+
+        // moments of t = (x - center) / half
+        let mut scaled = vec![0.0; 2 * n];
+        let mut denom = half;
+        for (k, m) in scaled.iter_mut().enumerate() {
+            // C(k, j) (-center)^(k-j)
+            let mut coef = 1.0;
+            let mut sum = 0.0;
+            for j in (0..=k).rev() {
+                sum += coef * moments[j];
+                coef *= -center * j as f64 / (k + 1 - j) as f64;
+            }
+            *m = sign * sum / denom;
+            denom *= half;
+        }
+
+        let zeros = vec![0.0; 2 * n];
+        let (a, b) = modified_chebyshev(&scaled, &zeros, &zeros)?;
+        Self::from_recurrence(&a, &b)?
+            .scale(center, sign * half)
+            .check(n, range)
+    }
+
+    /// `n`-point Gauss rule for the weight function `w` over the finite range
+    ///
+    /// The modified moments of `w` with respect to the Legendre polynomials
+    /// are calculated by `QAGS` algorithm with `config`, and the rule is
+    /// computed from them by modified Chebyshev algorithm.
+    ///
+    /// Returns `None` if the range is not finite, any of the moments cannot
+    /// be calculated, or `w` is not a positive weight function.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use gkquad::single::{GaussRule, IntegrationConfig, Range};
+    ///
+    /// // ∫ √x cos(x) dx (0 < x < 1)
+    /// let range = Range::new(0.0, 1.0).unwrap();
+    /// let config = IntegrationConfig::default();
+    /// let rule = GaussRule::from_weight(10, |x: f64| x.sqrt(), &range, &config).unwrap();
+    /// let result = rule.integrate(&mut |x: f64| x.cos());
+    /// ```
+    ///
+    /// # Panics
+    ///
+    /// Panics if `n` is zero.
+    pub fn from_weight<W: Integrand>(
+        n: usize,
+        mut w: W,
+        range: &Range,
+        config: &IntegrationConfig,
+    ) -> Option<Self> {
+        assert!(n > 0, "order of the rule must be positive");
+        if range.begin.is_infinite() || range.end.is_infinite() {
+            return None;
+        }
+
+        let (center, half, sign) = scaling(range)?;
+        let range = Range::new(center - half, center + half)?;
+        let (a, b) = legendre_recurrence(2 * n);
+        let mut qags = QAGS::new();
+        let mut config = config.clone();
+
+        let mut moments = Vec::with_capacity(2 * n);
+        for k in 0..2 * n {
+            // w(x) p_k(t), where p_k is the monic Legendre polynomial
+            let mut f = |x: f64| {
+                let t = (x - center) / half;
+                let (mut p0, mut p1) = (0.0, 1.0);
+                for j in 0..k {
+                    let p2 = (t - a[j]) * p1 - b[j] * p0;
+                    p0 = p1;
+                    p1 = p2;
+                }
+                w.apply(x) * p1
+            };
+
+            let result = qags.integrate(&mut f, &range, &config).ok()?;
+            if k == 0 {
+                // the higher moments are small, so relative tolerance is useless
+                let abs = config.tolerance.to_abs(result.estimate.abs());
+                config.tolerance = Tolerance::Absolute(abs);
+            }
+            moments.push(result.estimate / half);
+        }
+
+        let (alpha, beta) = modified_chebyshev(&moments, &a, &b)?;
+        Self::from_recurrence(&alpha, &beta)?
+            .scale(center, sign * half)
+            .check(n, &range)
+    }
+
+    fn from_recurrence(a: &[f64], b: &[f64]) -> Option<Self> {
+        let (nodes, weights) = golub_welsch(a, b)?
+            .into_iter()
+            .filter(|&(_, w)| w > 0.0)
+            .unzip();
+        Some(Self { nodes, weights })
+    }
+
+    /// Returns `None` unless the rule has `n` nodes inside the range, which
+    /// is the property of the Gauss rule for a positive weight function.
+    fn check(self, n: usize, range: &Range) -> Option<Self> {
+        let (lo, hi) = if range.begin <= range.end {
+            (range.begin, range.end)
+        } else {
+            (range.end, range.begin)
+        };
+
+        if self.len() == n && self.nodes.iter().all(|&x| lo <= x && x <= hi) {
+            Some(self)
+        } else {
+            None
+        }
+    }
+
+    /// map the nodes from [-1, 1] into the range centered at `center`
+    ///
+    /// The weights are multiplied by `half`, so negative `half` inverts the
+    /// sign of the weights.
+    fn scale(mut self, center: f64, half: f64) -> Self {
+        self.nodes
+            .iter_mut()
+            .for_each(|x| *x = center + half.abs() * *x);
+        self.weights.iter_mut().for_each(|w| *w *= half);
+        self
+    }
+
+    /// nodes of the rule in ascending order
+    #[inline]
+    pub fn nodes(&self) -> &[f64] {
+        &self.nodes
+    }
+
+    /// weights corresponding to the nodes
+    #[inline]
+    pub fn weights(&self) -> &[f64] {
+        &self.weights
+    }
+
+    /// number of the nodes
+    #[inline]
+    pub fn len(&self) -> usize {
+        self.nodes.len()
+    }
+
+    /// Returns `true` if the rule has no nodes
+    #[inline]
+    pub fn is_empty(&self) -> bool {
+        self.nodes.is_empty()
+    }
+
+    /// Apply the rule to the integrand
+    pub fn integrate<F: Integrand + ?Sized>(&self, f: &mut F) -> f64 {
+        self.integrate_with(f, |x| x)
+    }
+
+    /// Apply the rule to `f(transform(x))`
+    pub(crate) fn integrate_with<F, T>(&self, f: &mut F, transform: T) -> f64
+    where
+        F: Integrand + ?Sized,
+        T: Fn(f64) -> f64,
+    {
+        let mut values: Vec<f64> = self.nodes.iter().map(|&x| transform(x)).collect();
+        f.apply_to_slice(&mut values);
+        values
+            .iter()
+            .zip(self.weights.iter())
+            .map(|(y, w)| y * w)
+            .sum()
+    }
+}
+
+/// center, half width and orientation of the range
+///
+/// `(0, 1)` is used as the center and half width for the infinite range.
+/// Returns `None` if the range is empty.
+fn scaling(range: &Range) -> Option<(f64, f64, f64)> {
+    let sign = if range.begin <= range.end { 1.0 } else { -1.0 };
+    if range.begin.is_finite() && range.end.is_finite() {
+        let half = 0.5 * (range.end - range.begin).abs();
+        if half == 0.0 {
+            return None;
+        }
+        Some((0.5 * (range.begin + range.end), half, sign))
+    } else {
+        Some((0.0, 1.0, sign))
+    }
+}
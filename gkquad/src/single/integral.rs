@@ -1,7 +1,11 @@
 use super::algorithm::*;
-use super::common::{Integrand, IntegrationConfig, Range};
+use super::common::{
+    Integrand, IntegrandComplex, IntegrandVec, IntegrationConfig, Range, TryIntegrand,
+};
 
-use crate::common::IntegrationResult;
+use crate::common::{
+    ComplexIntegrationResult, IntegrationResult, TryIntegrationResult, VecIntegrationResult,
+};
 
 /// Performs integration using `QAGS` algorithm,
 /// which achieves great performance for many kinds of functions.
@@ -29,3 +33,65 @@ pub fn integral_with_config<F: Integrand, I: Into<Range>>(
 ) -> IntegrationResult {
     AUTO::new().integrate(&mut f, &range.into(), &config)
 }
+
+/// Performs integration of the fallible function using `QAGS` algorithm.
+///
+/// The integration stops at the first error returned by the integrand.
+///
+/// # Examples
+///
+/// ```
+/// use gkquad::single::try_integral;
+///
+/// let f = |x: f64| if x > 0.5 { Err(x) } else { Ok(x.sqrt()) };
+/// let err = try_integral(f, 0.0..1.0).unwrap_err();
+/// assert!(err.x > 0.5);
+/// ```
+#[inline]
+pub fn try_integral<F: TryIntegrand, I: Into<Range>>(
+    mut f: F,
+    range: I,
+) -> TryIntegrationResult<F::Error> {
+    QAGS::new().try_integrate(&mut f, &range.into(), &IntegrationConfig::default())
+}
+
+/// Performs integration of the vector-valued function with `dim` components
+/// using `QAGSVec` algorithm.
+///
+/// # Examples
+///
+/// ```
+/// use gkquad::single::integral_vec;
+///
+/// let f = |x: f64, out: &mut [f64]| {
+///     out[0] = x.sin();
+///     out[1] = x.cos();
+/// };
+/// let result = integral_vec(f, 2, 0.0..1.0).unwrap();
+/// ```
+#[inline]
+pub fn integral_vec<F: IntegrandVec, I: Into<Range>>(
+    mut f: F,
+    dim: usize,
+    range: I,
+) -> VecIntegrationResult {
+    QAGSVec::new().integrate(&mut f, dim, &range.into(), &IntegrationConfig::default())
+}
+
+/// Performs integration of the complex-valued function using `QAGS` algorithm.
+///
+/// # Examples
+///
+/// ```
+/// use gkquad::single::integral_complex;
+///
+/// // ∫ exp(ix) dx over [0, 1]
+/// let result = integral_complex(|x: f64| (x.cos(), x.sin()), 0.0..1.0).unwrap();
+/// ```
+#[inline]
+pub fn integral_complex<F: IntegrandComplex, I: Into<Range>>(
+    mut f: F,
+    range: I,
+) -> ComplexIntegrationResult {
+    QAGS::new().integrate_complex(&mut f, &range.into(), &IntegrationConfig::default())
+}
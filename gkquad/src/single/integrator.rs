@@ -1,7 +1,8 @@
 use super::algorithm::*;
-use super::common::{Integrand, IntegrationConfig, Points, Range};
+use super::common::{Integrand, IntegrationConfig, Points, Range, RangeTransform};
 
 use crate::common::{IntegrationResult, Tolerance};
+use crate::float::Float;
 
 /// Integration Executor
 ///
@@ -27,11 +28,24 @@ use crate::common::{IntegrationResult, Tolerance};
 ///     m.set(m.get() + 1);
 /// }
 /// ```
+///
+/// The floating-point type other than `f64` can be used with the algorithms
+/// which support it.
+///
+/// ```
+/// use gkquad::single::algorithm::QAGS;
+/// use gkquad::single::Integrator;
+///
+/// let result = Integrator::with_algorithm(|x: f32| x.sqrt(), QAGS::new())
+///     .run(0.0..1.0)
+///     .unwrap();
+/// assert!((result.estimate - 2.0 / 3.0).abs() < 1e-6);
+/// ```
 #[derive(Clone, Debug, Default, PartialEq)]
-pub struct Integrator<F: Integrand, A: Algorithm<F>> {
+pub struct Integrator<F: Integrand<T>, A: Algorithm<F, T>, T: Float = f64> {
     integrand: F,
     algorithm: A,
-    config: IntegrationConfig,
+    config: IntegrationConfig<T>,
 }
 
 impl<F: Integrand> Integrator<F, AUTO> {
@@ -45,9 +59,9 @@ impl<F: Integrand> Integrator<F, AUTO> {
     }
 }
 
-impl<F: Integrand, A: Algorithm<F>> Integrator<F, A> {
+impl<T: Float, F: Integrand<T>, A: Algorithm<F, T>> Integrator<F, A, T> {
     #[inline]
-    pub fn with_algorithm(integrand: F, algorithm: A) -> Integrator<F, A> {
+    pub fn with_algorithm(integrand: F, algorithm: A) -> Integrator<F, A, T> {
         Self {
             integrand,
             algorithm,
@@ -57,7 +71,7 @@ impl<F: Integrand, A: Algorithm<F>> Integrator<F, A> {
 
     /// Set algorithm
     #[inline]
-    pub fn algorithm<B: Algorithm<F>>(self, algorithm: B) -> Integrator<F, B> {
+    pub fn algorithm<B: Algorithm<F, T>>(self, algorithm: B) -> Integrator<F, B, T> {
         Integrator {
             integrand: self.integrand,
             algorithm,
@@ -67,7 +81,7 @@ impl<F: Integrand, A: Algorithm<F>> Integrator<F, A> {
 
     /// Set tolerance
     #[inline]
-    pub fn tolerance(mut self, t: Tolerance) -> Self {
+    pub fn tolerance(mut self, t: Tolerance<T>) -> Self {
         assert!(!t.contains_nan(), "Tolerance must not contain NAN.");
 
         self.config.tolerance = t;
@@ -82,7 +96,7 @@ impl<F: Integrand, A: Algorithm<F>> Integrator<F, A> {
     }
 
     /// Set singular points
-    pub fn points(mut self, pts: &[f64]) -> Self {
+    pub fn points(mut self, pts: &[T]) -> Self {
         assert!(
             pts.iter().all(|&x| !x.is_nan()),
             "cannot include NAN value in singular points: {:?}",
@@ -94,20 +108,44 @@ impl<F: Integrand, A: Algorithm<F>> Integrator<F, A> {
         self
     }
 
+    /// Declare that the integrand is periodic and the range is a full period
+    #[inline]
+    pub fn periodic(mut self, periodic: bool) -> Self {
+        self.config.periodic = periodic;
+        self
+    }
+
+    /// Set the transformation applied to the infinite range
+    ///
+    /// # Panics
+    ///
+    /// Panics if the center is not finite, or the scale is not positive and
+    /// finite.
+    #[inline]
+    pub fn range_transform(mut self, transform: RangeTransform) -> Self {
+        assert!(
+            transform.is_valid(),
+            "invalid range transformation: {:?}",
+            transform
+        );
+        self.config.range_transform = transform;
+        self
+    }
+
     #[inline]
     pub fn get_algorithm(&self) -> &A {
         &self.algorithm
     }
 
     #[inline]
-    pub fn run<T: Into<Range>>(&mut self, range: T) -> IntegrationResult {
+    pub fn run<R: Into<Range<T>>>(&mut self, range: R) -> IntegrationResult<T> {
         self.algorithm
             .integrate(&mut self.integrand, &range.into(), &self.config)
     }
 }
 
 // Integrator can safely implement Eq because Nan value of tolerance is always checked.
-impl<F: Integrand + Eq, A: Algorithm<F> + Eq> Eq for Integrator<F, A> {}
+impl<T: Float, F: Integrand<T> + Eq, A: Algorithm<F, T> + Eq> Eq for Integrator<F, A, T> {}
 
 impl<F: Integrand> From<F> for Integrator<F, AUTO> {
     #[inline]
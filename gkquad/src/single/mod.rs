@@ -33,16 +33,23 @@
 
 pub mod algorithm;
 mod common;
+mod gauss_rule;
 mod integral;
 mod integrator;
+mod orthopoly;
+#[cfg(feature = "std")]
+mod qcheb;
 mod qelg;
 mod qk;
 mod util;
 mod workspace;
 
 pub use common::*;
+pub use gauss_rule::*;
 pub use integral::*;
 pub use integrator::*;
 pub use qk::*;
 #[doc(hidden)]
+pub use util::TryIntegrandWrapper;
+#[doc(hidden)]
 pub use workspace::*;
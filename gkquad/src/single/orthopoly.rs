@@ -0,0 +1,369 @@
+//! Gaussian quadrature rules computed from the recurrence coefficients of
+//! orthogonal polynomials
+
+use alloc::vec;
+use alloc::vec::Vec;
+
+#[cfg(not(feature = "std"))]
+use crate::float::Float;
+
+/// recurrence coefficients `(a, b)` of the monic Legendre polynomials
+///
+/// `p_{k+1}(x) = (x - a_k) p_k(x) - b_k p_{k-1}(x)`, and `b_0` is the integral
+/// of the weight function over [-1, 1].
+pub fn legendre_recurrence(n: usize) -> (Vec<f64>, Vec<f64>) {
+    let a = vec![0.0; n];
+    let b = (0..n)
+        .map(|k| {
+            if k == 0 {
+                2.0
+            } else {
+                let k2 = (k * k) as f64;
+                k2 / (4. * k2 - 1.)
+            }
+        })
+        .collect();
+    (a, b)
+}
+
+/// recurrence coefficients of the monic Laguerre polynomials (weight `e^-x`
+/// over [0, ∞))
+pub fn laguerre_recurrence(n: usize) -> (Vec<f64>, Vec<f64>) {
+    let a = (0..n).map(|k| (2 * k + 1) as f64).collect();
+    let b = (0..n)
+        .map(|k| if k == 0 { 1.0 } else { (k * k) as f64 })
+        .collect();
+    (a, b)
+}
+
+/// recurrence coefficients of the monic Hermite polynomials (weight `e^-x²`
+/// over (-∞, ∞))
+pub fn hermite_recurrence(n: usize) -> (Vec<f64>, Vec<f64>) {
+    let a = vec![0.0; n];
+    let b = (0..n)
+        .map(|k| {
+            if k == 0 {
+                core::f64::consts::PI.sqrt()
+            } else {
+                0.5 * k as f64
+            }
+        })
+        .collect();
+    (a, b)
+}
+
+/// recurrence coefficients of the monic Jacobi polynomials (weight
+/// `(1-x)^α (1+x)^β` over [-1, 1])
+#[cfg(feature = "std")]
+pub fn jacobi_recurrence(n: usize, alpha: f64, beta: f64) -> (Vec<f64>, Vec<f64>) {
+    let ab = alpha + beta;
+    let a = (0..n)
+        .map(|k| {
+            if k == 0 {
+                (beta - alpha) / (ab + 2.)
+            } else {
+                let k2 = 2. * k as f64 + ab;
+                (beta * beta - alpha * alpha) / (k2 * (k2 + 2.))
+            }
+        })
+        .collect();
+    let b = (0..n)
+        .map(|k| {
+            if k == 0 {
+                let ln_b0 = (ab + 1.) * core::f64::consts::LN_2
+                    + ln_gamma(alpha + 1.)
+                    + ln_gamma(beta + 1.)
+                    - ln_gamma(ab + 2.);
+                ln_b0.exp()
+            } else if k == 1 {
+                4. * (1. + alpha) * (1. + beta) / ((2. + ab) * (2. + ab) * (3. + ab))
+            } else {
+                let kf = k as f64;
+                let k2 = 2. * kf + ab;
+                4. * kf * (kf + alpha) * (kf + beta) * (kf + ab) / (k2 * k2 * (k2 + 1.) * (k2 - 1.))
+            }
+        })
+        .collect();
+    (a, b)
+}
+
+/// logarithm of the gamma function for the positive argument by Lanczos
+/// approximation
+#[cfg(feature = "std")]
+fn ln_gamma(mut x: f64) -> f64 {
+    const G: f64 = 7.;
+    const P: [f64; 9] = [
+        0.99999999999980993,
+        676.5203681218851,
+        -1259.1392167224028,
+        771.32342877765313,
+        -176.61502916214059,
+        12.507343278686905,
+        -0.13857109526572012,
+        9.9843695780195716e-6,
+        1.5056327351493116e-7,
+    ];
+
+    debug_assert!(x > 0.0);
+
+    // the approximation is accurate for x >= 1/2
+    let mut shift = 0.0;
+    while x < 1.5 {
+        shift -= x.ln();
+        x += 1.;
+    }
+
+    let x = x - 1.;
+    let mut sum = P[0];
+    for (i, &p) in P.iter().enumerate().skip(1) {
+        sum += p / (x + i as f64);
+    }
+
+    let t = x + G + 0.5;
+    shift + 0.5 * (2. * core::f64::consts::PI).ln() + (x + 0.5) * t.ln() - t + sum.ln()
+}
+
+/// Calculate the recurrence coefficients of the polynomials orthogonal with
+/// respect to the weight function from its modified moments by modified
+/// Chebyshev algorithm.
+///
+/// `moments[k]` is the integral of `w(x) p_k(x)`, where `p_k` are the monic
+/// polynomials defined by the recurrence coefficients `a` and `b`. Ordinary
+/// moments correspond to the coefficients being all zero. `n` coefficients are
+/// computed from `2n` moments, and `a` and `b` must contain at least `2n-1`
+/// coefficients.
+///
+/// Returns `None` if any of the moments is not finite, or the moments are
+/// not consistent with a positive weight function.
+///
+/// # References
+///
+/// * W. Gautschi, Orthogonal Polynomials: Computation and Approximation,
+///   Oxford University Press (2004), Section 2.1.7
+pub fn modified_chebyshev(moments: &[f64], a: &[f64], b: &[f64]) -> Option<(Vec<f64>, Vec<f64>)> {
+    let n = moments.len() / 2;
+    debug_assert!(n >= 1);
+    debug_assert!(a.len() + 1 >= 2 * n && b.len() + 1 >= 2 * n);
+
+    if moments[..2 * n].iter().any(|m| !m.is_finite()) || moments[0] <= 0.0 {
+        return None;
+    }
+
+    let mut alpha = Vec::with_capacity(n);
+    let mut beta = Vec::with_capacity(n);
+    alpha.push(a[0] + moments[1] / moments[0]);
+    beta.push(moments[0]);
+    if !alpha[0].is_finite() {
+        return None;
+    }
+
+    // sigma_{k-1, l} and sigma_{k, l}
+    let mut prev = vec![0.0; 2 * n];
+    let mut cur = moments[..2 * n].to_vec();
+
+    for k in 1..n {
+        let mut next = vec![0.0; 2 * n];
+        for l in k..2 * n - k {
+            next[l] = cur[l + 1] - (alpha[k - 1] - a[l]) * cur[l] - beta[k - 1] * prev[l]
+                + b[l] * cur[l - 1];
+        }
+
+        if !next[k].is_finite() || next[k] <= 0.0 {
+            return None;
+        }
+
+        alpha.push(a[k] + next[k + 1] / next[k] - cur[k] / cur[k - 1]);
+        beta.push(next[k] / cur[k - 1]);
+        if !alpha[k].is_finite() || !beta[k].is_finite() {
+            return None;
+        }
+
+        prev = cur;
+        cur = next;
+    }
+
+    Some((alpha, beta))
+}
+
+/// Calculate the recurrence coefficients of the Jacobi-Kronrod matrix, whose
+/// eigenvalues are the nodes of `2n+1`-point Gauss-Kronrod rule.
+///
+/// `a` and `b` must contain at least `⌊3n/2⌋+1` and `⌈3n/2⌉+1` coefficients
+/// of the original polynomials, respectively.
+///
+/// # References
+///
+/// * D. P. Laurie, Calculation of Gauss-Kronrod quadrature rules, Math. Comp.
+///   66 (1997), 1133-1145.
+/// * W. Gautschi, `r_kronrod.m` in OPQ suite
+pub fn kronrod_recurrence(n: usize, a: &[f64], b: &[f64]) -> (Vec<f64>, Vec<f64>) {
+    // ⌊3n/2⌋ and ⌈3n/2⌉
+    let (na, nb) = (3 * n / 2, 3 * n - 3 * n / 2);
+
+    debug_assert!(n >= 1);
+    debug_assert!(a.len() > na && b.len() > nb);
+
+    let mut a: Vec<f64> = (0..=2 * n)
+        .map(|k| if k <= na { a[k] } else { 0.0 })
+        .collect();
+    let mut b: Vec<f64> = (0..=2 * n)
+        .map(|k| if k <= nb { b[k] } else { 0.0 })
+        .collect();
+
+    let mut s = vec![0.0; n / 2 + 2];
+    let mut t = vec![0.0; n / 2 + 2];
+    t[1] = b[n + 1];
+
+    for m in 0..n.saturating_sub(1) {
+        // the right hand side must be evaluated with the old values of `s`
+        let mut acc = 0.0;
+        let u = s.clone();
+        for k in (0..=m.div_ceil(2)).rev() {
+            let l = m - k;
+            acc += (a[k + n + 1] - a[l]) * t[k + 1] + b[k + n + 1] * u[k] - b[l] * u[k + 1];
+            s[k + 1] = acc;
+        }
+        core::mem::swap(&mut s, &mut t);
+    }
+
+    for j in (0..=n / 2).rev() {
+        s[j + 1] = s[j];
+    }
+
+    for m in n - 1..2 * n - 2 {
+        let mut acc = 0.0;
+        let mut j = 0;
+        let u = s.clone();
+        for k in m + 1 - n..=(m - 1) / 2 {
+            let l = m - k;
+            j = n - 1 - l;
+            acc += -(a[k + n + 1] - a[l]) * t[j + 1] - b[k + n + 1] * u[j + 1] + b[l] * u[j + 2];
+            s[j + 1] = acc;
+        }
+
+        let k = m.div_ceil(2);
+        if m % 2 == 0 {
+            a[k + n + 1] = a[k] + (s[j + 1] - b[k + n + 1] * s[j + 2]) / t[j + 2];
+        } else {
+            b[k + n + 1] = s[j + 1] / s[j + 2];
+        }
+        core::mem::swap(&mut s, &mut t);
+    }
+
+    a[2 * n] = a[n - 1] - b[2 * n] * s[1] / t[1];
+    (a, b)
+}
+
+/// Calculate the nodes and weights of the Gaussian quadrature rule from the
+/// recurrence coefficients by Golub-Welsch algorithm.
+///
+/// The nodes are the eigenvalues of the symmetric tridiagonal (Jacobi) matrix
+/// whose diagonal is `a` and off-diagonal is `√b[1..]`, and the weights are
+/// `b[0]` times the squared first components of the normalized eigenvectors.
+/// The returned pairs `(node, weight)` are sorted in ascending order of nodes.
+///
+/// Returns `None` if the eigenvalues do not converge, or if any node or weight
+/// is not finite.
+pub fn golub_welsch(a: &[f64], b: &[f64]) -> Option<Vec<(f64, f64)>> {
+    const MAX_ITERS: usize = 60;
+
+    let n = a.len();
+    debug_assert!(b.len() >= n);
+
+    let mut d = a.to_vec();
+    let mut e: Vec<f64> = b[1..n].iter().map(|x| x.sqrt()).collect();
+    e.push(0.0);
+
+    // first components of the eigenvectors
+    let mut z = vec![0.0; n];
+    z[0] = 1.0;
+
+    // implicit QL method
+    for l in 0..n {
+        let mut iter = 0;
+        loop {
+            let mut m = l;
+            while m + 1 < n {
+                let dd = d[m].abs() + d[m + 1].abs();
+                if e[m].abs() <= core::f64::EPSILON * dd {
+                    break;
+                }
+                m += 1;
+            }
+
+            if m == l {
+                break;
+            }
+
+            iter += 1;
+            if iter > MAX_ITERS {
+                return None;
+            }
+
+            let mut g = (d[l + 1] - d[l]) / (2. * e[l]);
+            let mut r = pythag(g, 1.0);
+            g = d[m] - d[l] + e[l] / (g + if g >= 0.0 { r } else { -r });
+
+            let (mut s, mut c, mut p) = (1.0, 1.0, 0.0);
+            let mut underflow = false;
+
+            for i in (l..m).rev() {
+                let f = s * e[i];
+                let bb = c * e[i];
+                r = pythag(f, g);
+                e[i + 1] = r;
+
+                if r == 0.0 {
+                    d[i + 1] -= p;
+                    e[m] = 0.0;
+                    underflow = true;
+                    break;
+                }
+
+                s = f / r;
+                c = g / r;
+                g = d[i + 1] - p;
+                r = (d[i] - g) * s + 2. * c * bb;
+                p = s * r;
+                d[i + 1] = g + p;
+                g = c * r - bb;
+
+                let f = z[i + 1];
+                z[i + 1] = s * z[i] + c * f;
+                z[i] = c * z[i] - s * f;
+            }
+
+            if underflow {
+                continue;
+            }
+
+            d[l] -= p;
+            e[l] = g;
+            e[m] = 0.0;
+        }
+    }
+
+    let mut nodes: Vec<(f64, f64)> = d
+        .into_iter()
+        .zip(z)
+        .map(|(x, v)| (x, b[0] * v * v))
+        .collect();
+    if nodes.iter().any(|&(x, w)| !x.is_finite() || !w.is_finite()) {
+        return None;
+    }
+    nodes.sort_by(|x, y| x.0.partial_cmp(&y.0).unwrap());
+    Some(nodes)
+}
+
+/// `√(a² + b²)` without destructive overflow or underflow
+#[inline]
+fn pythag(a: f64, b: f64) -> f64 {
+    let (a, b) = (a.abs(), b.abs());
+    if a > b {
+        a * (1. + (b / a) * (b / a)).sqrt()
+    } else if b == 0.0 {
+        0.0
+    } else {
+        b * (1. + (a / b) * (a / b)).sqrt()
+    }
+}
@@ -0,0 +1,87 @@
+use crate::single::common::{Integrand, Range};
+use crate::single::util::Aligned;
+
+/// values of cos(πk/24) for k = 0, 1, ..., 12
+const COS24: [f64; 13] = [
+    1.0,
+    0.991444861373810411144557526928563,
+    0.965925826289068286749743199728897,
+    0.923879532511286756128183189396788,
+    0.866025403784438646763723170752936,
+    0.793353340291235164579776961501299,
+    core::f64::consts::FRAC_1_SQRT_2,
+    0.608761429008720639416097542898164,
+    0.5,
+    0.382683432365089771728459984030399,
+    0.258819045102520762348898837624048,
+    0.130526192220051591548406227895489,
+    0.0,
+];
+
+/// cos(πm/24) for any non-negative integer m
+#[inline]
+fn cos24(m: usize) -> f64 {
+    let m = m % 48;
+    let m = if m > 24 { 48 - m } else { m };
+
+    if m > 12 {
+        -COS24[24 - m]
+    } else {
+        COS24[m]
+    }
+}
+
+/// Chebyshev series expansion of the integrand over the subrange
+///
+/// `f(center + half_length * t)` is approximated by `Σ cheb24[k] T_k(t)`
+/// (25-point interpolation) and `Σ cheb12[k] T_k(t)` (13-point interpolation).
+pub struct ChebyshevSeries {
+    /// coefficients of the 13-point interpolation
+    pub cheb12: [f64; 13],
+    /// coefficients of the 25-point interpolation
+    pub cheb24: [f64; 25],
+}
+
+/// Compute the Chebyshev series expansion of `f` at the 25 points
+/// `cos(πk/24)` (k = 0, ..., 24) mapped onto the range.
+pub fn qcheb<F: Integrand + ?Sized>(f: &mut F, range: &Range) -> ChebyshevSeries {
+    let center = 0.5 * (range.begin + range.end);
+    let half_length = 0.5 * (range.end - range.begin);
+
+    let mut fval = unsafe { Aligned::<[f64; 25]>::uninit() };
+    for (j, x) in fval.iter_mut().enumerate() {
+        *x = center + half_length * cos24(j);
+    }
+
+    f.apply_to_slice(&mut *fval);
+
+    // the end points have half weights
+    fval[0] *= 0.5;
+    fval[24] *= 0.5;
+
+    let mut cheb24 = [0.0; 25];
+    for (k, c) in cheb24.iter_mut().enumerate() {
+        *c = fval
+            .iter()
+            .enumerate()
+            .map(|(j, &v)| v * cos24(j * k))
+            .sum::<f64>()
+            / 12.;
+    }
+    cheb24[0] *= 0.5;
+    cheb24[24] *= 0.5;
+
+    // 13-point interpolation uses only the even nodes
+    let mut cheb12 = [0.0; 13];
+    for (k, c) in cheb12.iter_mut().enumerate() {
+        let mut sum = fval[0] + fval[24] * cos24(24 * k);
+        for i in 1..12 {
+            sum += fval[2 * i] * cos24(2 * i * k);
+        }
+        *c = sum / 6.;
+    }
+    cheb12[0] *= 0.5;
+    cheb12[12] *= 0.5;
+
+    ChebyshevSeries { cheb12, cheb24 }
+}
@@ -6,10 +6,9 @@
 //#     double res3la[3];
 //#   };
 
-#[cfg(not(feature = "std"))]
 use crate::float::Float;
 
-pub struct ExtrapolationTable {
+pub struct ExtrapolationTable<T = f64> {
     /// rlist2\[n\] contains the new element in the first
     /// column of the epsilon table
     pub n: usize,
@@ -17,20 +16,20 @@ pub struct ExtrapolationTable {
     /// diagonals of the triangular epsilon table. the elements
     /// are numbered starting at the right^hand corner of the
     /// triangle
-    pub rlist2: [f64; 52],
+    pub rlist2: [T; 52],
     /// number of calls to the routine
     pub nres: usize,
     /// the vector containing the last 3 results
-    pub res3la: [f64; 3],
+    pub res3la: [T; 3],
 }
 
-impl ExtrapolationTable {
+impl<T: Float> ExtrapolationTable<T> {
     /// append calculation result.
     ///
     /// size of rlist2 is limited, but when it get full, the contents are
     /// shifted and the oldest result are removed from array
     #[inline]
-    pub fn append(&mut self, y: f64) {
+    pub fn append(&mut self, y: T) {
         let n = self.n;
         self.rlist2[n] = y;
         self.n += 1;
@@ -47,7 +46,7 @@ impl ExtrapolationTable {
     /// * `abserr` - estimate of the absolute error computed from
     ///              result and the 3 previous results
     ///
-    pub fn qelg(&mut self, result: &mut f64, abserr: &mut f64) {
+    pub fn qelg(&mut self, result: &mut T, abserr: &mut T) {
         //# double *epstab = table->rlist2;
         //# double *res3la = table->res3la;
         //# const size_t n = table->n - 1;
@@ -80,7 +79,7 @@ impl ExtrapolationTable {
         let nres_orig = self.nres;
 
         *result = current;
-        *abserr = core::f64::MAX;
+        *abserr = T::MAX;
 
         //# if (n < 2)
         //#   {
@@ -91,7 +90,7 @@ impl ExtrapolationTable {
 
         if n < 2 {
             *result = current;
-            *abserr = core::f64::MAX;
+            *abserr = T::MAX;
             return;
         }
 
@@ -101,7 +100,7 @@ impl ExtrapolationTable {
 
             let mut ep = epstab.as_mut_ptr().add(n);
             *ep.add(2) = *ep;
-            *ep = core::f64::MAX;
+            *ep = T::MAX;
 
             for i in 0..newelm {
                 //# double res = epstab[n - 2 * i + 2];
@@ -125,10 +124,10 @@ impl ExtrapolationTable {
                 let elabs = e1.abs();
                 let delta2 = e2 - e1;
                 let err2 = delta2.abs();
-                let tol2 = f64::max(e2.abs(), elabs) * core::f64::EPSILON;
+                let tol2 = e2.abs().max(elabs) * T::EPSILON;
                 let delta3 = e1 - e0;
                 let err3 = delta3.abs();
-                let tol3 = f64::max(elabs, e0.abs()) * core::f64::EPSILON;
+                let tol3 = elabs.max(e0.abs()) * T::EPSILON;
 
                 //# if (err2 <= tol2 && err3 <= tol3)
                 //#   {
@@ -144,8 +143,8 @@ impl ExtrapolationTable {
                 if err2 <= tol2 && err3 <= tol3 {
                     *result = res;
                     let absolute = err2 + err3;
-                    let relative = 5. * core::f64::EPSILON * res.abs();
-                    *abserr = f64::max(absolute, relative);
+                    let relative = T::from_f64(5.) * T::EPSILON * res.abs();
+                    *abserr = absolute.max(relative);
                     return;
                 }
 
@@ -159,7 +158,7 @@ impl ExtrapolationTable {
                 *ep = e1;
                 let delta1 = e1 - e3;
                 let err1 = delta1.abs();
-                let tol1 = f64::max(elabs, e3.abs()) * core::f64::EPSILON;
+                let tol1 = elabs.max(e3.abs()) * T::EPSILON;
 
                 //# if (err1 <= tol1 || err2 <= tol2 || err3 <= tol3)
                 //#   {
@@ -173,7 +172,7 @@ impl ExtrapolationTable {
                     break;
                 }
 
-                let ss = (1. / delta1 + 1. / delta2) - 1. / delta3;
+                let ss = (T::ONE / delta1 + T::ONE / delta2) - T::ONE / delta3;
 
                 // Test to detect irregular behaviour in the table, and
                 // eventually omit a part of the table by adjusting the value of
@@ -185,7 +184,7 @@ impl ExtrapolationTable {
                 //#     break;
                 //#   }
 
-                if (ss * e1).abs() <= 0.0001 {
+                if (ss * e1).abs() <= T::from_f64(0.0001) {
                     n_final = 2 * i;
                     break;
                 }
@@ -196,7 +195,7 @@ impl ExtrapolationTable {
                 //# res = e1 + 1 / ss;
                 //# epstab[n - 2 * i] = res;
 
-                res = e1 + 1. / ss;
+                res = e1 + T::ONE / ss;
                 *ep = res;
 
                 //# {
@@ -295,7 +294,7 @@ impl ExtrapolationTable {
 
             if let Some(res) = res3la.get_mut(nres_orig) {
                 *res = *result;
-                *abserr = core::f64::MAX;
+                *abserr = T::MAX;
             } else {
                 /* Compute error estimate */
                 *abserr = (*result - res3la[2]).abs()
@@ -319,18 +318,18 @@ impl ExtrapolationTable {
         //# *abserr = GSL_MAX_DBL (*abserr, 5 * GSL_DBL_EPSILON * fabs (*result));
 
         self.nres = nres_orig + 1;
-        *abserr = f64::max(*abserr, 5. * core::f64::EPSILON * result.abs());
+        *abserr = (*abserr).max(T::from_f64(5.) * T::EPSILON * result.abs());
     }
 }
 
-impl Default for ExtrapolationTable {
+impl<T: Float> Default for ExtrapolationTable<T> {
     #[inline]
     fn default() -> Self {
         Self {
             n: 0,
-            rlist2: [0f64; 52],
+            rlist2: [T::ZERO; 52],
             nres: 0,
-            res3la: [0f64; 3],
+            res3la: [T::ZERO; 3],
         }
     }
 }
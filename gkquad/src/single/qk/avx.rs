@@ -65,8 +65,8 @@ pub unsafe fn qk<F, K, G>(
 ) -> QKResult
 where
     F: Integrand + ?Sized,
-    K: Array<Item = f64>,
-    G: Array<Item = f64>,
+    K: Array<Item = f64> + ?Sized,
+    G: Array<Item = f64> + ?Sized,
 {
     let xgk = xgk.as_slice();
     let wg = wg.as_slice();
@@ -77,7 +77,7 @@ where
     debug_assert!(buf.len() >= xgk.len() * 2 + 1);
     debug_assert!(range.begin.is_finite() && range.end.is_finite());
 
-    let n = K::CAPACITY;
+    let n = xgk.len();
     let center = 0.5 * (range.begin + range.end);
     let half_length = 0.5 * (range.end - range.begin);
     let abs_half_length = half_length.abs();
@@ -190,7 +190,6 @@ where
     buf[16] = center;
 
     f.apply_to_slice(buf);
-    // buf.iter_mut().for_each(|v| *v = f.apply(*v));
 
     let mut result_kronrod: __m256d;
     let mut result_abs: __m256d;
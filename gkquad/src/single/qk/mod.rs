@@ -3,25 +3,36 @@ mod avx;
 #[cfg(all(feature = "simd", target_feature = "avx"))]
 use avx::{qk, qk17 as qk17_};
 
-#[cfg(not(all(feature = "simd", target_feature = "avx")))]
 mod naive;
 #[cfg(not(all(feature = "simd", target_feature = "avx")))]
 use naive::{qk, qk17 as qk17_};
 
-use super::common::{Integrand, Range};
-use super::util::Aligned;
+mod rule;
+pub use rule::*;
+
+mod vector;
+pub(crate) use vector::qk_vec;
+
+use alloc::vec::Vec;
+use core::any::TypeId;
+use core::marker::PhantomData;
+use smallvec::SmallVec;
+
+use super::common::{Integrand, IntegrandComplex, Range};
+use super::util::{Aligned, ComplexWrapper};
+use crate::float::Float;
 
 /// holds the result of Gauss-Kronrod integration
 #[derive(Debug)]
-pub struct QKResult {
+pub struct QKResult<T = f64> {
     /// approximation to the integral
-    pub estimate: f64,
+    pub estimate: T,
     /// estimate of the modulus of the absolute error
-    pub delta: f64,
+    pub delta: T,
     /// approximation to the integral of |f|
-    pub absvalue: f64,
+    pub absvalue: T,
     /// approximation to the integral of |f - quad(f)/(b - a)|
-    pub asc: f64,
+    pub asc: T,
 }
 
 /// Performs Gauss-Kronrod integration with 17-point kronrod rule
@@ -72,10 +83,259 @@ pub fn qk57<F: Integrand + ?Sized>(f: &mut F, r: &Range) -> QKResult {
     }
 }
 
+/// Gauss-Kronrod rule used in the adaptive algorithms
+///
+/// # Examples
+///
+/// ```
+/// use gkquad::single::algorithm::QAGS;
+/// use gkquad::single::{GaussKronrod, Integrator};
+///
+/// // use 41-point rule for smooth integrand
+/// let result = Integrator::with_algorithm(|x: f64| x.exp(), QAGS::with_rule(GaussKronrod::K41))
+///     .run(0.0..1.0)
+///     .unwrap()
+///     .estimate;
+/// ```
+#[derive(Clone, Copy, Debug, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub enum GaussKronrod {
+    /// 17-point rule
+    K17,
+    /// 25-point rule
+    K25,
+    /// 33-point rule
+    K33,
+    /// 41-point rule
+    K41,
+    /// 49-point rule
+    K49,
+    /// 57-point rule
+    K57,
+}
+
+impl GaussKronrod {
+    /// number of points of the Kronrod rule
+    #[inline]
+    pub fn points(self) -> usize {
+        match self {
+            GaussKronrod::K17 => 17,
+            GaussKronrod::K25 => 25,
+            GaussKronrod::K33 => 33,
+            GaussKronrod::K41 => 41,
+            GaussKronrod::K49 => 49,
+            GaussKronrod::K57 => 57,
+        }
+    }
+
+    /// Performs Gauss-Kronrod integration with this rule
+    #[inline]
+    pub fn integrate<F: Integrand + ?Sized>(self, f: &mut F, r: &Range) -> QKResult {
+        match self {
+            GaussKronrod::K17 => qk17(f, r),
+            GaussKronrod::K25 => qk25(f, r),
+            GaussKronrod::K33 => qk33(f, r),
+            GaussKronrod::K41 => qk41(f, r),
+            GaussKronrod::K49 => qk49(f, r),
+            GaussKronrod::K57 => qk57(f, r),
+        }
+    }
+
+    /// Performs Gauss-Kronrod integration of the complex-valued function with
+    /// this rule
+    ///
+    /// The real and imaginary parts are integrated in one pass, and the
+    /// results are returned as `(re, im)`.
+    pub fn integrate_complex<F: IntegrandComplex + ?Sized>(
+        self,
+        f: &mut F,
+        r: &Range,
+    ) -> (QKResult, QKResult) {
+        let mut buf = Vec::new();
+        let mut results = qk_vec(&mut ComplexWrapper { inner: f }, r, self, 2, &mut buf);
+        let im = results.pop().unwrap();
+        let re = results.pop().unwrap();
+        (re, im)
+    }
+
+    /// abscissae, Gauss weights, Kronrod weights, and Kronrod weight for the
+    /// center point
+    #[inline]
+    pub(crate) fn coefficients(self) -> (&'static [f64], &'static [f64], &'static [f64], f64) {
+        match self {
+            GaussKronrod::K17 => (&XGK17.value, &WG17.value, &WGK17.value, WCK17),
+            GaussKronrod::K25 => (&XGK25.value, &WG25.value, &WGK25.value, WCK25),
+            GaussKronrod::K33 => (&XGK33.value, &WG33.value, &WGK33.value, WCK33),
+            GaussKronrod::K41 => (&XGK41.value, &WG41.value, &WGK41.value, WCK41),
+            GaussKronrod::K49 => (&XGK49.value, &WG49.value, &WGK49.value, WCK49),
+            GaussKronrod::K57 => (&XGK57.value, &WG57.value, &WGK57.value, WCK57),
+        }
+    }
+
+    /// same as `coefficients`, but the coefficients are decimal literals
+    #[inline]
+    fn literals(
+        self,
+    ) -> (
+        &'static [&'static str],
+        &'static [&'static str],
+        &'static [&'static str],
+        &'static str,
+    ) {
+        match self {
+            GaussKronrod::K17 => (&XGK17_LIT, &WG17_LIT, &WGK17_LIT, WCK17_LIT),
+            GaussKronrod::K25 => (&XGK25_LIT, &WG25_LIT, &WGK25_LIT, WCK25_LIT),
+            GaussKronrod::K33 => (&XGK33_LIT, &WG33_LIT, &WGK33_LIT, WCK33_LIT),
+            GaussKronrod::K41 => (&XGK41_LIT, &WG41_LIT, &WGK41_LIT, WCK41_LIT),
+            GaussKronrod::K49 => (&XGK49_LIT, &WG49_LIT, &WGK49_LIT, WCK49_LIT),
+            GaussKronrod::K57 => (&XGK57_LIT, &WG57_LIT, &WGK57_LIT, WCK57_LIT),
+        }
+    }
+}
+
+/// Gauss-Kronrod rule whose coefficients are converted into the floating-point
+/// type `T`
+///
+/// The conversion is performed only once on creation, so this object should be
+/// re-used when the rule is applied to many ranges.
+///
+/// # Examples
+///
+/// ```
+/// use gkquad::single::{GaussKronrod, QKCoefficients, Range};
+///
+/// let rule = QKCoefficients::<f32>::new(GaussKronrod::K25);
+/// let result = rule.integrate(&mut |x: f32| x * x, &Range::new(0.0, 3.0).unwrap());
+/// assert!((result.estimate - 9.0).abs() < 1e-5);
+/// ```
+#[derive(Clone, Debug)]
+pub struct QKCoefficients<T> {
+    rule: GaussKronrod,
+    pub(crate) xgk: Vec<T>,
+    pub(crate) wg: Vec<T>,
+    pub(crate) wgk: Vec<T>,
+    pub(crate) wck: T,
+}
+
+impl<T: Float> QKCoefficients<T> {
+    /// Convert the coefficients of `rule` into `T`
+    #[inline]
+    pub fn new(rule: GaussKronrod) -> Self {
+        if is_f64::<T>() {
+            Self::empty(rule)
+        } else {
+            Self::parse(rule)
+        }
+    }
+
+    /// parse the decimal literals of the coefficients
+    fn parse(rule: GaussKronrod) -> Self {
+        let (xgk, wg, wgk, wck) = rule.literals();
+        let parse = |s: &[&str]| s.iter().map(|x| T::parse_literal(x)).collect();
+
+        Self {
+            rule,
+            xgk: parse(xgk),
+            wg: parse(wg),
+            wgk: parse(wgk),
+            wck: T::parse_literal(wck),
+        }
+    }
+
+    /// coefficients which are never read, since `f64` uses the specialized
+    /// kernels
+    #[inline]
+    fn empty(rule: GaussKronrod) -> Self {
+        Self {
+            rule,
+            xgk: Vec::new(),
+            wg: Vec::new(),
+            wgk: Vec::new(),
+            wck: T::ZERO,
+        }
+    }
+
+    /// Gauss-Kronrod rule
+    #[inline]
+    pub fn rule(&self) -> GaussKronrod {
+        self.rule
+    }
+
+    /// Performs Gauss-Kronrod integration with this rule
+    #[inline]
+    pub fn integrate<F: Integrand<T> + ?Sized>(&self, f: &mut F, r: &Range<T>) -> QKResult<T> {
+        if is_f64::<T>() {
+            // `T` is `f64`, so the casts below do not change any type
+            unsafe {
+                let mut f = AsF64 {
+                    inner: f,
+                    _phantom: PhantomData,
+                };
+                let r = &*(r as *const Range<T> as *const Range);
+                core::mem::transmute_copy(&self.rule.integrate(&mut f, r))
+            }
+        } else {
+            let mut buf: SmallVec<[T; 64]> = SmallVec::from_elem(T::ZERO, 2 * self.xgk.len() + 1);
+            unsafe {
+                naive::qk(
+                    f,
+                    r,
+                    &self.xgk[..],
+                    &self.wg[..],
+                    &self.wgk[..],
+                    self.wck,
+                    &mut buf,
+                )
+            }
+        }
+    }
+}
+
+/// whether `T` is `f64`, which has the specialized kernels
+#[inline(always)]
+fn is_f64<T: Float>() -> bool {
+    TypeId::of::<T>() == TypeId::of::<f64>()
+}
+
+/// `Integrand<T>` seen as `Integrand<f64>`
+///
+/// This must be constructed only if `T` is `f64`.
+struct AsF64<'a, T, F: ?Sized> {
+    inner: &'a mut F,
+    _phantom: PhantomData<T>,
+}
+
+impl<'a, T: Float, F: Integrand<T> + ?Sized> Integrand for AsF64<'a, T, F> {
+    #[inline]
+    fn apply(&mut self, x: f64) -> f64 {
+        debug_assert!(is_f64::<T>());
+        unsafe { core::mem::transmute_copy(&self.inner.apply(core::mem::transmute_copy(&x))) }
+    }
+
+    #[inline]
+    fn apply_to_slice(&mut self, s: &mut [f64]) {
+        debug_assert!(is_f64::<T>());
+        let s = unsafe { core::slice::from_raw_parts_mut(s.as_mut_ptr() as *mut T, s.len()) };
+        self.inner.apply_to_slice(s)
+    }
+}
+
+/// define the coefficient for `f64` together with its decimal literal, which
+/// is converted into the other floating-point types at full precision
+macro_rules! gk_const {
+    ($name:ident: [f64; $n:expr], $lit:ident = [$($x:literal),* $(,)?]) => {
+        const $name: Aligned<[f64; $n]> = Aligned::new([$($x),*]);
+        const $lit: [&str; $n] = [$(stringify!($x)),*];
+    };
+    ($name:ident: f64, $lit:ident = $x:literal) => {
+        const $name: f64 = $x;
+        const $lit: &str = stringify!($x);
+    };
+}
+
 // Gauss-Kronrod weights
 // source: https://keisan.casio.com/exec/system/1289382036
 
-const XGK17: Aligned<[f64; 8]> = Aligned::new([
+gk_const!(XGK17: [f64; 8], XGK17_LIT = [
     0.183434642495649804939476142360184,
     0.360701097928131957192548622296891,
     0.525532409916328985817739049189246,
@@ -86,16 +346,16 @@ const XGK17: Aligned<[f64; 8]> = Aligned::new([
     0.993379875881716155935888069019671,
 ]);
 
-const WG17: Aligned<[f64; 4]> = Aligned::new([
+gk_const!(WG17: [f64; 4], WG17_LIT = [
     0.362683783378361982965150449277196,
     0.313706645877887287337962201986601,
     0.222381034453374470544355994426241,
     0.101228536290376259152531354309962,
 ]);
 
-const WCK17: f64 = 0.184446405744691643528970955705643;
+gk_const!(WCK17: f64, WCK17_LIT = 0.184446405744691643528970955705643);
 
-const WGK17: Aligned<[f64; 8]> = Aligned::new([
+gk_const!(WGK17: [f64; 8], WGK17_LIT = [
     0.181400025068034643061748525172550,
     0.172070608555211311857294880203857,
     0.156652606168188400490248088486969,
@@ -106,7 +366,7 @@ const WGK17: Aligned<[f64; 8]> = Aligned::new([
     0.017822383320710355152786961202750,
 ]);
 
-const XGK25: Aligned<[f64; 12]> = Aligned::new([
+gk_const!(XGK25: [f64; 12], XGK25_LIT = [
     0.125233408511468915472441369463853,
     0.248505748320469276267790960362718,
     0.367831498998180193752691536643718,
@@ -121,7 +381,7 @@ const XGK25: Aligned<[f64; 12]> = Aligned::new([
     0.996933922529595426912350237258385,
 ]);
 
-const WG25: Aligned<[f64; 6]> = Aligned::new([
+gk_const!(WG25: [f64; 6], WG25_LIT = [
     0.249147045813402785000562436042951,
     0.233492536538354808760849898924878,
     0.203167426723065921749064455809798,
@@ -130,9 +390,9 @@ const WG25: Aligned<[f64; 6]> = Aligned::new([
     0.047175336386511827194615961485017,
 ]);
 
-const WCK25: f64 = 0.125556893905474335304296132860078;
+gk_const!(WCK25: f64, WCK25_LIT = 0.125556893905474335304296132860078);
 
-const WGK25: Aligned<[f64; 12]> = Aligned::new([
+gk_const!(WGK25: [f64; 12], WGK25_LIT = [
     0.124584164536156073437312473209229,
     0.121626303523948383246099758091310,
     0.116712053501756826293580745305730,
@@ -147,7 +407,7 @@ const WGK25: Aligned<[f64; 12]> = Aligned::new([
     0.008257711433168395757693922439212,
 ]);
 
-const XGK33: Aligned<[f64; 16]> = Aligned::new([
+gk_const!(XGK33: [f64; 16], XGK33_LIT = [
     0.095012509837637440185319335424958,
     0.189168579018083726314712086634942,
     0.281603550779258913230460501460496,
@@ -166,7 +426,7 @@ const XGK33: Aligned<[f64; 16]> = Aligned::new([
     0.998239274145444514183282371262429,
 ]);
 
-const WG33: Aligned<[f64; 8]> = Aligned::new([
+gk_const!(WG33: [f64; 8], WG33_LIT = [
     0.189450610455068496285396723208283,
     0.182603415044923588866763667969220,
     0.169156519395002538189312079030360,
@@ -177,9 +437,9 @@ const WG33: Aligned<[f64; 8]> = Aligned::new([
     0.027152459411754094851780572456018,
 ]);
 
-const WCK33: f64 = 0.095154216080498307020415055955840;
+gk_const!(WCK33: f64, WCK33_LIT = 0.095154216080498307020415055955840);
 
-const WGK33: Aligned<[f64; 16]> = Aligned::new([
+gk_const!(WGK33: [f64; 16], WGK33_LIT = [
     0.094728401247230041326733968799471,
     0.093438674060921230478147190079644,
     0.091292032828191662272226594165447,
@@ -198,7 +458,7 @@ const WGK33: Aligned<[f64; 16]> = Aligned::new([
     0.004742777049247317906344087722423,
 ]);
 
-const XGK41: Aligned<[f64; 20]> = Aligned::new([
+gk_const!(XGK41: [f64; 20], XGK41_LIT = [
     0.076526521133497333754640409398838,
     0.152605465240922675505220241022678,
     0.227785851141645078080496195368575,
@@ -221,7 +481,7 @@ const XGK41: Aligned<[f64; 20]> = Aligned::new([
     0.998859031588277663838315576545863,
 ]);
 
-const WG41: Aligned<[f64; 10]> = Aligned::new([
+gk_const!(WG41: [f64; 10], WG41_LIT = [
     0.152753387130725850698084331955098,
     0.149172986472603746787828737001969,
     0.142096109318382051329298325067165,
@@ -234,9 +494,9 @@ const WG41: Aligned<[f64; 10]> = Aligned::new([
     0.017614007139152118311861962351853,
 ]);
 
-const WCK41: f64 = 0.076600711917999656445049901530102;
+gk_const!(WCK41: f64, WCK41_LIT = 0.076600711917999656445049901530102);
 
-const WGK41: Aligned<[f64; 20]> = Aligned::new([
+gk_const!(WGK41: [f64; 20], WGK41_LIT = [
     0.076377867672080736705502835038061,
     0.075704497684556674659542775376617,
     0.074582875400499188986581418362488,
@@ -259,7 +519,7 @@ const WGK41: Aligned<[f64; 20]> = Aligned::new([
     0.003073583718520531501218293246031,
 ]);
 
-const XGK49: Aligned<[f64; 24]> = Aligned::new([
+gk_const!(XGK49: [f64; 24], XGK49_LIT = [
     0.064056892862605626085043082624745,
     0.127851240286216699326667098746182,
     0.191118867473616309158639820757070,
@@ -286,7 +546,7 @@ const XGK49: Aligned<[f64; 24]> = Aligned::new([
     0.999201056021875051655759554001639,
 ]);
 
-const WG49: Aligned<[f64; 12]> = Aligned::new([
+gk_const!(WG49: [f64; 12], WG49_LIT = [
     0.127938195346752156974056165224695,
     0.125837456346828296121375382511184,
     0.121670472927803391204463153476262,
@@ -301,9 +561,9 @@ const WG49: Aligned<[f64; 12]> = Aligned::new([
     0.012341229799987199546805667070037,
 ]);
 
-const WCK49: f64 = 0.064100463769266717756751728006586;
+gk_const!(WCK49: f64, WCK49_LIT = 0.064100463769266717756751728006586);
 
-const WGK49: Aligned<[f64; 24]> = Aligned::new([
+gk_const!(WGK49: [f64; 24], WGK49_LIT = [
     0.063969626241376347509644034120127,
     0.063574878712972420734803194875307,
     0.062917112269698113170570246311836,
@@ -330,7 +590,7 @@ const WGK49: Aligned<[f64; 24]> = Aligned::new([
     0.002152308550946222060921213031076,
 ]);
 
-const XGK57: Aligned<[f64; 28]> = Aligned::new([
+gk_const!(XGK57: [f64; 28], XGK57_LIT = [
     0.055079289884034270426516527341880,
     0.109991649290732781027815261597935,
     0.164569282133380771281471777891166,
@@ -361,7 +621,7 @@ const XGK57: Aligned<[f64; 28]> = Aligned::new([
     0.999409527464458185430469787030878,
 ]);
 
-const WG57: Aligned<[f64; 14]> = Aligned::new([
+gk_const!(WG57: [f64; 14], WG57_LIT = [
     0.110047013016475196282376265601818,
     0.108711192258294135253571519303673,
     0.106055765922846417910416436996811,
@@ -378,9 +638,9 @@ const WG57: Aligned<[f64; 14]> = Aligned::new([
     0.009124282593094517738816153922952,
 ]);
 
-const WCK57: f64 = 0.055107015299777746735626928438028;
+gk_const!(WCK57: f64, WCK57_LIT = 0.055107015299777746735626928438028);
 
-const WGK57: Aligned<[f64; 28]> = Aligned::new([
+gk_const!(WGK57: [f64; 28], WGK57_LIT = [
     0.055023774809077271036502426453062,
     0.054772896844694716781166407068630,
     0.054354779876438122260022699069615,
@@ -1,8 +1,6 @@
 use super::super::common::{Integrand, Range};
 use super::super::qk::QKResult;
 use super::super::util::{rescale_error, Array};
-
-#[cfg(not(feature = "std"))]
 use crate::float::Float;
 
 /// perform Gauss-Kronrod integration with custom points
@@ -10,7 +8,8 @@ use crate::float::Float;
 /// # Parameters
 ///
 /// * xgk: abscissae of the Gauss-Kronrod rule
-/// * wg: weight of the Gauss rule
+/// * wg: weight of the Gauss rule, followed by the weight for the center point
+///   if `xgk.len()` is odd
 /// * wgk: weight of the Kronrod rule
 /// * wck: Kronrod weight for center point
 /// * buf: buffer to hold return value of integrand, may be not initialized.
@@ -21,7 +20,7 @@ use crate::float::Float;
 /// function causes an undefined behaviour.
 ///
 /// * xgk.len() > 0
-/// * xgk.len() / 2 == wg.len()
+/// * xgk.len().div_ceil(2) == wg.len()
 /// * buf.len() >= xgk.len() * 2 - 1
 /// * range is finite
 ///
@@ -29,67 +28,99 @@ use crate::float::Float;
 ///
 /// - SIMD implementation
 #[inline(always)]
-pub unsafe fn qk<F, K, G>(
+pub unsafe fn qk<T, F, K, G>(
     f: &mut F,
-    range: &Range,
+    range: &Range<T>,
     xgk: &K,
     wg: &G,
     wgk: &K,
-    wck: f64,
-    buf: &mut [f64],
-) -> QKResult
+    wck: T,
+    buf: &mut [T],
+) -> QKResult<T>
 where
-    F: Integrand + ?Sized,
-    K: Array<Item = f64>,
-    G: Array<Item = f64>,
+    T: Float,
+    F: Integrand<T> + ?Sized,
+    K: Array<Item = T> + ?Sized,
+    G: Array<Item = T> + ?Sized,
 {
     let xgk = xgk.as_slice();
     let wg = wg.as_slice();
     let wgk = wgk.as_slice();
 
     debug_assert!(!xgk.is_empty());
-    debug_assert!(xgk.len() == wg.len() * 2);
+    debug_assert!(xgk.len().div_ceil(2) == wg.len());
     debug_assert!(buf.len() > xgk.len() * 2);
     debug_assert!(range.begin.is_finite() && range.end.is_finite());
 
-    let n = K::CAPACITY;
-    let center = 0.5 * (range.begin + range.end);
-    let half_length = 0.5 * (range.end - range.begin);
-    let abs_half_length = half_length.abs();
+    let n = xgk.len();
+    let half = T::from_f64(0.5);
+    let center = half * (range.begin + range.end);
+    let half_length = half * (range.end - range.begin);
 
     *buf.get_unchecked_mut(n << 1) = center;
 
     for j in 0..n {
-        let abscissa = half_length * xgk.get_unchecked(j);
+        let abscissa = half_length * *xgk.get_unchecked(j);
         *buf.get_unchecked_mut(j) = center - abscissa;
         *buf.get_unchecked_mut(j + n) = center + abscissa;
     }
 
     f.apply_to_slice(buf);
 
-    let f_center = buf.get_unchecked(n << 1);
-    let mut result_gauss = 0.;
+    qk_sum(|j| *buf.get_unchecked(j), half_length, wg, wgk, wck)
+}
+
+/// sum up the values of the integrand with the weights of the Gauss and the
+/// Kronrod rules
+///
+/// With `n = wgk.len()`, `fv(j)` must return the value at
+/// `center - half_length * xgk[j]` for `j < n`, at
+/// `center + half_length * xgk[j - n]` for `n <= j < 2n`, and at `center` for
+/// `j == 2n`.
+///
+/// The Gauss abscissae are located at the even indices if `n` is even.
+/// Otherwise they are located at the odd indices and the center point, and the
+/// last element of `wg` is the Gauss weight for the center point.
+#[inline(always)]
+pub fn qk_sum<T: Float, V: Fn(usize) -> T>(
+    fv: V,
+    half_length: T,
+    wg: &[T],
+    wgk: &[T],
+    wck: T,
+) -> QKResult<T> {
+    debug_assert!(wgk.len().div_ceil(2) == wg.len());
+
+    let n = wgk.len();
+    let half = T::from_f64(0.5);
+    let abs_half_length = half_length.abs();
+
+    let f_center = fv(n << 1);
+    let mut result_gauss = if n % 2 == 1 {
+        f_center * wg[n / 2]
+    } else {
+        T::ZERO
+    };
     let mut result_kronrod = f_center * wck;
     let mut result_abs = result_kronrod.abs();
 
-    for j in 0..n {
-        let fval1 = *buf.get_unchecked(j);
-        let fval2 = *buf.get_unchecked(j + n);
+    for (j, &w) in wgk.iter().enumerate() {
+        let fval1 = fv(j);
+        let fval2 = fv(j + n);
         let fsum = fval1 + fval2;
-        result_kronrod += wgk.get_unchecked(j) * fsum;
-        result_abs += wgk.get_unchecked(j) * (fval1.abs() + fval2.abs());
+        result_kronrod += w * fsum;
+        result_abs += w * (fval1.abs() + fval2.abs());
 
-        if j % 2 == 0 {
-            result_gauss += *wg.get_unchecked(j / 2) * fsum;
+        if (j + n).is_multiple_of(2) {
+            result_gauss += wg[j / 2] * fsum;
         }
     }
 
-    let mean = result_kronrod * 0.5;
+    let mean = result_kronrod * half;
     let mut result_asc = wck * (f_center - mean).abs();
 
-    for j in 0..n {
-        result_asc += wgk.get_unchecked(j)
-            * ((buf.get_unchecked(j) - mean).abs() + (buf.get_unchecked(j + n) - mean).abs());
+    for (j, &w) in wgk.iter().enumerate() {
+        result_asc += w * ((fv(j) - mean).abs() + (fv(j + n) - mean).abs());
     }
 
     let err = (result_kronrod - result_gauss) * half_length;
@@ -106,6 +137,7 @@ where
 }
 
 #[inline(always)]
+#[cfg_attr(all(feature = "simd", target_feature = "avx"), allow(dead_code))]
 pub unsafe fn qk17<F: Integrand + ?Sized>(
     f: &mut F,
     range: &Range,
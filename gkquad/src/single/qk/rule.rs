@@ -0,0 +1,196 @@
+use alloc::vec::Vec;
+
+use super::QKResult;
+use crate::single::common::{Integrand, Range};
+use crate::single::orthopoly::{golub_welsch, kronrod_recurrence, legendre_recurrence};
+use crate::single::util::Aligned;
+
+/// Gauss-Kronrod rule with arbitrary number of points
+///
+/// The nodes and weights are computed at run time by Laurie's algorithm and
+/// Golub-Welsch algorithm. The `n`-point rule consists of the `(n-1)/2`-point
+/// Gauss rule and its Kronrod extension, so `GaussKronrodRule::new(25)` is
+/// equivalent to [`qk25`](fn.qk25.html).
+///
+/// `n` can be any odd number greater than 1. If the number of the Gauss points
+/// is odd (`n = 4k+3`), the center point is also a Gauss node, and the rule is
+/// always evaluated by the scalar kernel, since the SIMD kernel assumes that
+/// the Gauss nodes are placed at the even positions of the abscissae.
+///
+/// # Examples
+///
+/// ```
+/// use gkquad::single::{qk_with_rule, GaussKronrodRule, Range};
+///
+/// let rule = GaussKronrodRule::new(73).unwrap();
+/// let result = qk_with_rule(&mut |x: f64| x.cos(), &Range::new(0.0, 1.0).unwrap(), &rule);
+/// assert!((result.estimate - 1f64.sin()).abs() < 1e-15);
+/// ```
+#[derive(Clone)]
+pub struct GaussKronrodRule {
+    /// number of the positive abscissae
+    n: usize,
+    /// abscissae in ascending order, excluding the center point
+    xgk: Vec<Aligned<[f64; 4]>>,
+    /// weights of the Gauss rule
+    wg: Vec<Aligned<[f64; 4]>>,
+    /// weights of the Kronrod rule
+    wgk: Vec<Aligned<[f64; 4]>>,
+    /// Kronrod weight for the center point
+    wck: f64,
+}
+
+impl GaussKronrodRule {
+    /// Compute the `n`-point Gauss-Kronrod rule
+    ///
+    /// Returns `None` if `n` is even or less than 3, or the computation fails.
+    pub fn new(n: usize) -> Option<Self> {
+        if n < 3 || n % 2 != 1 {
+            return None;
+        }
+
+        // number of the Gauss points
+        let m = (n - 1) / 2;
+        let (a, b) = legendre_recurrence(3 * m / 2 + 2);
+        let (a, b) = kronrod_recurrence(m, &a, &b);
+        let nodes = golub_welsch(&a, &b)?;
+
+        // symmetrize the nodes and weights
+        let positive = |j: usize| {
+            let (x1, w1) = nodes[m + 1 + j];
+            let (x2, w2) = nodes[m - 1 - j];
+            (0.5 * (x1 - x2), 0.5 * (w1 + w2))
+        };
+
+        let mut xgk = Vec::with_capacity(m);
+        let mut wgk = Vec::with_capacity(m);
+        for j in 0..m {
+            let (x, w) = positive(j);
+            xgk.push(x);
+            wgk.push(w);
+        }
+
+        // the Gauss nodes are placed at the even positions if `m` is even, and
+        // at the odd positions and the center otherwise
+        let (a, b) = legendre_recurrence(m);
+        let gauss = golub_welsch(&a, &b)?;
+        let h = m / 2;
+        let mut wg: Vec<f64> = (0..h)
+            .map(|j| 0.5 * (gauss[m - h + j].1 + gauss[h - 1 - j].1))
+            .collect();
+        if m % 2 == 1 {
+            wg.push(gauss[h].1);
+        }
+
+        Some(Self {
+            n: m,
+            xgk: to_aligned(&xgk),
+            wg: to_aligned(&wg),
+            wgk: to_aligned(&wgk),
+            wck: nodes[m].1,
+        })
+    }
+
+    /// number of points of the Kronrod rule
+    #[inline]
+    pub fn points(&self) -> usize {
+        2 * self.n + 1
+    }
+
+    /// positive abscissae of the Kronrod rule in ascending order
+    ///
+    /// The Gauss abscissae are located at the even indices if the number of
+    /// the Gauss points is even, and at the odd indices otherwise.
+    #[inline]
+    pub fn abscissae(&self) -> &[f64] {
+        as_slice(&self.xgk, self.n)
+    }
+
+    /// weights of the Gauss rule corresponding to the positive Gauss abscissae
+    ///
+    /// If the number of the Gauss points is odd, the last element is the weight
+    /// for the center point.
+    #[inline]
+    pub fn gauss_weights(&self) -> &[f64] {
+        as_slice(&self.wg, self.n.div_ceil(2))
+    }
+
+    /// weights of the Kronrod rule corresponding to the positive abscissae
+    #[inline]
+    pub fn kronrod_weights(&self) -> &[f64] {
+        as_slice(&self.wgk, self.n)
+    }
+
+    /// Kronrod weight for the center point
+    #[inline]
+    pub fn center_weight(&self) -> f64 {
+        self.wck
+    }
+}
+
+impl core::fmt::Debug for GaussKronrodRule {
+    fn fmt(&self, f: &mut core::fmt::Formatter) -> core::fmt::Result {
+        f.debug_struct("GaussKronrodRule")
+            .field("points", &self.points())
+            .finish()
+    }
+}
+
+impl PartialEq for GaussKronrodRule {
+    #[inline]
+    fn eq(&self, other: &Self) -> bool {
+        self.n == other.n
+    }
+}
+
+/// Performs Gauss-Kronrod integration with the given rule
+pub fn qk_with_rule<F: Integrand + ?Sized>(
+    f: &mut F,
+    r: &Range,
+    rule: &GaussKronrodRule,
+) -> QKResult {
+    let n = rule.n;
+    let mut buf: Vec<Aligned<[f64; 4]>> = (0..(2 * n + 4) / 4)
+        .map(|_| Aligned::new([0.0; 4]))
+        .collect();
+    let buf = as_mut_slice(&mut buf, 2 * n + 1);
+
+    let (xgk, wg, wgk) = (
+        rule.abscissae(),
+        rule.gauss_weights(),
+        rule.kronrod_weights(),
+    );
+
+    // SIMD kernel requires the number of abscissae to be a multiple of 4
+    unsafe {
+        if n.is_multiple_of(4) {
+            super::qk(f, r, xgk, wg, wgk, rule.wck, buf)
+        } else {
+            super::naive::qk(f, r, xgk, wg, wgk, rule.wck, buf)
+        }
+    }
+}
+
+/// copy the values into the aligned chunks, padded with zeros
+fn to_aligned(values: &[f64]) -> Vec<Aligned<[f64; 4]>> {
+    values
+        .chunks(4)
+        .map(|c| {
+            let mut chunk = [0.0; 4];
+            chunk[..c.len()].copy_from_slice(c);
+            Aligned::new(chunk)
+        })
+        .collect()
+}
+
+#[inline]
+fn as_slice(chunks: &[Aligned<[f64; 4]>], len: usize) -> &[f64] {
+    debug_assert!(len <= chunks.len() * 4);
+    unsafe { core::slice::from_raw_parts(chunks.as_ptr() as *const f64, len) }
+}
+
+#[inline]
+fn as_mut_slice(chunks: &mut [Aligned<[f64; 4]>], len: usize) -> &mut [f64] {
+    debug_assert!(len <= chunks.len() * 4);
+    unsafe { core::slice::from_raw_parts_mut(chunks.as_mut_ptr() as *mut f64, len) }
+}
@@ -0,0 +1,41 @@
+use alloc::vec::Vec;
+use smallvec::SmallVec;
+
+use super::naive::qk_sum;
+use super::{GaussKronrod, QKResult};
+use crate::single::common::{IntegrandVec, Range};
+
+/// perform Gauss-Kronrod integration for each component of the vector-valued
+/// integrand
+///
+/// All the abscissae are passed to the integrand at once, so the integrand is
+/// evaluated only once at each abscissa. `buf` is used to hold the return
+/// values of the integrand.
+pub fn qk_vec<F: IntegrandVec + ?Sized>(
+    f: &mut F,
+    range: &Range,
+    rule: GaussKronrod,
+    dim: usize,
+    buf: &mut Vec<f64>,
+) -> Vec<QKResult> {
+    debug_assert!(range.begin.is_finite() && range.end.is_finite());
+
+    let (xgk, wg, wgk, wck) = rule.coefficients();
+    let n = xgk.len();
+    let center = 0.5 * (range.begin + range.end);
+    let half_length = 0.5 * (range.end - range.begin);
+
+    let mut xs: SmallVec<[f64; 64]> = SmallVec::with_capacity(2 * n + 1);
+    xs.extend(xgk.iter().map(|&x| center - half_length * x));
+    xs.extend(xgk.iter().map(|&x| center + half_length * x));
+    xs.push(center);
+
+    // values at the j-th abscissa are stored in buf[j * dim..(j + 1) * dim]
+    buf.clear();
+    buf.resize((2 * n + 1) * dim, 0.0);
+    f.apply_to_slice(&xs, buf);
+
+    (0..dim)
+        .map(|k| qk_sum(|j| buf[j * dim + k], half_length, wg, wgk, wck))
+        .collect()
+}
@@ -1,14 +1,14 @@
 use core::mem::MaybeUninit;
 use core::ops::{Deref, DerefMut};
+use smallvec::SmallVec;
 
-use crate::single::common::{Integrand, Range};
-
-#[cfg(not(feature = "std"))]
 use crate::float::Float;
+use crate::single::common::{
+    Integrand, IntegrandComplex, IntegrandVec, Range, RangeTransform, TryIntegrand,
+};
 
 pub trait Array {
     type Item;
-    const CAPACITY: usize;
 
     fn as_slice(&self) -> &[Self::Item];
     fn as_mut_slice(&mut self) -> &mut [Self::Item];
@@ -19,7 +19,6 @@ macro_rules! impl_array {
         $(
             impl Array for [f64; $N] {
                 type Item = f64;
-                const CAPACITY: usize = $N;
 
                 #[inline]
                 fn as_slice(&self) -> &[f64] {
@@ -37,9 +36,24 @@ macro_rules! impl_array {
 
 impl_array!(4 6 8 10 12 14 16 20 24 28);
 
+impl<T> Array for [T] {
+    type Item = T;
+
+    #[inline]
+    fn as_slice(&self) -> &[T] {
+        self
+    }
+
+    #[inline]
+    fn as_mut_slice(&mut self) -> &mut [T] {
+        self
+    }
+}
+
+#[derive(Clone)]
 #[repr(align(32))]
 pub struct Aligned<T: ?Sized> {
-    value: T,
+    pub(crate) value: T,
 }
 
 impl<T> Aligned<T> {
@@ -71,55 +85,186 @@ impl<T: ?Sized> DerefMut for Aligned<T> {
     }
 }
 
-pub struct IntegrandWrapper<'a, F: Integrand + ?Sized + 'a> {
+/// Integrand over (-1, 1) transformed from the infinite range
+///
+/// If `transform` is `None`, the integrand is evaluated as it is.
+pub struct IntegrandWrapper<'a, F: ?Sized + 'a> {
     pub inner: &'a mut F,
-    pub transform: bool,
+    pub transform: Option<&'a RangeTransform>,
 }
 
-impl<'a, F: Integrand + ?Sized + 'a> Integrand for IntegrandWrapper<'a, F> {
+impl<'a, T: Float, F: Integrand<T> + ?Sized + 'a> Integrand<T> for IntegrandWrapper<'a, F> {
     #[inline]
-    fn apply(&mut self, x: f64) -> f64 {
-        if self.transform {
-            let coef = 1. / (1. - x.abs());
-            let x2 = x * coef;
-            self.inner.apply(x2) * coef * coef
-        } else {
-            self.inner.apply(x)
+    fn apply(&mut self, x: T) -> T {
+        match self.transform {
+            Some(transform) => {
+                let (x2, coef1, coef2) = transform.forward(x);
+                self.inner.apply(x2) * coef1 * coef2
+            }
+            None => self.inner.apply(x),
         }
     }
 
+    fn apply_to_slice(&mut self, s: &mut [T]) {
+        match self.transform {
+            Some(transform) => {
+                let coefs: SmallVec<[(T, T); 64]> = s
+                    .iter_mut()
+                    .map(|x| {
+                        let (x2, coef1, coef2) = transform.forward(*x);
+                        *x = x2;
+                        (coef1, coef2)
+                    })
+                    .collect();
+                self.inner.apply_to_slice(s);
+                s.iter_mut()
+                    .zip(coefs)
+                    .for_each(|(y, (coef1, coef2))| *y = *y * coef1 * coef2);
+            }
+            None => self.inner.apply_to_slice(s),
+        }
+    }
+}
+
+/// Vector-valued integrand over (-1, 1) transformed from the infinite range
+pub struct IntegrandVecWrapper<'a, F: IntegrandVec + ?Sized + 'a> {
+    pub inner: &'a mut F,
+    pub transform: Option<&'a RangeTransform>,
+}
+
+impl<'a, F: IntegrandVec + ?Sized + 'a> IntegrandVec for IntegrandVecWrapper<'a, F> {
     #[inline]
-    fn apply_to_slice(&mut self, s: &mut [f64]) {
-        if self.transform {
-            s.iter_mut().for_each(|x| {
-                let coef = 1. / (1. - x.abs());
-                let x2 = *x * coef;
-                *x = self.inner.apply(x2) * coef * coef
-            })
-        } else {
-            s.iter_mut().for_each(|x| *x = self.inner.apply(*x))
+    fn apply(&mut self, x: f64, out: &mut [f64]) {
+        match self.transform {
+            Some(transform) => {
+                let (x2, coef1, coef2) = transform.forward(x);
+                self.inner.apply(x2, out);
+                out.iter_mut().for_each(|y| *y = *y * coef1 * coef2);
+            }
+            None => self.inner.apply(x, out),
+        }
+    }
+
+    fn apply_to_slice(&mut self, xs: &[f64], out: &mut [f64]) {
+        match self.transform {
+            Some(transform) => {
+                let (xs2, coefs): (SmallVec<[f64; 64]>, SmallVec<[(f64, f64); 64]>) = xs
+                    .iter()
+                    .map(|&x| {
+                        let (x2, coef1, coef2) = transform.forward(x);
+                        (x2, (coef1, coef2))
+                    })
+                    .unzip();
+                self.inner.apply_to_slice(&xs2, out);
+                let dim = out.len() / xs.len().max(1);
+                for (j, (coef1, coef2)) in coefs.into_iter().enumerate() {
+                    out[j * dim..(j + 1) * dim]
+                        .iter_mut()
+                        .for_each(|y| *y = *y * coef1 * coef2);
+                }
+            }
+            None => self.inner.apply_to_slice(xs, out),
+        }
+    }
+}
+
+/// Complex-valued integrand regarded as the vector of the real and imaginary
+/// parts
+pub struct ComplexWrapper<'a, F: IntegrandComplex + ?Sized + 'a> {
+    pub inner: &'a mut F,
+}
+
+impl<'a, F: IntegrandComplex + ?Sized + 'a> IntegrandVec for ComplexWrapper<'a, F> {
+    #[inline]
+    fn apply(&mut self, x: f64, out: &mut [f64]) {
+        let (re, im) = self.inner.apply(x);
+        out[0] = re;
+        out[1] = im;
+    }
+}
+
+/// Fallible integrand regarded as the infallible one
+///
+/// The first error is stored with the point where it occured, and `NAN` is
+/// returned without calling the inner function afterwards.
+pub struct TryIntegrandWrapper<'a, F: TryIntegrand<T> + ?Sized + 'a, T: Float = f64> {
+    pub inner: &'a mut F,
+    /// the number of points at which the inner function was evaluated
+    pub nevals: usize,
+    pub failure: Option<(T, F::Error)>,
+}
+
+impl<'a, T: Float, F: TryIntegrand<T> + ?Sized + 'a> TryIntegrandWrapper<'a, F, T> {
+    #[inline]
+    pub fn new(inner: &'a mut F) -> Self {
+        Self {
+            inner,
+            nevals: 0,
+            failure: None,
         }
     }
 }
 
+impl<'a, T: Float, F: TryIntegrand<T> + ?Sized + 'a> Integrand<T>
+    for TryIntegrandWrapper<'a, F, T>
+{
+    #[inline]
+    #[allow(clippy::eq_op)]
+    fn apply(&mut self, x: T) -> T {
+        if self.failure.is_some() {
+            return T::ZERO / T::ZERO;
+        }
+
+        self.nevals += 1;
+        match self.inner.try_apply(x) {
+            Ok(y) => y,
+            Err(e) => {
+                self.failure = Some((x, e));
+                T::ZERO / T::ZERO
+            }
+        }
+    }
+
+    #[allow(clippy::eq_op)]
+    fn apply_to_slice(&mut self, s: &mut [T]) {
+        if self.failure.is_none() {
+            // the abscissae are overwritten by the function values
+            let xs: SmallVec<[T; 64]> = s.iter().cloned().collect();
+            match self.inner.try_apply_to_slice(s) {
+                Ok(()) => {
+                    self.nevals += s.len();
+                    return;
+                }
+                Err((i, e)) => {
+                    self.nevals += i + 1;
+                    self.failure = Some((xs[i], e));
+                }
+            }
+        }
+
+        s.iter_mut().for_each(|y| *y = T::ZERO / T::ZERO);
+    }
+}
+
 /// 区間幅が中央値の値に対して狭すぎる場合trueを返す
 ///
 /// 例えば、区間[1e20, 1e20 + 1]は浮動小数点の桁落ちにより台形公式による分割を
 /// 行ったときのxの値の誤差が大きくなるため、これ以上分割できない
 #[inline]
-pub fn subrange_too_small(a1: f64, a2: f64, b2: f64) -> bool {
-    let tmp = (1. + 100. * core::f64::EPSILON) * (a2.abs() + 1000. * core::f64::MIN_POSITIVE);
+pub fn subrange_too_small<T: Float>(a1: T, a2: T, b2: T) -> bool {
+    let tmp = (T::ONE + T::from_f64(100.) * T::EPSILON)
+        * (a2.abs() + T::from_f64(1000.) * T::MIN_POSITIVE);
 
     // a1, b2は昇順とは限らないため両方チェックする:
     a1.abs() <= tmp && b2.abs() <= tmp
 }
 
 #[inline]
-pub fn rescale_error(mut err: f64, result_abs: f64, result_asc: f64) -> f64 {
+pub fn rescale_error<T: Float>(mut err: T, result_abs: T, result_asc: T) -> T {
     err = err.abs();
 
-    if result_asc != 0.0 && err != 0.0 {
-        let mut scale = 200.0 * err;
+    if result_asc != T::ZERO && err != T::ZERO {
+        let mut scale = T::from_f64(200.0) * err;
 
         if scale < result_asc {
             scale /= result_asc;
@@ -129,8 +274,9 @@ pub fn rescale_error(mut err: f64, result_abs: f64, result_asc: f64) -> f64 {
         }
     }
 
-    if result_abs > core::f64::MIN_POSITIVE / (50.0 * core::f64::EPSILON) {
-        let min_err = 50.0 * core::f64::EPSILON * result_abs;
+    let fifty_eps = T::from_f64(50.0) * T::EPSILON;
+    if result_abs > T::MIN_POSITIVE / fifty_eps {
+        let min_err = fifty_eps * result_abs;
 
         if min_err > err {
             err = min_err;
@@ -143,13 +289,13 @@ pub fn rescale_error(mut err: f64, result_abs: f64, result_asc: f64) -> f64 {
 /// Compare the integral of f(x) with the integral of |f(x)| to determine if
 /// f(x) covers both positive and negative values
 #[inline]
-pub fn test_positivity(result: f64, resabs: f64) -> bool {
-    result.abs() >= (1.0 - 50.0 * core::f64::EPSILON) * resabs
+pub fn test_positivity<T: Float>(result: T, resabs: T) -> bool {
+    result.abs() >= (T::ONE - T::from_f64(50.0) * T::EPSILON) * resabs
 }
 
 #[inline]
-pub fn bisect(range: &Range) -> (Range, Range) {
-    let center = (range.begin + range.end) * 0.5;
+pub fn bisect<T: Float>(range: &Range<T>) -> (Range<T>, Range<T>) {
+    let center = (range.begin + range.end) * T::from_f64(0.5);
     unsafe {
         (
             Range::new_unchecked(range.begin, center),
@@ -159,23 +305,22 @@ pub fn bisect(range: &Range) -> (Range, Range) {
 }
 
 #[inline]
-pub fn transform_point(x: f64) -> f64 {
-    if x == core::f64::NEG_INFINITY {
-        -1.0
-    } else if x == core::f64::INFINITY {
-        1.0
-    } else {
-        x / (1.0 + x.abs())
-    }
+pub fn transform_point<T: Float>(x: T, transform: &RangeTransform) -> T {
+    transform.inverse(x)
 }
 
 // transform infinite range to finite
 #[inline]
-pub fn transform_range(range: &Range) -> Range {
-    unsafe { Range::new_unchecked(transform_point(range.begin), transform_point(range.end)) }
+pub fn transform_range<T: Float>(range: &Range<T>, transform: &RangeTransform) -> Range<T> {
+    unsafe {
+        Range::new_unchecked(
+            transform_point(range.begin, transform),
+            transform_point(range.end, transform),
+        )
+    }
 }
 
-pub fn insert_sort<F: FnMut(f64, f64) -> bool>(s: &mut [f64], is_less: &mut F) {
+pub fn insert_sort<T: Copy, F: FnMut(T, T) -> bool>(s: &mut [T], is_less: &mut F) {
     unsafe {
         let sp_begin = s.as_mut_ptr();
         let sp_end = sp_begin.add(s.len());
@@ -201,7 +346,7 @@ pub fn insert_sort<F: FnMut(f64, f64) -> bool>(s: &mut [f64], is_less: &mut F) {
                 ip = ip.sub(1);
             }
 
-            let count = ((sp as usize) - (insert_pos as usize)) / 8;
+            let count = sp.offset_from(insert_pos) as usize;
             core::ptr::copy(insert_pos, insert_pos.add(1), count);
             *insert_pos = target;
 
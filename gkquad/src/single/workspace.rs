@@ -1,23 +1,24 @@
 use alloc::vec::Vec;
 
 use super::Range;
+use crate::float::Float;
 
 /// Representing the subrange and the integral estimates
 #[derive(Clone, Debug)]
-pub struct SubRangeInfo {
+pub struct SubRangeInfo<T = f64> {
     /// Subrange
-    pub range: Range,
+    pub range: Range<T>,
     /// Result of Gauss-Kronrod integration for subrange
-    pub estimate: f64,
+    pub estimate: T,
     /// Absolute estimation error
-    pub delta: f64,
+    pub delta: T,
     /// Recursion depth of subrange
     pub level: usize,
 }
 
-impl SubRangeInfo {
+impl<T: Float> SubRangeInfo<T> {
     #[inline]
-    pub fn new(range: Range, estimate: f64, delta: f64, level: usize) -> Self {
+    pub fn new(range: Range<T>, estimate: T, delta: T, level: usize) -> Self {
         Self {
             range,
             estimate,
@@ -29,7 +30,7 @@ impl SubRangeInfo {
 
 /// handles the memory for the subrange ranges, results, and error estimates
 #[derive(Clone, Debug)]
-pub struct WorkSpace {
+pub struct WorkSpace<T = f64> {
     /// maxerr = `subranges[order[nrmax]].delta`. nrmax is normally 0 but will be
     /// positive if subdivision increased error estimate
     pub nrmax: usize,
@@ -39,7 +40,7 @@ pub struct WorkSpace {
     pub maximum_level: usize,
     /// vector of dimension at least limit, the elements of which are the
     /// subranges
-    pub subranges: Vec<SubRangeInfo>,
+    pub subranges: Vec<SubRangeInfo<T>>,
     /// vector of dimension at least limit, the first k elements of which are
     /// indices to the error estimates over the subranges, such that
     /// `subranges[order[0]].delta, ..., subranges[order[n - 1]].delta`
@@ -47,9 +48,9 @@ pub struct WorkSpace {
     pub order: Vec<usize>,
 }
 
-impl WorkSpace {
+impl<T: Float> WorkSpace<T> {
     #[inline]
-    pub const fn new() -> WorkSpace {
+    pub const fn new() -> WorkSpace<T> {
         WorkSpace {
             nrmax: 0,
             i: 0,
@@ -60,7 +61,7 @@ impl WorkSpace {
     }
 
     #[inline]
-    pub fn with_capacity(n: usize) -> WorkSpace {
+    pub fn with_capacity(n: usize) -> WorkSpace<T> {
         WorkSpace {
             nrmax: 0,
             i: 0,
@@ -99,7 +100,7 @@ impl WorkSpace {
     }
 
     #[inline]
-    pub fn push(&mut self, subrange: SubRangeInfo) {
+    pub fn push(&mut self, subrange: SubRangeInfo<T>) {
         self.subranges.push(subrange);
         self.order.push(self.order.len());
     }
@@ -138,7 +139,7 @@ impl WorkSpace {
     }
 
     /// append the newly-created subranges to the list
-    pub fn update(self: &mut WorkSpace, s1: SubRangeInfo, s2: SubRangeInfo) {
+    pub fn update(&mut self, s1: SubRangeInfo<T>, s2: SubRangeInfo<T>) {
         debug_assert_eq!(self.subranges.len(), self.order.len());
         let new_level = self.subranges[self.i].level + 1;
 
@@ -159,6 +160,31 @@ impl WorkSpace {
         debug_assert_eq!(self.subranges.len(), self.order.len());
     }
 
+    /// replace the current subrange without bisecting it, and move it to the
+    /// position of the new error estimate in the list
+    pub(crate) fn replace(&mut self, subrange: SubRangeInfo<T>) {
+        debug_assert_eq!(self.subranges.len(), self.order.len());
+        let i = self.i;
+        let delta = subrange.delta;
+        self.subranges[i] = subrange;
+
+        let subranges = &self.subranges;
+        let order = &mut self.order;
+        let mut k = self.nrmax;
+
+        while k > 0 && delta > subranges[order[k - 1]].delta {
+            order[k] = order[k - 1];
+            k -= 1;
+        }
+        while k + 1 < order.len() && delta < subranges[order[k + 1]].delta {
+            order[k] = order[k + 1];
+            k += 1;
+        }
+        order[k] = i;
+
+        self.i = order[self.nrmax];
+    }
+
     #[inline(always)]
     fn qpsrt(&mut self) {
         let last = self.size() - 1;
@@ -266,20 +292,22 @@ impl WorkSpace {
 
     /// retrieve the next subrange
     #[inline]
-    pub fn get(&self) -> &SubRangeInfo {
+    pub fn get(&self) -> &SubRangeInfo<T> {
         &self.subranges[self.i]
     }
 
     /// calculate the sum of integral estimates for all subranges
     #[inline]
-    pub fn sum_results(self: &WorkSpace) -> f64 {
-        self.subranges.iter().map(|s| s.estimate).sum()
+    pub fn sum_results(&self) -> T {
+        self.subranges
+            .iter()
+            .fold(T::ZERO, |acc, s| acc + s.estimate)
     }
 }
 
-impl Default for WorkSpace {
+impl<T: Float> Default for WorkSpace<T> {
     #[inline]
-    fn default() -> WorkSpace {
+    fn default() -> WorkSpace<T> {
         WorkSpace::new()
     }
 }
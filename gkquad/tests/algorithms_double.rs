@@ -5,9 +5,9 @@ use common::functions::*;
 use gkquad::double::algorithm::*;
 use gkquad::double::range::*;
 use gkquad::double::Integrator2;
-use gkquad::single::Range;
-use gkquad::RuntimeError;
+use gkquad::single::{GaussKronrod, Range, RangeTransform};
 use gkquad::Tolerance::{self, *};
+use gkquad::{RuntimeError, Solution};
 
 struct Expect {
     value: f64,
@@ -193,3 +193,156 @@ fn qagp_gp3() {
         expect,
     )
 }
+
+#[test]
+fn qags_g5_range_transform() {
+    let transform = RangeTransform::Rational {
+        center: 1e4,
+        scale: 10.0,
+    };
+    let range = Rectangle::from((.., ..));
+    let result = Integrator2::with_algorithm(g5 as fn(f64, f64) -> f64, QAGS2::new())
+        .tolerance(Relative(1e-10))
+        .max_evals(100000)
+        .range_transform(transform)
+        .run(range)
+        .unwrap();
+    assert_rel!(result.estimate, 100. * std::f64::consts::PI, 1e-10);
+}
+
+// both the inner and the outer integrals use the given rule, so each inner
+// integral takes a multiple of 41 evaluations
+#[test]
+fn rule_k41() {
+    fn run<A: Algorithm2<fn(f64, f64) -> f64, Rectangle>>(algorithm: A) -> Solution {
+        let range = Rectangle::new(0., 1., 0., 1.).unwrap();
+        let result = Integrator2::with_algorithm(g1 as fn(f64, f64) -> f64, algorithm)
+            .tolerance(Relative(1e-10))
+            .run(range)
+            .unwrap();
+        assert_rel!(result.estimate, 0.75, 1e-14);
+        assert_eq!(result.nevals % 41, 0);
+        result
+    }
+
+    let rule = GaussKronrod::K41;
+    run(QAG2::with_rule(rule));
+    run(QAGP2::with_rule(rule));
+    let result = run(QAGS2::with_rule(rule));
+    assert_eq!(result, run(AUTO2::with_rule(rule)));
+}
+
+#[cfg(feature = "rayon")]
+#[test]
+fn parallel_qags_same_as_sequential() {
+    let yrange = |x: f64| Range::new(0.0, x).unwrap();
+    let range = DynamicY::new(0.0, 1.0, yrange).unwrap();
+    let expect = Integrator2::with_algorithm(g1 as fn(f64, f64) -> f64, QAGS2::new())
+        .tolerance(Relative(1e-10))
+        .run(range.clone());
+    let result = Integrator2::with_algorithm(g1 as fn(f64, f64) -> f64, ParallelQAGS2::new())
+        .tolerance(Relative(1e-10))
+        .run(range);
+    assert_eq!(result, expect);
+
+    let range = Rectangle::from((.., ..));
+    let expect = Integrator2::with_algorithm(g5 as fn(f64, f64) -> f64, QAGS2::new())
+        .tolerance(Relative(1e-10))
+        .max_evals(100000)
+        .run(range.clone());
+    let result = Integrator2::with_algorithm(g5 as fn(f64, f64) -> f64, ParallelQAGS2::new())
+        .tolerance(Relative(1e-10))
+        .max_evals(100000)
+        .run(range);
+    assert_eq!(result, expect);
+}
+
+#[cfg(feature = "rayon")]
+#[test]
+fn parallel_qagp_same_as_sequential() {
+    let range = Rectangle::new(-1.0, 1.0, -1.0, 1.0).unwrap();
+    let expect = Integrator2::with_algorithm(gp1 as fn(f64, f64) -> f64, QAGP2::new())
+        .tolerance(Relative(1e-6))
+        .max_evals(200000)
+        .points(&[(0.0, 0.0)])
+        .run(range.clone());
+    let result = Integrator2::with_algorithm(gp1 as fn(f64, f64) -> f64, ParallelQAGP2::new())
+        .tolerance(Relative(1e-6))
+        .max_evals(200000)
+        .points(&[(0.0, 0.0)])
+        .run(range);
+    assert_eq!(result, expect);
+}
+
+#[cfg(feature = "rayon")]
+#[test]
+fn parallel_rule_k41() {
+    let range = Rectangle::new(0., 1., 0., 1.).unwrap();
+    let rule = GaussKronrod::K41;
+    let expect = Integrator2::with_algorithm(g1 as fn(f64, f64) -> f64, QAGS2::with_rule(rule))
+        .tolerance(Relative(1e-10))
+        .run(range.clone());
+    let result =
+        Integrator2::with_algorithm(g1 as fn(f64, f64) -> f64, ParallelQAGS2::with_rule(rule))
+            .tolerance(Relative(1e-10))
+            .run(range.clone());
+    assert_eq!(result, expect);
+    assert_eq!(result.unwrap().nevals % 41, 0);
+
+    let expect = Integrator2::with_algorithm(g1 as fn(f64, f64) -> f64, QAGP2::with_rule(rule))
+        .tolerance(Relative(1e-10))
+        .run(range.clone());
+    let result =
+        Integrator2::with_algorithm(g1 as fn(f64, f64) -> f64, ParallelQAGP2::with_rule(rule))
+            .tolerance(Relative(1e-10))
+            .run(range);
+    assert_eq!(result, expect);
+}
+
+// the inner integrals evaluated concurrently share the remaining budget
+#[cfg(feature = "rayon")]
+#[test]
+fn parallel_max_evals() {
+    let yrange = |x: f64| {
+        let ymax = (1.0 - x * x).sqrt();
+        Range::new(-ymax, ymax).unwrap()
+    };
+    let range = DynamicY::new(-1.0, 1.0, yrange).unwrap();
+    let max_evals = 5000;
+    let result = Integrator2::with_algorithm(gp3 as fn(f64, f64) -> f64, ParallelQAGS2::new())
+        .tolerance(Absolute(1e-10))
+        .max_evals(max_evals)
+        .run(range.clone());
+    assert_eq!(
+        result.as_ref().err(),
+        Some(&RuntimeError::InsufficientIteration)
+    );
+    assert!(unsafe { result.unwrap_unchecked() }.nevals <= max_evals);
+
+    let result = Integrator2::with_algorithm(gp3 as fn(f64, f64) -> f64, ParallelQAGP2::new())
+        .tolerance(Absolute(1e-10))
+        .max_evals(max_evals)
+        .points(&[(0.0, 0.0)])
+        .run(range);
+    assert_eq!(
+        result.as_ref().err(),
+        Some(&RuntimeError::InsufficientIteration)
+    );
+    assert!(unsafe { result.unwrap_unchecked() }.nevals <= max_evals);
+}
+
+#[cfg(feature = "rayon")]
+#[test]
+fn parallel_qagp_error() {
+    let yrange = |x: f64| {
+        let ymax = (1.0 - x * x).sqrt();
+        Range::new(-ymax, ymax).unwrap()
+    };
+    let range = DynamicY::new(-1.0, 1.0, yrange).unwrap();
+    let result = Integrator2::with_algorithm(gp3 as fn(f64, f64) -> f64, ParallelQAGP2::new())
+        .tolerance(Absolute(1e-5))
+        .max_evals(100000)
+        .points(&[(0.0, 0.0)])
+        .run(range);
+    assert_eq!(result.err(), Some(RuntimeError::InsufficientIteration));
+}
@@ -2,12 +2,16 @@
 
 #[macro_use]
 mod common;
+use common::double_double::DoubleDouble;
 use common::functions::*;
 
 use gkquad::single::algorithm::*;
-use gkquad::single::{Integrator, WorkSpace};
-use gkquad::RuntimeError;
+use gkquad::single::{
+    BatchIntegrand, GaussKronrod, IntegrandVec, IntegrationConfig, Integrator, Points, Range,
+    RangeTransform, TryIntegrand, WorkSpace,
+};
 use gkquad::Tolerance::{self, *};
+use gkquad::{Float, RuntimeError, TryIntegrationResult};
 
 trait AlgorithmWithWorkSpace {
     fn with_workspace(ws: &mut WorkSpace) -> Self;
@@ -80,6 +84,34 @@ fn test_algorithm<A: Algorithm<fn(f64) -> f64> + AlgorithmWithWorkSpace>(
     }
 }
 
+/// Test the algorithm which cannot be constructed only from workspace
+#[allow(dead_code)]
+fn test_algorithm_with<A: Algorithm<fn(f64) -> f64>>(
+    algorithm: A,
+    f: fn(f64) -> f64,
+    a: f64,
+    b: f64,
+    tol: Tolerance,
+    expect: Expect,
+) {
+    let mut integrator = Integrator::with_algorithm(f, algorithm).tolerance(tol);
+    let result = integrator.run(a..b);
+    assert_eq!(result.as_ref().err(), expect.error.as_ref());
+
+    let result = unsafe { result.unwrap_unchecked() };
+    assert_rel!(result.estimate, expect.value, 1e-15);
+    assert_rel!(result.delta, expect.delta, 1e-7);
+    assert_eq!(result.nevals, expect.nevals);
+
+    let result = integrator.run(b..a);
+    assert_eq!(result.as_ref().err(), expect.error.as_ref());
+
+    let result = unsafe { result.unwrap_unchecked() };
+    assert_rel!(result.estimate, -expect.value, 1e-15);
+    assert_rel!(result.delta, expect.delta, 1e-7);
+    assert_eq!(result.nevals, expect.nevals);
+}
+
 #[test]
 #[ignore]
 fn qag_f1_15pt() {
@@ -181,6 +213,44 @@ fn qags_f4() {
     test_algorithm::<QAGS>(f4, 1.0, 1000.0, &[], Absolute(1e-7), expect);
 }
 
+#[test]
+fn qags_f1_k41() {
+    let expect = Expect {
+        value: 7.716049382712836e-2,
+        delta: 4.428188889970076e-12,
+        order: &[],
+        nevals: 123,
+        error: None,
+    };
+    let algorithm = QAGS::with_rule(GaussKronrod::K41);
+    test_algorithm_with(algorithm, f1, 0.0, 1.0, Relative(1e-10), expect);
+}
+
+#[test]
+fn auto_k41() {
+    let rule = GaussKronrod::K41;
+    let inf = std::f64::INFINITY;
+
+    let result = Integrator::with_algorithm(f15 as fn(f64) -> f64, AUTO::with_rule(rule))
+        .run(0.0..inf)
+        .unwrap();
+    let expect = Integrator::with_algorithm(f15 as fn(f64) -> f64, QAGS::with_rule(rule))
+        .run(0.0..inf)
+        .unwrap();
+    assert_eq!(result, expect);
+
+    let result = Integrator::with_algorithm(f1 as fn(f64) -> f64, AUTO::with_rule(rule))
+        .points(&[0.5])
+        .run(0.0..1.0)
+        .unwrap();
+    let expect = Integrator::with_algorithm(f1 as fn(f64) -> f64, QAGP::with_rule(rule))
+        .points(&[0.5])
+        .run(0.0..1.0)
+        .unwrap();
+    assert_eq!(result, expect);
+    assert_eq!(result.nevals % 41, 0);
+}
+
 #[test]
 fn qagp_f5() {
     let expect = Expect {
@@ -216,3 +286,857 @@ fn qagp_f7() {
     };
     test_algorithm::<QAGP>(f7, -1., 1., &[0.], Absolute(1e-10), expect);
 }
+
+#[test]
+fn qng_f1_21pt() {
+    let expect = Expect {
+        value: 7.716049379303085e-2,
+        delta: 9.424302194248483e-8,
+        order: &[],
+        nevals: 21,
+        error: None,
+    };
+    test_algorithm_with(QNG::new(), f1, 0.0, 1.0, Relative(1e-1), expect);
+}
+
+#[test]
+fn qng_f1_43pt() {
+    let expect = Expect {
+        value: 7.716049382706505e-2,
+        delta: 2.666891413688592e-12,
+        order: &[],
+        nevals: 43,
+        error: None,
+    };
+    test_algorithm_with(QNG::new(), f1, 0.0, 1.0, Relative(1e-9), expect);
+}
+
+#[test]
+fn qng_f1_87pt() {
+    let expect = Expect {
+        value: 7.716049382716030e-2,
+        delta: 8.566535680046933e-16,
+        order: &[],
+        nevals: 87,
+        error: None,
+    };
+    test_algorithm_with(QNG::new(), f1, 0.0, 1.0, Relative(1e-13), expect);
+}
+
+#[test]
+#[cfg(feature = "std")]
+fn qawo_f8() {
+    let expect = Expect {
+        value: -1.2813684839916742e-1,
+        delta: 1.2823075934420558e-13,
+        order: &[],
+        nevals: 425,
+        error: None,
+    };
+    let algorithm = QAWO::new(10. * std::f64::consts::PI, Oscillation::Sine);
+    test_algorithm_with(algorithm, f8, 0., 1., Relative(1e-7), expect);
+}
+
+#[test]
+#[cfg(feature = "std")]
+fn qawf_f9() {
+    let expect = Expect {
+        value: 9.9999999992797939e-1,
+        delta: 3.9051730627015502e-8,
+        order: &[],
+        nevals: 700,
+        error: None,
+    };
+    let algorithm = QAWF::new(std::f64::consts::FRAC_PI_2, Oscillation::Cosine);
+    test_algorithm_with(
+        algorithm,
+        f9,
+        0.,
+        std::f64::INFINITY,
+        Absolute(1e-7),
+        expect,
+    );
+}
+
+#[test]
+#[cfg(feature = "std")]
+fn qaws_f10() {
+    let expect = Expect {
+        value: -1.8927518534894017e-1,
+        delta: 1.1290995975557558e-8,
+        order: &[],
+        nevals: 350,
+        error: None,
+    };
+    let weight = AlgebraicLogWeight::new(0., 0., 1, 0).unwrap();
+    let algorithm = QAWS::new(weight);
+    test_algorithm_with(algorithm, f10, 0., 1., Relative(1e-7), expect);
+}
+
+#[test]
+#[cfg(feature = "std")]
+fn qawc_f11() {
+    let expect = Expect {
+        value: -8.994400695773012e-2,
+        delta: 2.5014921831850723e-8,
+        order: &[],
+        nevals: 225,
+        error: None,
+    };
+    test_algorithm_with(QAWC::new(0.), f11, -1., 5., Relative(1e-3), expect);
+}
+
+#[test]
+#[cfg(feature = "std")]
+fn qawc_pole_on_boundary() {
+    let mut integrator = Integrator::with_algorithm(f11 as fn(f64) -> f64, QAWC::new(0.));
+    let result = integrator.run(0.0..1.0);
+    assert_eq!(result.err(), Some(RuntimeError::PoleOnBoundary));
+}
+
+#[test]
+#[cfg(feature = "std")]
+fn tanh_sinh_f2() {
+    let expect = Expect {
+        value: 1.0000000000000006e2,
+        delta: 2.2737367544323206e-13,
+        order: &[],
+        nevals: 74,
+        error: None,
+    };
+    test_algorithm_with(TanhSinh::new(), f2, 0., 1., Relative(1e-10), expect);
+}
+
+#[test]
+#[cfg(feature = "std")]
+fn tanh_sinh_f12() {
+    let expect = Expect {
+        value: 1.7724538509055154,
+        delta: 1.4797052472204086e-12,
+        order: &[],
+        nevals: 99,
+        error: None,
+    };
+    let (a, b) = (std::f64::NEG_INFINITY, std::f64::INFINITY);
+    test_algorithm_with(TanhSinh::new(), f12, a, b, Absolute(1e-10), expect);
+}
+
+#[test]
+#[cfg(feature = "std")]
+fn tanh_sinh_f15() {
+    let expect = Expect {
+        value: 5.661543487607878,
+        delta: 0.0,
+        order: &[],
+        nevals: 82,
+        error: None,
+    };
+    let b = std::f64::INFINITY;
+    test_algorithm_with(TanhSinh::new(), f15, 0.0, b, Relative(1e-10), expect);
+}
+
+// ∫ (1 + x²)^-0.6 dx (-∞ < x < ∞) = √π Γ(0.1) / Γ(0.6) = 11.32308697521575
+#[test]
+#[cfg(feature = "std")]
+fn double_exponential_f15() {
+    let expect = Expect {
+        value: 11.323086975215755,
+        delta: 0.0,
+        order: &[],
+        nevals: 95,
+        error: None,
+    };
+    let (a, b) = (std::f64::NEG_INFINITY, std::f64::INFINITY);
+    test_algorithm_with(DoubleExponential::new(), f15, a, b, Relative(1e-10), expect);
+}
+
+// the terms at the truncation points become significant as the estimate
+// decreases through the cancellation
+#[test]
+#[cfg(feature = "std")]
+fn double_exponential_cancellation() {
+    let f = |x: f64| (x - 1.) * (-x).exp() + 1e-6 / (1. + x * x);
+    let result = Integrator::with_algorithm(f, DoubleExponential::new())
+        .tolerance(Relative(1e-10))
+        .run(0.0..std::f64::INFINITY)
+        .unwrap();
+    assert_rel!(result.estimate, 1e-6 * std::f64::consts::FRAC_PI_2, 1e-10);
+}
+
+#[test]
+#[cfg(feature = "std")]
+fn double_exponential_finite_range() {
+    let mut integrator =
+        Integrator::with_algorithm(f15 as fn(f64) -> f64, DoubleExponential::new());
+    let result = integrator.run(0.0..1.0);
+    assert_eq!(result.err(), Some(RuntimeError::InvalidRange));
+}
+
+#[test]
+#[cfg(feature = "std")]
+fn clenshaw_curtis_f3() {
+    let expect = Expect {
+        value: -7.238969575482962e-1,
+        delta: 2.220446049250313e-16,
+        order: &[],
+        nevals: 65,
+        error: None,
+    };
+    test_algorithm_with(
+        ClenshawCurtis::new(),
+        f3,
+        0.3,
+        2.71,
+        Relative(1e-12),
+        expect,
+    );
+}
+
+// the subrange with the largest error must be bisected first until the end
+#[test]
+#[cfg(feature = "std")]
+fn clenshaw_curtis_largest_error_first() {
+    let mut ws = WorkSpace::new();
+    let result = Integrator::with_algorithm(
+        |x: f64| (x - 0.3).abs().sqrt() + (x - 1.7).abs().sqrt() + (x - 1.0).abs(),
+        ClenshawCurtis::with_workspace(&mut ws),
+    )
+    .tolerance(Absolute(1e-13))
+    .max_evals(10000)
+    .run(0.1..2.0);
+    assert!(!result.has_err());
+
+    let largest = ws.subranges.iter().map(|s| s.delta).fold(0.0, f64::max);
+    assert_eq!(ws.get().delta, largest);
+}
+
+// f1 returns NaN at x = 0
+#[test]
+#[cfg(feature = "std")]
+fn cquad_f1() {
+    let expect = Expect {
+        value: 7.716049382717813e-2,
+        delta: 3.650280126912451e-10,
+        order: &[],
+        nevals: 217,
+        error: None,
+    };
+    test_algorithm_with(CQUAD::new(), f1, 0.0, 1.0, Relative(1e-8), expect);
+}
+
+// the midpoints of the subranges must be evaluated, otherwise the subranges
+// are interpolated as if the integrand were not finite there
+#[test]
+#[cfg(feature = "std")]
+fn cquad_smooth_evaluates_midpoints() {
+    let mut xs = Vec::new();
+    let mut ws = WorkSpace::new();
+    let result = Integrator::with_algorithm(
+        |x: f64| {
+            xs.push(x);
+            (-100. * x * x).exp()
+        },
+        CQUAD::with_workspace(&mut ws),
+    )
+    .tolerance(Relative(1e-12))
+    .run(-1.0..1.0)
+    .unwrap();
+
+    // erf(10) = 1 within the double precision
+    let expect = std::f64::consts::PI.sqrt() / 10.;
+    assert!((result.estimate - expect).abs() <= 1e-12 * expect);
+
+    assert!(ws.subranges.iter().any(|s| s.level > 0));
+    for s in ws.subranges.iter().filter(|s| s.level > 0) {
+        let center = 0.5 * (s.range.begin + s.range.end);
+        let half_length = 0.5 * (s.range.end - s.range.begin);
+        assert!(
+            xs.iter()
+                .any(|&x| (x - center).abs() <= 1e-12 * half_length),
+            "midpoint of {:?} is not evaluated",
+            s.range
+        );
+    }
+}
+
+// the subranges are selected through the ordering of the workspace, and the
+// dropped subranges are kept at its end
+#[test]
+#[cfg(feature = "std")]
+fn cquad_nan_workspace_order() {
+    let mut ws = WorkSpace::new();
+    let result = Integrator::with_algorithm(
+        |x: f64| x.sin() / x + (x - 0.3).abs().sqrt(),
+        CQUAD::with_workspace(&mut ws),
+    )
+    .tolerance(Relative(1e-10))
+    .run(-1.0..1.0)
+    .unwrap();
+
+    // Si(1) = 0.946083070367183
+    let expect = 2. * 0.946083070367183 + (2. * 1.3f64.powf(1.5) + 2. * 0.7f64.powf(1.5)) / 3.;
+    assert_rel!(result.estimate, expect, 1e-10);
+    assert_rel!(result.estimate, ws.sum_results(), 1e-14);
+
+    let mut order = ws.order.clone();
+    order.sort_unstable();
+    assert!(order.iter().copied().eq(0..ws.size()));
+    for k in 1..ws.size() {
+        let (e1, e2) = (
+            ws.subranges[ws.order[k - 1]].delta,
+            ws.subranges[ws.order[k]].delta,
+        );
+        assert!(e1 >= e2);
+    }
+    assert_eq!(ws.subranges[ws.order[ws.size() - 1]].delta, 0.0);
+}
+
+#[test]
+fn gauss_laguerre_f13() {
+    let expect = Expect {
+        value: 4.999999999999999e-1,
+        delta: 4.188305258168157e-11,
+        order: &[],
+        nevals: 56,
+        error: None,
+    };
+    let b = std::f64::INFINITY;
+    test_algorithm_with(GaussLaguerre::new(), f13, 0.0, b, Relative(1e-10), expect);
+}
+
+#[test]
+fn gauss_hermite_f13() {
+    let expect = Expect {
+        value: 1.3803884470431436,
+        delta: 1.1843193092886395e-11,
+        order: &[],
+        nevals: 24,
+        error: None,
+    };
+    let (a, b) = (std::f64::NEG_INFINITY, std::f64::INFINITY);
+    test_algorithm_with(GaussHermite::new(), f13, a, b, Relative(1e-10), expect);
+}
+
+#[test]
+#[cfg(feature = "std")]
+fn gauss_jacobi_f13() {
+    let expect = Expect {
+        value: 2.329605704310866,
+        delta: 1.7763568394002505e-15,
+        order: &[],
+        nevals: 24,
+        error: None,
+    };
+    let algorithm = GaussJacobi::new(1.5, -0.7);
+    test_algorithm_with(algorithm, f13, 0.0, 1.0, Relative(1e-10), expect);
+}
+
+#[test]
+fn gauss_lobatto_f14() {
+    let expect = Expect {
+        value: 2.777777777777778e-1,
+        delta: 3.0623579837485626e-17,
+        order: &[],
+        nevals: 313,
+        error: None,
+    };
+    test_algorithm_with(GaussLobatto::new(), f14, 0.0, 1.0, Relative(1e-10), expect);
+}
+
+#[test]
+fn romberg_f3() {
+    let expect = Expect {
+        value: -7.238969575482962e-1,
+        delta: 3.2307490016592055e-14,
+        order: &[],
+        nevals: 257,
+        error: None,
+    };
+    test_algorithm_with(Romberg::new(), f3, 0.3, 2.71, Relative(1e-10), expect);
+}
+
+// f3 is periodic with period 2π
+#[test]
+fn romberg_periodic_f3() {
+    let expect = Expect {
+        value: -1.8513312258599124e-1,
+        delta: 1.5300927191930214e-11,
+        order: &[],
+        nevals: 32,
+        error: None,
+    };
+    let b = 2. * std::f64::consts::PI;
+    test_algorithm_with(Romberg::periodic(), f3, 0.0, b, Relative(1e-10), expect);
+}
+
+#[test]
+fn auto_periodic_f3() {
+    let b = 2. * std::f64::consts::PI;
+    let result = Integrator::new(f3 as fn(f64) -> f64)
+        .tolerance(Relative(1e-10))
+        .periodic(true)
+        .run(0.0..b)
+        .unwrap();
+    assert_rel!(result.estimate, -1.8513312258599124e-1, 1e-15);
+    assert_eq!(result.nevals, 32);
+}
+
+// the integrand is wrongly declared as periodic
+#[test]
+fn auto_periodic_fallback() {
+    let result = Integrator::new(f3 as fn(f64) -> f64)
+        .tolerance(Relative(1e-10))
+        .periodic(true)
+        .run(0.3..2.71)
+        .unwrap();
+    let expect = Integrator::with_algorithm(f3 as fn(f64) -> f64, QAGS::new())
+        .tolerance(Relative(1e-10))
+        .run(0.3..2.71)
+        .unwrap();
+    assert_rel!(result.estimate, expect.estimate, 1e-12);
+
+    // at most a quarter of the evaluations is spent by the trapezoid rule
+    let max_evals = IntegrationConfig::<f64>::default().max_evals;
+    assert!(result.nevals <= expect.nevals + max_evals / 4);
+}
+
+#[test]
+fn qags_f16_range_transform() {
+    let (a, b) = (std::f64::NEG_INFINITY, std::f64::INFINITY);
+    let transform = RangeTransform::Rational {
+        center: 1e4,
+        scale: 10.0,
+    };
+    let result = Integrator::with_algorithm(f16 as fn(f64) -> f64, QAGS::new())
+        .tolerance(Relative(1e-10))
+        .range_transform(transform)
+        .run(a..b)
+        .unwrap();
+    assert_rel!(result.estimate, 10. * std::f64::consts::PI.sqrt(), 1e-12);
+}
+
+#[test]
+#[cfg(feature = "std")]
+fn qagp_f16_range_transform_exponential_tan() {
+    let transforms = [
+        RangeTransform::Exponential {
+            center: 1e4,
+            scale: 10.0,
+        },
+        RangeTransform::Tan {
+            center: 1e4,
+            scale: 10.0,
+        },
+    ];
+
+    // f16 cut off below x = 1e4 + 3, which is given as a singular point and
+    // mapped by the inverse transformation
+    let f = |x: f64| if x < 1e4 + 3. { 0.0 } else { f16(x) };
+    let c = 5. * std::f64::consts::PI.sqrt();
+    let (erfc_03, erfc_1) = (0.6713732405408726, 0.15729920705028513);
+    let (neg_inf, inf) = (std::f64::NEG_INFINITY, std::f64::INFINITY);
+    let cases = [
+        (neg_inf, inf, c * erfc_03),
+        (1e4, inf, c * erfc_03),
+        (neg_inf, 1e4 + 10., c * (erfc_03 - erfc_1)),
+    ];
+
+    for transform in transforms.iter() {
+        for &(a, b, expect) in cases.iter() {
+            let result = Integrator::with_algorithm(f, QAGP::new())
+                .tolerance(Relative(1e-10))
+                .points(&[1e4 + 3.])
+                .range_transform(transform.clone())
+                .run(a..b)
+                .unwrap();
+            assert_rel!(result.estimate, expect, 1e-12);
+        }
+    }
+}
+
+#[test]
+fn qags_vec_f1_f2_f3() {
+    let mut f = |x: f64, out: &mut [f64]| {
+        out[0] = f1(x);
+        out[1] = f2(x);
+        out[2] = f3(x);
+    };
+    let mut config = IntegrationConfig::default();
+    config.tolerance = Relative(1e-10);
+
+    let result = QAGSVec::new()
+        .integrate(&mut f, 3, &Range::new(0.0, 1.0).unwrap(), &config)
+        .unwrap();
+    let f3_expect = Integrator::with_algorithm(f3 as fn(f64) -> f64, QAGS::new())
+        .tolerance(Relative(1e-12))
+        .run(0.0..1.0)
+        .unwrap()
+        .estimate;
+    assert_rel!(result.estimate[0], 1. / (3.6 * 3.6), 1e-10);
+    assert_rel!(result.estimate[1], 100., 1e-10);
+    assert_rel!(result.estimate[2], f3_expect, 1e-10);
+    assert_eq!(result.nevals, 475);
+
+    let result2 = QAGSVec::new()
+        .integrate(&mut f, 3, &Range::new(1.0, 0.0).unwrap(), &config)
+        .unwrap();
+    for (x, y) in result.estimate.iter().zip(result2.estimate.iter()) {
+        assert_rel!(*x, -*y, 1e-15);
+    }
+    assert_eq!(result2.nevals, result.nevals);
+}
+
+#[test]
+fn qags_vec_batch() {
+    struct Batch {
+        sizes: Vec<usize>,
+    }
+
+    impl IntegrandVec for Batch {
+        fn apply(&mut self, _: f64, _: &mut [f64]) {
+            unreachable!("the integrand must be evaluated in batches")
+        }
+
+        fn apply_to_slice(&mut self, xs: &[f64], out: &mut [f64]) {
+            self.sizes.push(xs.len());
+            for (&x, out) in xs.iter().zip(out.chunks_mut(2)) {
+                out[0] = (-x * x).exp();
+                out[1] = x * x * (-x * x).exp();
+            }
+        }
+    }
+
+    let mut config = IntegrationConfig::default();
+    config.tolerance = Relative(1e-10);
+
+    let mut g = |x: f64, out: &mut [f64]| {
+        out[0] = (-x * x).exp();
+        out[1] = x * x * (-x * x).exp();
+    };
+    for range in [Range::new(0.0, 1.0), Range::new(0.0, std::f64::INFINITY)].iter() {
+        let range = range.as_ref().unwrap();
+        let mut f = Batch { sizes: Vec::new() };
+        let result = QAGSVec::new().integrate(&mut f, 2, range, &config).unwrap();
+        let expect = QAGSVec::new().integrate(&mut g, 2, range, &config).unwrap();
+        assert_eq!(result, expect);
+        assert!(f.sizes.iter().all(|&n| n == 25));
+        assert_eq!(f.sizes.iter().sum::<usize>(), result.nevals);
+    }
+}
+
+#[test]
+fn qagp_vec_f5_f14() {
+    let pts = [1. / 3., 1., std::f64::consts::SQRT_2];
+    let mut f = |x: f64, out: &mut [f64]| {
+        out[0] = f5(x);
+        out[1] = f14(x);
+    };
+    let mut config = IntegrationConfig::default();
+    config.tolerance = Relative(1e-10);
+    config.points = Points::from(&pts[..]);
+
+    let result = QAGPVec::new()
+        .integrate(&mut f, 2, &Range::new(0.0, 3.0).unwrap(), &config)
+        .unwrap();
+    for (k, &g) in [f5 as fn(f64) -> f64, f14].iter().enumerate() {
+        let expect = Integrator::with_algorithm(g, QAGP::new())
+            .tolerance(Relative(1e-12))
+            .points(&pts)
+            .run(0.0..3.0)
+            .unwrap()
+            .estimate;
+        assert_rel!(result.estimate[k], expect, 1e-10);
+        assert!(result.delta[k] <= 1e-10 * expect.abs());
+    }
+}
+
+#[test]
+fn qags_complex_exp() {
+    let mut f = |x: f64| (x.cos(), x.sin());
+    let mut config = IntegrationConfig::default();
+    config.tolerance = Relative(1e-10);
+
+    let result = QAGS::new()
+        .integrate_complex(&mut f, &Range::new(0.0, 1.0).unwrap(), &config)
+        .unwrap();
+    assert_rel!(result.estimate.0, 1f64.sin(), 1e-14);
+    assert_rel!(result.estimate.1, 1. - 1f64.cos(), 1e-14);
+    assert_eq!(result.nevals, 25);
+}
+
+#[test]
+fn qagp_complex_singular() {
+    let pts = [0.3];
+    let g = |x: f64| 1. / (x - 0.3).abs().sqrt();
+    let mut f = |x: f64| (g(x) * (10. * x).cos(), g(x) * (10. * x).sin());
+    let mut config = IntegrationConfig::default();
+    config.tolerance = Relative(1e-10);
+    config.points = Points::from(&pts[..]);
+
+    let result = QAGP::new()
+        .integrate_complex(&mut f, &Range::new(0.0, 1.0).unwrap(), &config)
+        .unwrap();
+    let re = Integrator::with_algorithm(|x: f64| g(x) * (10. * x).cos(), QAGP::new())
+        .tolerance(Relative(1e-12))
+        .points(&pts)
+        .run(0.0..1.0)
+        .unwrap();
+    let im = Integrator::with_algorithm(|x: f64| g(x) * (10. * x).sin(), QAGP::new())
+        .tolerance(Relative(1e-12))
+        .points(&pts)
+        .run(0.0..1.0)
+        .unwrap();
+    assert_rel!(result.estimate.0, re.estimate, 1e-10);
+    assert_rel!(result.estimate.1, im.estimate, 1e-10);
+    assert!(result.nevals < re.nevals + im.nevals);
+}
+
+#[test]
+fn qags_f32_singular() {
+    let result = Integrator::with_algorithm(|x: f32| x.ln() / x.sqrt(), QAGS::new())
+        .tolerance(Relative(1e-5))
+        .run(0.0..1.0)
+        .unwrap();
+    assert_rel!(result.estimate, -4.0, 1e-5);
+    assert!(result.delta <= 4e-5);
+}
+
+#[test]
+fn qagp_f32_f5() {
+    let f = |x: f32| {
+        let x2 = x * x;
+        x2 * x * (((x2 - 1.) * (x2 - 2.)).abs()).ln()
+    };
+    let expect = Integrator::with_algorithm(f5, QAGP::new())
+        .tolerance(Relative(1e-10))
+        .points(&[1., 2f64.sqrt()])
+        .run(0.0..3.0)
+        .unwrap();
+    let result = Integrator::with_algorithm(f, QAGP::new())
+        .tolerance(Relative(1e-4))
+        .points(&[1., 2f32.sqrt()])
+        .run(0.0..3.0)
+        .unwrap();
+    assert_rel!(result.estimate as f64, expect.estimate, 1e-4);
+}
+
+#[test]
+fn qags_double_double() {
+    let dd = DoubleDouble::from_f64;
+    let pi = DoubleDouble::new(std::f64::consts::PI, 1.2246467991473532e-16);
+    let range = Range::new(dd(0.0), dd(1.0)).unwrap();
+
+    // smooth integrand
+    let result =
+        Integrator::with_algorithm(|x: DoubleDouble| dd(4.0) / (dd(1.0) + x * x), QAGS::new())
+            .tolerance(Relative(dd(1e-28)))
+            .run(&range)
+            .unwrap();
+    assert!((result.estimate - pi).abs() < dd(1e-28));
+
+    // singular integrand which requires the extrapolation
+    let result = Integrator::with_algorithm(|x: DoubleDouble| dd(1.0) / x.sqrt(), QAGS::new())
+        .tolerance(Relative(dd(1e-25)))
+        .max_evals(10000)
+        .run(&range)
+        .unwrap();
+    assert!((result.estimate - dd(2.0)).abs() < dd(2e-25));
+}
+
+fn check_try_integrate<F>(run: F)
+where
+    F: FnOnce(
+        &mut dyn FnMut(f64) -> Result<f64, &'static str>,
+    ) -> TryIntegrationResult<&'static str>,
+{
+    let mut calls = 0;
+    let mut failed = false;
+    let mut f = |x: f64| {
+        assert!(!failed, "integrand was called after the error");
+        calls += 1;
+        if x > 0.7 {
+            failed = true;
+            Err("failed")
+        } else {
+            Ok(x.exp())
+        }
+    };
+
+    let err = run(&mut f).unwrap_err();
+    assert_eq!(err.error, "failed");
+    assert!(err.x > 0.7);
+    assert_eq!(err.partial.nevals, calls);
+}
+
+#[test]
+fn try_integrate_stops_at_first_error() {
+    let range = Range::new(0.0, 1.0).unwrap();
+    let config = IntegrationConfig::default();
+
+    check_try_integrate(|mut f| QAG::new().try_integrate(&mut f, &range, &config));
+    check_try_integrate(|mut f| QAGS::new().try_integrate(&mut f, &range, &config));
+    check_try_integrate(|mut f| QAGP::new().try_integrate(&mut f, &range, &config));
+    check_try_integrate(|mut f| QNG::new().try_integrate(&mut f, &range, &config));
+    #[cfg(feature = "std")]
+    check_try_integrate(|mut f| CQUAD::new().try_integrate(&mut f, &range, &config));
+    #[cfg(feature = "std")]
+    check_try_integrate(|mut f| TanhSinh::new().try_integrate(&mut f, &range, &config));
+    check_try_integrate(|mut f| GaussLobatto::new().try_integrate(&mut f, &range, &config));
+    check_try_integrate(|mut f| AUTO::new().try_integrate(&mut f, &range, &config));
+}
+
+#[test]
+fn try_integrate_ok() {
+    let range = Range::new(0.0, 1.0).unwrap();
+    let config = IntegrationConfig::default();
+    let mut f = |x: f64| Ok::<_, ()>(x.exp());
+
+    let result = QAGS::new()
+        .try_integrate(&mut f, &range, &config)
+        .unwrap()
+        .unwrap();
+    let expect = QAGS::new()
+        .integrate(&mut |x: f64| x.exp(), &range, &config)
+        .unwrap();
+    assert_eq!(result, expect);
+}
+
+#[test]
+fn try_integrate_batch() {
+    struct Batch {
+        sizes: Vec<usize>,
+        calls: usize,
+        limit: f64,
+    }
+
+    impl TryIntegrand for Batch {
+        type Error = &'static str;
+
+        fn try_apply(&mut self, _: f64) -> Result<f64, &'static str> {
+            unreachable!("the integrand must be evaluated in batches")
+        }
+
+        fn try_apply_to_slice(&mut self, s: &mut [f64]) -> Result<(), (usize, &'static str)> {
+            self.sizes.push(s.len());
+            for (i, x) in s.iter_mut().enumerate() {
+                self.calls += 1;
+                if *x > self.limit {
+                    return Err((i, "failed"));
+                }
+                *x = x.exp();
+            }
+            Ok(())
+        }
+    }
+
+    let range = Range::new(0.0, 1.0).unwrap();
+    let config = IntegrationConfig::default();
+
+    let mut f = Batch {
+        sizes: Vec::new(),
+        calls: 0,
+        limit: std::f64::INFINITY,
+    };
+    let result = QAGS::new()
+        .try_integrate(&mut f, &range, &config)
+        .unwrap()
+        .unwrap();
+    let expect = QAGS::new()
+        .integrate(&mut |x: f64| x.exp(), &range, &config)
+        .unwrap();
+    assert_eq!(result, expect);
+    assert!(f.sizes.iter().all(|&n| n >= 17));
+
+    // the evaluation stops in the middle of the first batch
+    let mut f = Batch {
+        sizes: Vec::new(),
+        calls: 0,
+        limit: 0.7,
+    };
+    let err = QAGS::new()
+        .try_integrate(&mut f, &range, &config)
+        .unwrap_err();
+    assert_eq!(err.error, "failed");
+    assert!(err.x > 0.7);
+    assert_eq!(f.sizes.len(), 1);
+    assert!(f.calls < f.sizes[0]);
+    assert_eq!(err.partial.nevals, f.calls);
+}
+
+#[test]
+fn qags_batch_integrand() {
+    for &(a, b) in &[(0.0, 1.0), (0.0, std::f64::INFINITY)] {
+        let mut sizes = Vec::new();
+        let f = BatchIntegrand::new(|s: &mut [f64]| {
+            sizes.push(s.len());
+            s.iter_mut().for_each(|x| *x = (-*x).exp() * x.sqrt());
+        });
+        let result = Integrator::with_algorithm(f, QAGS::new())
+            .tolerance(Relative(1e-10))
+            .run(a..b)
+            .unwrap();
+        let expect = Integrator::with_algorithm(|x: f64| (-x).exp() * x.sqrt(), QAGS::new())
+            .tolerance(Relative(1e-10))
+            .run(a..b)
+            .unwrap();
+        assert_eq!(result, expect);
+
+        // each rule is evaluated in a single call
+        assert!(sizes.len() > 1);
+        assert!(sizes.iter().all(|&n| n >= 17));
+        assert_eq!(sizes.iter().sum::<usize>(), result.nevals);
+    }
+}
+
+#[cfg(feature = "rayon")]
+#[test]
+fn parallel_qag_thread_independent() {
+    let f = |x: f64| (x - 0.3).abs().ln() * (5. * x).cos();
+    let run = |threads: usize| {
+        let pool = rayon::ThreadPoolBuilder::new()
+            .num_threads(threads)
+            .build()
+            .unwrap();
+        pool.install(|| {
+            Integrator::with_algorithm(f, ParallelQAG::new())
+                .tolerance(Relative(1e-10))
+                .max_evals(20000)
+                .points(&[0.3])
+                .run(0.0..1.0)
+                .unwrap()
+        })
+    };
+
+    let result = run(1);
+    for &threads in &[2, 3, 8] {
+        assert_eq!(run(threads), result);
+    }
+
+    let expect = Integrator::with_algorithm(f, QAGP::new())
+        .tolerance(Relative(1e-12))
+        .points(&[0.3])
+        .run(0.0..1.0)
+        .unwrap();
+    assert_rel!(result.estimate, expect.estimate, 1e-10);
+}
+
+#[cfg(feature = "rayon")]
+#[test]
+fn parallel_qag_infinite_range() {
+    let result = Integrator::with_algorithm(|x: f64| (-x * x).exp(), ParallelQAG::new())
+        .tolerance(Relative(1e-12))
+        .run(std::f64::NEG_INFINITY..std::f64::INFINITY)
+        .unwrap();
+    assert_rel!(result.estimate, std::f64::consts::PI.sqrt(), 1e-12);
+
+    // bisecting one subrange at a time
+    let result = Integrator::with_algorithm(f16, ParallelQAG::new().batch_size(1))
+        .tolerance(Relative(1e-10))
+        .run(0.0..std::f64::INFINITY)
+        .unwrap();
+    let expect = Integrator::with_algorithm(f16, QAGS::new())
+        .tolerance(Relative(1e-12))
+        .run(0.0..std::f64::INFINITY)
+        .unwrap();
+    assert_rel!(result.estimate, expect.estimate, 1e-10);
+}
@@ -0,0 +1,171 @@
+#![allow(dead_code)]
+
+//! Minimal double-double arithmetic, used to test the generic floating-point
+//! support with the type more precise than `f64`
+
+use core::ops::{Add, AddAssign, Div, DivAssign, Mul, MulAssign, Neg, Sub, SubAssign};
+
+use gkquad::Float;
+
+/// unevaluated sum `hi + lo` where `|lo| <= ulp(hi) / 2`
+#[derive(Clone, Copy, Debug, PartialEq, PartialOrd)]
+pub struct DoubleDouble {
+    pub hi: f64,
+    pub lo: f64,
+}
+
+impl DoubleDouble {
+    pub const fn new(hi: f64, lo: f64) -> Self {
+        Self { hi, lo }
+    }
+}
+
+#[inline]
+fn two_sum(a: f64, b: f64) -> (f64, f64) {
+    let s = a + b;
+    let bb = s - a;
+    (s, (a - (s - bb)) + (b - bb))
+}
+
+#[inline]
+fn quick_two_sum(a: f64, b: f64) -> DoubleDouble {
+    let s = a + b;
+    DoubleDouble::new(s, b - (s - a))
+}
+
+impl Add for DoubleDouble {
+    type Output = Self;
+
+    fn add(self, other: Self) -> Self {
+        let (s, e) = two_sum(self.hi, other.hi);
+        let (t, f) = two_sum(self.lo, other.lo);
+        let r = quick_two_sum(s, e + t);
+        quick_two_sum(r.hi, r.lo + f)
+    }
+}
+
+impl Neg for DoubleDouble {
+    type Output = Self;
+
+    fn neg(self) -> Self {
+        Self::new(-self.hi, -self.lo)
+    }
+}
+
+impl Sub for DoubleDouble {
+    type Output = Self;
+
+    fn sub(self, other: Self) -> Self {
+        self + (-other)
+    }
+}
+
+impl Mul for DoubleDouble {
+    type Output = Self;
+
+    fn mul(self, other: Self) -> Self {
+        let p = self.hi * other.hi;
+        let e = self.hi.mul_add(other.hi, -p);
+        quick_two_sum(p, e + (self.hi * other.lo + self.lo * other.hi))
+    }
+}
+
+impl Div for DoubleDouble {
+    type Output = Self;
+
+    fn div(self, other: Self) -> Self {
+        let q1 = self.hi / other.hi;
+        let r = self - other * Self::from_f64(q1);
+        let q2 = r.hi / other.hi;
+        let r = r - other * Self::from_f64(q2);
+        let q3 = r.hi / other.hi;
+        quick_two_sum(q1, q2) + Self::from_f64(q3)
+    }
+}
+
+macro_rules! impl_assign {
+    ($($trait:ident $method:ident $op:tt),*) => {
+        $(
+            impl $trait for DoubleDouble {
+                fn $method(&mut self, other: Self) {
+                    *self = *self $op other;
+                }
+            }
+        )*
+    };
+}
+
+impl_assign!(AddAssign add_assign +, SubAssign sub_assign -, MulAssign mul_assign *, DivAssign div_assign /);
+
+impl Float for DoubleDouble {
+    const ZERO: Self = Self::new(0.0, 0.0);
+    const ONE: Self = Self::new(1.0, 0.0);
+    // 2^-104
+    const EPSILON: Self = Self::new(4.930380657631324e-32, 0.0);
+    const MIN_POSITIVE: Self = Self::new(std::f64::MIN_POSITIVE, 0.0);
+    const MAX: Self = Self::new(std::f64::MAX, 0.0);
+    const INFINITY: Self = Self::new(std::f64::INFINITY, 0.0);
+    const NEG_INFINITY: Self = Self::new(std::f64::NEG_INFINITY, 0.0);
+
+    fn from_f64(x: f64) -> Self {
+        Self::new(x, 0.0)
+    }
+
+    fn to_f64(self) -> f64 {
+        self.hi + self.lo
+    }
+
+    /// parse the literal without exponent
+    fn parse_literal(s: &str) -> Self {
+        let ten = Self::from_f64(10.0);
+        let (negative, s) = match s.strip_prefix('-') {
+            Some(s) => (true, s),
+            None => (false, s),
+        };
+
+        let mut value = Self::ZERO;
+        let mut scale = Self::ONE;
+        let mut fraction = false;
+        for c in s.chars() {
+            if c == '.' {
+                fraction = true;
+                continue;
+            }
+
+            let digit = c.to_digit(10).expect("invalid floating-point literal");
+            value = value * ten + Self::from_f64(digit as f64);
+            if fraction {
+                scale *= ten;
+            }
+        }
+
+        let value = value / scale;
+        if negative {
+            -value
+        } else {
+            value
+        }
+    }
+
+    fn abs(self) -> Self {
+        if self.hi < 0.0 {
+            -self
+        } else {
+            self
+        }
+    }
+
+    fn sqrt(self) -> Self {
+        if self.hi <= 0.0 {
+            return Self::from_f64(self.hi.sqrt());
+        }
+
+        // one Newton iteration doubles the precision
+        let y = Self::from_f64(self.hi.sqrt());
+        y + (self - y * y) / (y * Self::from_f64(2.0))
+    }
+
+    fn is_nan(self) -> bool {
+        self.hi.is_nan()
+    }
+}
@@ -50,6 +50,11 @@ pub fn g4(x: f64, y: f64) -> f64 {
     1.0 / (1.0 + x + y).powi(3)
 }
 
+// gaussian centered far from the origin
+pub fn g5(x: f64, y: f64) -> f64 {
+    f16(x) * f16(y)
+}
+
 // singular points in (0.0, 0.0)
 pub fn gp1(x: f64, y: f64) -> f64 {
     1.0 / f64::sqrt(f64::abs(x) + f64::abs(y))
@@ -62,3 +67,62 @@ pub fn gp2(x: f64, y: f64) -> f64 {
 pub fn gp3(x: f64, y: f64) -> f64 {
     0.5 / f64::sqrt(x * x + y * y)
 }
+
+// integrated with the weight function sin(10πx)
+pub fn f8(x: f64) -> f64 {
+    if x == 0.0 {
+        0.0
+    } else {
+        x.ln()
+    }
+}
+
+// integrated with the weight function cos(πx/2) over [0, ∞)
+pub fn f9(x: f64) -> f64 {
+    if x == 0.0 {
+        0.0
+    } else {
+        1. / x.sqrt()
+    }
+}
+
+// integrated with the weight function log(x) over [0, 1]
+pub fn f10(x: f64) -> f64 {
+    if x == 0.0 {
+        0.0
+    } else {
+        let u = x.ln();
+        let v = 1. + u * u;
+        1. / (v * v)
+    }
+}
+
+// integrated with the weight function 1/x
+pub fn f11(x: f64) -> f64 {
+    1. / (5. * x * x * x + 6.)
+}
+
+// integrated over (-∞, ∞)
+pub fn f12(x: f64) -> f64 {
+    (-x * x).exp()
+}
+
+// integrated with the weight functions e^-x and e^-x²
+pub fn f13(x: f64) -> f64 {
+    x.cos()
+}
+
+// kink at x = 1/3
+pub fn f14(x: f64) -> f64 {
+    (x - 1. / 3.).abs()
+}
+
+// slowly decaying function integrated over infinite range
+pub fn f15(x: f64) -> f64 {
+    (1. + x * x).powf(-0.6)
+}
+
+// gaussian centered far from the origin
+pub fn f16(x: f64) -> f64 {
+    (-(x - 1e4) * (x - 1e4) / 100.).exp()
+}
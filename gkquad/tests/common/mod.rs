@@ -1,4 +1,5 @@
 #[macro_use]
 mod macros;
 
+pub mod double_double;
 pub mod functions;
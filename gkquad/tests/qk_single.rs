@@ -2,7 +2,10 @@
 mod common;
 use common::functions::*;
 
-use gkquad::single::{qk17, qk25, qk33, qk41, qk49, qk57, QKResult, Range};
+use gkquad::single::{
+    qk17, qk25, qk33, qk41, qk49, qk57, qk_with_rule, GaussKronrod, GaussKronrodRule, GaussRule,
+    IntegrationConfig, QKResult, Range,
+};
 
 struct Expect {
     estimate: f64,
@@ -322,3 +325,176 @@ fn qk57_f3() {
         },
     );
 }
+
+#[test]
+fn qk_with_rule_builtin() {
+    let builtin: [(usize, fn(&mut fn(f64) -> f64, &Range) -> QKResult); 6] = [
+        (17, qk17),
+        (25, qk25),
+        (33, qk33),
+        (41, qk41),
+        (49, qk49),
+        (57, qk57),
+    ];
+
+    let mut f: fn(f64) -> f64 = f3;
+    let range = Range::new(0.3, 2.71).unwrap();
+
+    for &(n, qk) in builtin.iter() {
+        let rule = GaussKronrodRule::new(n).unwrap();
+        assert_eq!(rule.points(), n);
+
+        let expect = qk(&mut f, &range);
+        let result = qk_with_rule(&mut f, &range, &rule);
+        assert_rel!(result.estimate, expect.estimate, 1e-14);
+        assert_rel!(result.absvalue, expect.absvalue, 1e-14);
+        assert_rel!(result.asc, expect.asc, 1e-14);
+    }
+}
+
+#[test]
+fn gauss_kronrod_rule_odd_gauss() {
+    // 3-point Gauss-Legendre rule is the Kronrod extension of the midpoint rule
+    let rule = GaussKronrodRule::new(3).unwrap();
+    assert_rel!(rule.abscissae()[0], 0.6f64.sqrt(), 1e-15);
+    assert_rel!(rule.kronrod_weights()[0], 5.0 / 9.0, 1e-15);
+    assert_rel!(rule.center_weight(), 8.0 / 9.0, 1e-15);
+    assert_eq!(rule.gauss_weights().len(), 1);
+    assert_rel!(rule.gauss_weights()[0], 2.0, 1e-15);
+
+    // QUADPACK qk15
+    let xgk = [
+        0.207784955007898467600689403773245,
+        0.405845151377397166906606412076961,
+        0.586087235467691130294144845693013,
+        0.741531185599394439863864773280788,
+        0.864864423359769072789712788640926,
+        0.949107912342758524526189684047851,
+        0.991455371120812639206854697526329,
+    ];
+    let wgk = [
+        0.204432940075298892414161999234649,
+        0.190350578064785409913256402421014,
+        0.169004726639267902826583426598550,
+        0.140653259715525918745189590510238,
+        0.104790010322250183839876322541518,
+        0.063092092629978553290700663189204,
+        0.022935322010529224963732008058970,
+    ];
+    let wg = [
+        0.381830050505118944950369775488975,
+        0.279705391489276667901467771423780,
+        0.129484966168869693270611432679082,
+        0.417959183673469387755102040816327,
+    ];
+
+    let rule = GaussKronrodRule::new(15).unwrap();
+    assert_eq!(rule.points(), 15);
+    for (&x, &y) in rule.abscissae().iter().zip(xgk.iter()) {
+        assert_rel!(x, y, 1e-14);
+    }
+    for (&x, &y) in rule.kronrod_weights().iter().zip(wgk.iter()) {
+        assert_rel!(x, y, 1e-14);
+    }
+    for (&x, &y) in rule.gauss_weights().iter().zip(wg.iter()) {
+        assert_rel!(x, y, 1e-14);
+    }
+    assert_rel!(
+        rule.center_weight(),
+        0.209482141084727828012999174891714,
+        1e-14
+    );
+
+    // the 7-point Gauss rule is exact for the polynomial of degree 13
+    let mut f = |x: f64| x.powi(12);
+    let result = qk_with_rule(&mut f, &Range::new(-1.0, 1.0).unwrap(), &rule);
+    assert_rel!(result.estimate, 2.0 / 13.0, 1e-14);
+    assert!(result.delta < 1e-14);
+
+    let mut f = |x: f64| x.cos();
+    let result = qk_with_rule(&mut f, &Range::new(0.0, 1.0).unwrap(), &rule);
+    assert_rel!(result.estimate, 1f64.sin(), 1e-15);
+}
+
+#[test]
+fn gauss_kronrod_rule_invalid() {
+    assert!(GaussKronrodRule::new(1).is_none());
+    assert!(GaussKronrodRule::new(2).is_none());
+    assert!(GaussKronrodRule::new(20).is_none());
+}
+
+#[test]
+#[cfg(feature = "std")]
+fn gauss_rule_jacobi_on() {
+    // ∫ (5-x)^0.5 (x-2)^-0.5 x² dx (2 < x < 5)
+    let expect = 12.1875 * std::f64::consts::PI;
+
+    let rule = GaussRule::jacobi_on(5, 0.5, -0.5, &Range::new(2.0, 5.0).unwrap());
+    assert!(rule.nodes().iter().all(|&x| 2.0 < x && x < 5.0));
+    assert_rel!(rule.integrate(&mut |x: f64| x * x), expect, 1e-14);
+
+    let rule = GaussRule::jacobi_on(5, 0.5, -0.5, &Range::new(5.0, 2.0).unwrap());
+    assert_rel!(rule.integrate(&mut |x: f64| x * x), -expect, 1e-14);
+}
+
+#[test]
+fn gauss_rule_from_moments() {
+    // moments of the weight function 1 over [2, 3]
+    let moments: Vec<f64> = (1..=8)
+        .map(|k| (3f64.powi(k) - 2f64.powi(k)) / k as f64)
+        .collect();
+    let rule = GaussRule::from_moments(&moments, &Range::new(2.0, 3.0).unwrap()).unwrap();
+    assert_eq!(rule.len(), 4);
+
+    // exact for the polynomials of degree 7
+    let result = rule.integrate(&mut |x: f64| x.powi(7));
+    assert_rel!(result, (3f64.powi(8) - 2f64.powi(8)) / 8., 1e-12);
+
+    assert!(GaussRule::from_moments(&[1.0], &Range::new(2.0, 3.0).unwrap()).is_none());
+    assert!(GaussRule::from_moments(&[-1.0, 0.0], &Range::new(2.0, 3.0).unwrap()).is_none());
+}
+
+#[test]
+fn gauss_rule_from_moments_inconsistent() {
+    // moments of the weight function 1 over [0, 1]
+    let range = Range::new(0.0, 1.0).unwrap();
+    let mut moments: Vec<f64> = (1..=6).map(|k| 1. / k as f64).collect();
+    assert_eq!(GaussRule::from_moments(&moments, &range).unwrap().len(), 3);
+
+    // the nodes would lie outside the range
+    moments[5] = 7.0;
+    assert!(GaussRule::from_moments(&moments, &range).is_none());
+
+    moments[5] = std::f64::INFINITY;
+    assert!(GaussRule::from_moments(&moments, &range).is_none());
+
+    moments[5] = std::f64::NAN;
+    assert!(GaussRule::from_moments(&moments, &range).is_none());
+}
+
+#[test]
+fn gauss_rule_from_weight() {
+    let config = IntegrationConfig::default();
+    let range = Range::new(0.0, 1.0).unwrap();
+    let rule = GaussRule::from_weight(10, |x: f64| x.sqrt(), &range, &config).unwrap();
+
+    // ∫ √x cos(x) dx (0 < x < 1)
+    let result = rule.integrate(&mut |x: f64| x.cos());
+    assert_rel!(result, 0.5312026830845154, 1e-12);
+
+    let range = Range::new(0.0, std::f64::INFINITY).unwrap();
+    assert!(GaussRule::from_weight(10, |x: f64| x.sqrt(), &range, &config).is_none());
+}
+
+#[test]
+fn qk25_complex() {
+    let range = Range::new(0.3, 2.71).unwrap();
+    let (re, im) = GaussKronrod::K25.integrate_complex(&mut |x: f64| (f3(x), f1(x)), &range);
+    let re_expect = qk25(&mut (f3 as fn(f64) -> f64), &range);
+    let im_expect = qk25(&mut (f1 as fn(f64) -> f64), &range);
+
+    assert_rel!(re.estimate, re_expect.estimate, 1e-15);
+    assert_rel!(re.delta, re_expect.delta, 1e-7);
+    assert_rel!(im.estimate, im_expect.estimate, 1e-15);
+    assert_rel!(im.delta, im_expect.delta, 1e-7);
+}